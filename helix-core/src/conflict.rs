@@ -0,0 +1,169 @@
+//! Detection of git merge conflict markers.
+
+use crate::RopeSlice;
+
+/// A git merge conflict, stored as the lines its markers are found on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict {
+    /// Line of the `<<<<<<<` marker introducing "our" side.
+    pub start: usize,
+    /// Line of the `|||||||` marker introducing the common base, only
+    /// present for diff3 style conflicts.
+    pub base: Option<usize>,
+    /// Line of the `=======` marker separating the two sides.
+    pub separator: usize,
+    /// Line of the closing `>>>>>>>` marker.
+    pub end: usize,
+}
+
+impl Conflict {
+    /// Char range covering the whole conflict, marker lines included.
+    pub fn range(&self, text: RopeSlice) -> std::ops::Range<usize> {
+        text.line_to_char(self.start)..text.line_to_char(self.end + 1)
+    }
+
+    /// Char range of the lines making up "our" side of the conflict.
+    pub fn ours(&self, text: RopeSlice) -> std::ops::Range<usize> {
+        let until = self.base.unwrap_or(self.separator);
+        text.line_to_char(self.start + 1)..text.line_to_char(until)
+    }
+
+    /// Char range of the common base lines, if this is a diff3 style conflict.
+    pub fn base(&self, text: RopeSlice) -> Option<std::ops::Range<usize>> {
+        self.base
+            .map(|base| text.line_to_char(base + 1)..text.line_to_char(self.separator))
+    }
+
+    /// Char range of the lines making up "their" side of the conflict.
+    pub fn theirs(&self, text: RopeSlice) -> std::ops::Range<usize> {
+        text.line_to_char(self.separator + 1)..text.line_to_char(self.end)
+    }
+
+    pub fn contains_line(&self, line: usize) -> bool {
+        (self.start..=self.end).contains(&line)
+    }
+}
+
+/// Whether the line consists of `marker` repeated seven times, optionally
+/// followed by a label, which is how git writes conflict markers.
+fn is_marker(line: RopeSlice, marker: char) -> bool {
+    if line.len_chars() < 7 || line.chars().take(7).any(|c| c != marker) {
+        return false;
+    }
+    matches!(line.get_char(7), None | Some(' ' | '\r' | '\n'))
+}
+
+/// Scan `text` for git conflict markers, in both merge and diff3 style.
+/// Markers that do not form a complete conflict are ignored.
+pub fn find_conflicts(text: RopeSlice) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    let mut start = None;
+    let mut base = None;
+    let mut separator = None;
+
+    for (line_idx, line) in text.lines().enumerate() {
+        if is_marker(line, '<') {
+            start = Some(line_idx);
+            base = None;
+            separator = None;
+        } else if is_marker(line, '|') {
+            if start.is_some() && separator.is_none() {
+                base = Some(line_idx);
+            }
+        } else if is_marker(line, '=') {
+            if start.is_some() && separator.is_none() {
+                separator = Some(line_idx);
+            }
+        } else if is_marker(line, '>') {
+            if let (Some(start_line), Some(separator_line)) = (start, separator) {
+                conflicts.push(Conflict {
+                    start: start_line,
+                    base,
+                    separator: separator_line,
+                    end: line_idx,
+                });
+            }
+            start = None;
+            base = None;
+            separator = None;
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Rope;
+
+    #[test]
+    fn merge_style_conflict() {
+        let text = Rope::from(concat!(
+            "fn main() {\n",
+            "<<<<<<< HEAD\n",
+            "    println!(\"ours\");\n",
+            "=======\n",
+            "    println!(\"theirs\");\n",
+            ">>>>>>> feature\n",
+            "}\n",
+        ));
+        let conflicts = find_conflicts(text.slice(..));
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                start: 1,
+                base: None,
+                separator: 3,
+                end: 5,
+            }]
+        );
+
+        let text = text.slice(..);
+        let conflict = conflicts[0];
+        assert_eq!(
+            text.slice(conflict.ours(text)).to_string(),
+            "    println!(\"ours\");\n"
+        );
+        assert_eq!(
+            text.slice(conflict.theirs(text)).to_string(),
+            "    println!(\"theirs\");\n"
+        );
+        assert_eq!(conflict.base(text), None);
+    }
+
+    #[test]
+    fn diff3_style_conflict() {
+        let text = Rope::from(concat!(
+            "<<<<<<< HEAD\n",
+            "ours\n",
+            "||||||| merged common ancestors\n",
+            "base\n",
+            "=======\n",
+            "theirs\n",
+            ">>>>>>> feature\n",
+        ));
+        let text = text.slice(..);
+        let conflicts = find_conflicts(text);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = conflicts[0];
+        assert_eq!(conflict.base, Some(2));
+        assert_eq!(text.slice(conflict.ours(text)).to_string(), "ours\n");
+        assert_eq!(
+            text.slice(conflict.base(text).unwrap()).to_string(),
+            "base\n"
+        );
+        assert_eq!(text.slice(conflict.theirs(text)).to_string(), "theirs\n");
+    }
+
+    #[test]
+    fn ignores_incomplete_markers_and_separator_lookalikes() {
+        let text = Rope::from(concat!(
+            "Title\n",
+            "=======\n",
+            "<<<<<<< HEAD\n",
+            "unterminated\n",
+        ));
+        assert!(find_conflicts(text.slice(..)).is_empty());
+    }
+}
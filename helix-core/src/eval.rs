@@ -0,0 +1,199 @@
+//! A small arithmetic expression evaluator backing the `:eval` command.
+//!
+//! Supports `+`, `-`, `*`, `/`, `%`, `^` (exponentiation), unary minus and
+//! parentheses over floating point numbers. All computation is done in `f64`.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    /// An unexpected character at the given byte offset.
+    UnexpectedChar(usize),
+    /// The expression ended where an operand was expected.
+    UnexpectedEnd,
+    /// A numeric literal that does not parse as `f64`.
+    InvalidNumber(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedChar(pos) => write!(f, "unexpected character at offset {}", pos),
+            Self::UnexpectedEnd => f.write_str("unexpected end of expression"),
+            Self::InvalidNumber(num) => write!(f, "invalid number '{}'", num),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// Evaluate `expr` as an arithmetic expression.
+pub fn eval(expr: &str) -> Result<f64, EvalError> {
+    let mut parser = Parser {
+        input: expr.as_bytes(),
+        pos: 0,
+    };
+    let value = parser.expression()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.input.len() {
+        return Err(EvalError::UnexpectedChar(parser.pos));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .input
+            .get(self.pos)
+            .map_or(false, u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.pos).copied()
+    }
+
+    /// expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<f64, EvalError> {
+        let mut value = self.term()?;
+        loop {
+            match self.peek() {
+                Some(b'+') => {
+                    self.pos += 1;
+                    value += self.term()?;
+                }
+                Some(b'-') => {
+                    self.pos += 1;
+                    value -= self.term()?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    /// term := power (('*' | '/' | '%') power)*
+    fn term(&mut self) -> Result<f64, EvalError> {
+        let mut value = self.power()?;
+        loop {
+            match self.peek() {
+                Some(b'*') => {
+                    self.pos += 1;
+                    value *= self.power()?;
+                }
+                Some(b'/') => {
+                    self.pos += 1;
+                    value /= self.power()?;
+                }
+                Some(b'%') => {
+                    self.pos += 1;
+                    value %= self.power()?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    /// power := unary ('^' power)?, right associative
+    fn power(&mut self) -> Result<f64, EvalError> {
+        let base = self.unary()?;
+        if self.peek() == Some(b'^') {
+            self.pos += 1;
+            let exponent = self.power()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    /// unary := '-' unary | primary
+    fn unary(&mut self) -> Result<f64, EvalError> {
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+            return Ok(-self.unary()?);
+        }
+        self.primary()
+    }
+
+    /// primary := number | '(' expression ')'
+    fn primary(&mut self) -> Result<f64, EvalError> {
+        match self.peek() {
+            Some(b'(') => {
+                self.pos += 1;
+                let value = self.expression()?;
+                match self.peek() {
+                    Some(b')') => {
+                        self.pos += 1;
+                        Ok(value)
+                    }
+                    Some(_) => Err(EvalError::UnexpectedChar(self.pos)),
+                    None => Err(EvalError::UnexpectedEnd),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == b'.' => {
+                let start = self.pos;
+                while self
+                    .input
+                    .get(self.pos)
+                    .map_or(false, |c| c.is_ascii_digit() || *c == b'.' || *c == b'_')
+                {
+                    self.pos += 1;
+                }
+                // `input` only ever advances over ASCII here
+                let literal = std::str::from_utf8(&self.input[start..self.pos])
+                    .unwrap()
+                    .replace('_', "");
+                literal
+                    .parse()
+                    .map_err(|_| EvalError::InvalidNumber(literal))
+            }
+            Some(_) => Err(EvalError::UnexpectedChar(self.pos)),
+            None => Err(EvalError::UnexpectedEnd),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(eval("10 % 4"), Ok(2.0));
+        assert_eq!(eval("7 / 2"), Ok(3.5));
+    }
+
+    #[test]
+    fn test_unary_minus() {
+        assert_eq!(eval("-3 + 5"), Ok(2.0));
+        assert_eq!(eval("2 * -3"), Ok(-6.0));
+        assert_eq!(eval("--4"), Ok(4.0));
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(eval(".5 * 4"), Ok(2.0));
+        assert_eq!(eval("1_000 + 24"), Ok(1024.0));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(eval(""), Err(EvalError::UnexpectedEnd));
+        assert_eq!(eval("(1 + 2"), Err(EvalError::UnexpectedEnd));
+        assert_eq!(eval("1 + x"), Err(EvalError::UnexpectedChar(4)));
+        assert_eq!(
+            eval("1.2.3"),
+            Err(EvalError::InvalidNumber("1.2.3".to_string()))
+        );
+    }
+}
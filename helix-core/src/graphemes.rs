@@ -105,11 +105,16 @@ pub fn grapheme_width(g: &str) -> usize {
         // checking for ascii range values only, that works.
         1
     } else {
+        // Clusters composed with zero-width joiners (e.g. 🤦🏼‍♂️, taken from
+        // https://hsivonen.fi/string-length/) render as a single two-cell
+        // emoji in terminals, but summing the width of every codepoint
+        // overcounts them and makes the cursor drift.
+        if g.contains('\u{200D}') {
+            return 2;
+        }
         // We use max(1) here because all grapeheme clusters--even illformed
         // ones--should have at least some width so they can be edited
         // properly.
-        // TODO properly handle unicode width for all codepoints
-        // example of where unicode width is currently wrong: 🤦🏼‍♂️ (taken from https://hsivonen.fi/string-length/)
         UnicodeWidthStr::width(g).max(1)
     }
 }
@@ -114,6 +114,22 @@ impl History {
         self.current
     }
 
+    /// Number of revisions in the history, including the root revision.
+    #[inline]
+    pub fn revision_count(&self) -> usize {
+        self.revisions.len()
+    }
+
+    /// The timestamp the given revision was committed at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the revision does not exist.
+    #[inline]
+    pub fn revision_timestamp(&self, revision: usize) -> Instant {
+        self.revisions[revision].timestamp
+    }
+
     #[inline]
     pub const fn at_root(&self) -> bool {
         self.current == 0
@@ -134,6 +150,20 @@ impl History {
         down_txns.chain(up_txns).reduce(|acc, tx| tx.compose(acc))
     }
 
+    /// Returns the changes needed to take the document from the current
+    /// revision to the given one, composed into a transaction. This is the
+    /// opposite direction of [`Self::changes_since`] and does not move the
+    /// current revision.
+    pub fn changes_until(&self, revision: usize) -> Option<Transaction> {
+        let lca = self.lowest_common_ancestor(self.current, revision);
+        let up = self.path_up(self.current, lca);
+        let down = self.path_up(revision, lca);
+        let up_txns = up.iter().rev().map(|&n| self.revisions[n].inversion.clone());
+        let down_txns = down.iter().map(|&n| self.revisions[n].transaction.clone());
+
+        down_txns.chain(up_txns).reduce(|acc, tx| tx.compose(acc))
+    }
+
     /// Undo the last edit.
     pub fn undo(&mut self) -> Option<&Transaction> {
         if self.at_root() {
@@ -0,0 +1,127 @@
+/// Word lists the increment/decrement commands cycle through by default.
+static DEFAULT_CYCLES: &[&[&str]] = &[
+    &[
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "friday",
+        "saturday",
+        "sunday",
+    ],
+    &["mon", "tue", "wed", "thu", "fri", "sat", "sun"],
+    &[
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ],
+    &[
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ],
+    &["true", "false"],
+    &["yes", "no"],
+    &["on", "off"],
+];
+
+/// Cycle a word through closed lists like weekday names and boolean keywords.
+///
+/// Matching is case-insensitive; the casing style of the selected word
+/// (lowercase, Capitalized or UPPERCASE) is preserved in the replacement.
+pub fn increment(selected_text: &str, amount: i64) -> Option<String> {
+    DEFAULT_CYCLES
+        .iter()
+        .find_map(|words| cycle(words, selected_text, amount))
+}
+
+/// Cycle a word through the first of `cycles` that contains it. Used for the
+/// `increment-cycles` lists configured per language, which take precedence
+/// over the built-in ones.
+pub fn increment_in(cycles: &[Vec<String>], selected_text: &str, amount: i64) -> Option<String> {
+    cycles.iter().find_map(|words| {
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        cycle(&words, selected_text, amount)
+    })
+}
+
+fn cycle(words: &[&str], selected_text: &str, amount: i64) -> Option<String> {
+    if selected_text.is_empty() {
+        return None;
+    }
+
+    let lower = selected_text.to_lowercase();
+    let index = words.iter().position(|word| word.to_lowercase() == lower)?;
+    let next_index = (index as i64 + amount).rem_euclid(words.len() as i64) as usize;
+
+    Some(apply_case(selected_text, words[next_index]))
+}
+
+fn apply_case(pattern: &str, word: &str) -> String {
+    let mut chars = pattern.chars();
+    let first_upper = chars.next().map_or(false, char::is_uppercase);
+    let rest_upper = first_upper && chars.all(char::is_uppercase) && pattern.chars().count() > 1;
+
+    if rest_upper {
+        word.to_uppercase()
+    } else if first_upper {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    } else {
+        word.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cycle_weekdays_wraps_around() {
+        assert_eq!(increment("friday", 1).unwrap(), "saturday");
+        assert_eq!(increment("sunday", 1).unwrap(), "monday");
+        assert_eq!(increment("monday", -1).unwrap(), "sunday");
+        assert_eq!(increment("mon", 3).unwrap(), "thu");
+    }
+
+    #[test]
+    fn test_cycle_months() {
+        assert_eq!(increment("april", 1).unwrap(), "may");
+        assert_eq!(increment("may", 1).unwrap(), "june");
+        assert_eq!(increment("apr", 1).unwrap(), "may");
+        assert_eq!(increment("jun", -2).unwrap(), "apr");
+        assert_eq!(increment("december", 1).unwrap(), "january");
+        assert_eq!(increment("jan", -1).unwrap(), "dec");
+    }
+
+    #[test]
+    fn test_cycle_preserves_case() {
+        assert_eq!(increment("True", 1).unwrap(), "False");
+        assert_eq!(increment("FALSE", 1).unwrap(), "TRUE");
+        assert_eq!(increment("Wednesday", 1).unwrap(), "Thursday");
+        assert_eq!(increment("off", 1).unwrap(), "on");
+    }
+
+    #[test]
+    fn test_cycle_ignores_unknown_words() {
+        assert_eq!(increment("weekday", 1), None);
+        assert_eq!(increment("", 1), None);
+    }
+
+    #[test]
+    fn test_cycle_custom_lists_take_precedence() {
+        let cycles = vec![vec!["true".to_string(), "maybe".to_string()]];
+        assert_eq!(increment_in(&cycles, "true", 1).unwrap(), "maybe");
+        assert_eq!(increment_in(&cycles, "false", 1), None);
+    }
+}
@@ -1,3 +1,4 @@
+mod cycle;
 mod date_time;
 mod integer;
 
@@ -8,3 +9,11 @@ pub fn integer(selected_text: &str, amount: i64) -> Option<String> {
 pub fn date_time(selected_text: &str, amount: i64) -> Option<String> {
     date_time::increment(selected_text, amount)
 }
+
+pub fn cycle(selected_text: &str, amount: i64) -> Option<String> {
+    cycle::increment(selected_text, amount)
+}
+
+pub fn cycle_in(cycles: &[Vec<String>], selected_text: &str, amount: i64) -> Option<String> {
+    cycle::increment_in(cycles, selected_text, amount)
+}
@@ -4,9 +4,11 @@ pub mod auto_pairs;
 pub mod chars;
 pub mod comment;
 pub mod config;
+pub mod conflict;
 pub mod diagnostic;
 pub mod diff;
 pub mod doc_formatter;
+pub mod eval;
 pub mod graphemes;
 pub mod history;
 pub mod increment;
@@ -17,6 +19,7 @@ pub mod match_brackets;
 pub mod movement;
 pub mod object;
 pub mod path;
+pub mod perf;
 mod position;
 pub mod register;
 pub mod search;
@@ -30,6 +30,69 @@ pub fn expand_tilde(path: &Path) -> PathBuf {
     path.to_path_buf()
 }
 
+/// Replaces `$VAR` and `${VAR}` with the value of the environment variable.
+/// References to unset variables and malformed `${` sequences are left in
+/// place unchanged.
+pub fn expand_envs(path: &Path) -> PathBuf {
+    let path = path.to_string_lossy();
+    if !path.contains('$') {
+        return PathBuf::from(path.as_ref());
+    }
+
+    let mut expanded = String::with_capacity(path.len());
+    let mut chars = path.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+        let braced = chars.peek() == Some(&'{');
+        if braced {
+            chars.next();
+        }
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '_' || c.is_ascii_alphanumeric() {
+                name.push(c);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        if braced && chars.peek() != Some(&'}') {
+            // unterminated `${`, keep the text as written
+            expanded.push_str("${");
+            expanded.push_str(&name);
+            continue;
+        }
+        if braced {
+            chars.next();
+        }
+        match std::env::var(&name) {
+            Ok(value) => expanded.push_str(&value),
+            Err(_) => {
+                expanded.push('$');
+                if braced {
+                    expanded.push('{');
+                }
+                expanded.push_str(&name);
+                if braced {
+                    expanded.push('}');
+                }
+            }
+        }
+    }
+
+    PathBuf::from(expanded)
+}
+
+/// Expands both environment variables and a leading tilde, see
+/// [`expand_envs`] and [`expand_tilde`]. This is what prompt arguments that
+/// name a path (`:open`, `:write`, ...) go through.
+pub fn expand(path: &Path) -> PathBuf {
+    expand_tilde(&expand_envs(path))
+}
+
 /// Normalize a path, removing things like `.` and `..`.
 ///
 /// CAUTION: This does not resolve symlinks (unlike
@@ -86,7 +149,7 @@ pub fn get_normalized_path(path: &Path) -> PathBuf {
 /// This function is used instead of `std::fs::canonicalize` because we don't want to verify
 /// here if the path exists, just normalize it's components.
 pub fn get_canonicalized_path(path: &Path) -> std::io::Result<PathBuf> {
-    let path = expand_tilde(path);
+    let path = expand(path);
     let path = if path.is_relative() {
         std::env::current_dir().map(|current_dir| current_dir.join(path))?
     } else {
@@ -162,3 +225,35 @@ pub fn get_truncated_path<P: AsRef<Path>>(path: P) -> PathBuf {
     ret.push(file);
     ret
 }
+
+#[cfg(test)]
+mod tests {
+    use super::expand_envs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn expand_envs_replaces_set_variables() {
+        std::env::set_var("HELIX_PATH_TEST", "/tmp/helix");
+        assert_eq!(
+            expand_envs(Path::new("$HELIX_PATH_TEST/config.toml")),
+            PathBuf::from("/tmp/helix/config.toml")
+        );
+        assert_eq!(
+            expand_envs(Path::new("${HELIX_PATH_TEST}/config.toml")),
+            PathBuf::from("/tmp/helix/config.toml")
+        );
+    }
+
+    #[test]
+    fn expand_envs_keeps_unset_and_malformed_references() {
+        std::env::remove_var("HELIX_PATH_UNSET");
+        for path in [
+            "$HELIX_PATH_UNSET/x",
+            "${HELIX_PATH_UNSET}/x",
+            "${HELIX_PATH_UNSET/x",
+            "just/a/$",
+        ] {
+            assert_eq!(expand_envs(Path::new(path)), PathBuf::from(path));
+        }
+    }
+}
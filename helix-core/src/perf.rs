@@ -0,0 +1,71 @@
+//! Lightweight timing instrumentation behind the `:perf` overlay. Hot paths
+//! record how long an operation took into fixed-size ring buffers behind a
+//! mutex; the overlay reads them back once per frame. The lock is only held
+//! long enough to push or copy a handful of `Duration`s, so the cost to the
+//! instrumented code is negligible.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+
+/// How many samples are kept per metric.
+pub const HISTORY: usize = 60;
+
+/// The instrumented operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// A full compositor render plus terminal draw.
+    Render = 0,
+    /// Handling one terminal event, excluding the render it triggers.
+    Event = 1,
+    /// A tree-sitter (re)parse of all syntax layers of a document.
+    Parse = 2,
+    /// A language server request, from send until its response arrived.
+    LspRequest = 3,
+}
+
+const METRICS: usize = 4;
+
+static SAMPLES: Lazy<Mutex<[VecDeque<Duration>; METRICS]>> =
+    Lazy::new(|| Mutex::new(Default::default()));
+
+/// Record one sample for `metric`, dropping the oldest once `HISTORY`
+/// samples are kept.
+pub fn record(metric: Metric, duration: Duration) {
+    let mut samples = SAMPLES.lock().unwrap();
+    let samples = &mut samples[metric as usize];
+    if samples.len() == HISTORY {
+        samples.pop_front();
+    }
+    samples.push_back(duration);
+}
+
+/// The recorded samples for `metric`, oldest first.
+pub fn samples(metric: Metric) -> Vec<Duration> {
+    SAMPLES.lock().unwrap()[metric as usize]
+        .iter()
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_caps_history() {
+        for n in 0..HISTORY + 10 {
+            record(Metric::Parse, Duration::from_micros(n as u64));
+        }
+        let samples = samples(Metric::Parse);
+        assert_eq!(samples.len(), HISTORY);
+        // the oldest samples were dropped
+        assert_eq!(*samples.first().unwrap(), Duration::from_micros(10));
+        assert_eq!(
+            *samples.last().unwrap(),
+            Duration::from_micros(HISTORY as u64 + 9)
+        );
+    }
+}
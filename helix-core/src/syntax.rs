@@ -30,15 +30,6 @@ use serde::{ser::SerializeSeq, Deserialize, Serialize};
 
 use helix_loader::grammar::{get_language, load_runtime_file};
 
-fn deserialize_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    Option::<String>::deserialize(deserializer)?
-        .map(|buf| Regex::new(&buf).map_err(serde::de::Error::custom))
-        .transpose()
-}
-
 fn deserialize_lsp_config<'de, D>(deserializer: D) -> Result<Option<serde_json::Value>, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -115,11 +106,30 @@ pub struct LanguageConfiguration {
     #[serde(default)]
     pub diagnostic_severity: Severity,
 
+    /// Diagnostic codes that are dropped entirely, e.g. `"dead_code"`.
+    /// Numeric codes are matched against their decimal representation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignored_diagnostic_codes: Vec<String>,
+
+    /// Diagnostic sources that are dropped entirely, e.g. `"clippy"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignored_diagnostic_sources: Vec<String>,
+
+    /// Additional word lists the increment/decrement commands cycle through,
+    /// tried before the built-in ones, e.g. `[["pick", "fixup", "squash"]]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub increment_cycles: Vec<Vec<String>>,
+
     pub grammar: Option<String>, // tree-sitter grammar name, defaults to language_id
 
     // content_regex
-    #[serde(default, skip_serializing, deserialize_with = "deserialize_regex")]
-    pub injection_regex: Option<Regex>,
+    /// Source of the injection regex; compiled lazily on first use via
+    /// [`Self::injection_regex`] so startup doesn't pay for compiling the
+    /// pattern of every bundled language.
+    #[serde(default, skip_serializing, rename = "injection-regex")]
+    injection_regex_source: Option<String>,
+    #[serde(skip)]
+    injection_regex: OnceCell<Option<Regex>>,
     // first_line_regex
     //
     #[serde(skip)]
@@ -371,6 +381,9 @@ pub struct LanguageServerConfiguration {
     pub config: Option<serde_json::Value>,
     #[serde(default = "default_timeout")]
     pub timeout: u64,
+    /// Display this server's verbose progress messages in the status line
+    #[serde(default)]
+    pub display_messages: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -669,6 +682,24 @@ impl LanguageConfiguration {
         self.highlight_config.get().is_some()
     }
 
+    pub fn injection_regex(&self) -> Option<&Regex> {
+        self.injection_regex
+            .get_or_init(|| {
+                self.injection_regex_source.as_deref().and_then(|pattern| {
+                    Regex::new(pattern)
+                        .map_err(|err| {
+                            log::error!(
+                                "Invalid injection-regex for language {:?}: {}",
+                                self.language_id,
+                                err
+                            )
+                        })
+                        .ok()
+                })
+            })
+            .as_ref()
+    }
+
     pub fn indent_query(&self) -> Option<&Query> {
         self.indent_query
             .get_or_init(|| self.load_query("indents.scm"))
@@ -849,7 +880,7 @@ impl Loader {
         let mut best_match_length = 0;
         let mut best_match_position = None;
         for (i, configuration) in self.language_configs.iter().enumerate() {
-            if let Some(injection_regex) = &configuration.injection_regex {
+            if let Some(injection_regex) = configuration.injection_regex() {
                 if let Some(mat) = injection_regex.find(name) {
                     let length = mat.end() - mat.start();
                     if length > best_match_length {
@@ -971,6 +1002,7 @@ impl Syntax {
         source: &Rope,
         changeset: &ChangeSet,
     ) -> Result<(), Error> {
+        let parse_start = std::time::Instant::now();
         let mut queue = VecDeque::new();
         queue.push_back(self.root);
 
@@ -1240,6 +1272,8 @@ impl Syntax {
                     .contains(LayerUpdateFlags::TOUCHED)
             });
 
+            crate::perf::record(crate::perf::Metric::Parse, parse_start.elapsed());
+
             Ok(())
         })
     }
@@ -1248,6 +1282,47 @@ impl Syntax {
         self.layers[self.root].tree()
     }
 
+    /// The highlight capture names that apply at `byte`, outermost first,
+    /// across all syntax layers covering the position. Internal `local.*`
+    /// captures are skipped. Used by `:tree-sitter-highlights` for theme and
+    /// query debugging.
+    pub fn highlight_captures_at(&self, source: RopeSlice, byte: usize) -> Vec<String> {
+        let mut captures: Vec<(usize, String)> = Vec::new();
+        for (_, layer) in self.layers.iter() {
+            if layer.tree.is_none()
+                || !layer
+                    .ranges
+                    .iter()
+                    .any(|range| range.start_byte <= byte && byte < range.end_byte)
+            {
+                continue;
+            }
+
+            let mut cursor = QueryCursor::new();
+            cursor.set_byte_range(byte..byte + 1);
+            cursor.set_match_limit(TREE_SITTER_MATCH_LIMIT);
+            let names = layer.config.query.capture_names();
+            for mat in cursor.matches(
+                &layer.config.query,
+                layer.tree().root_node(),
+                RopeProvider(source),
+            ) {
+                for capture in mat.captures {
+                    let range = capture.node.byte_range();
+                    let name = &names[capture.index as usize];
+                    if range.start <= byte && byte < range.end && !name.starts_with("local.") {
+                        captures.push((range.len(), name.clone()));
+                    }
+                }
+            }
+        }
+
+        // outermost (widest) capture first, like vim's synstack
+        captures.sort_by_key(|&(len, _)| std::cmp::Reverse(len));
+        captures.dedup();
+        captures.into_iter().map(|(_, name)| name).collect()
+    }
+
     /// Iterate over the highlighted regions for a given slice of source code.
     pub fn highlight_iter<'a>(
         &'a self,
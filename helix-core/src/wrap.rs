@@ -1,7 +1,102 @@
 use smartstring::{LazyCompact, SmartString};
+use textwrap::{core::display_width, refill};
 
 /// Given a slice of text, return the text re-wrapped to fit it
 /// within the given width.
-pub fn reflow_hard_wrap(text: &str, text_width: usize) -> SmartString<LazyCompact> {
-    textwrap::refill(text, text_width).into()
+///
+/// When every non-blank line starts with `comment_token` (after any
+/// indentation), the token is stripped before wrapping and reapplied
+/// afterwards, so comments in languages `textwrap` does not recognize
+/// reflow correctly. Other prefixes such as list bullets are detected by
+/// `textwrap` itself.
+pub fn reflow_hard_wrap(
+    text: &str,
+    text_width: usize,
+    comment_token: Option<&str>,
+) -> SmartString<LazyCompact> {
+    if let Some(token) = comment_token {
+        let first_line = text.lines().next().unwrap_or_default();
+        let indent = &first_line[..first_line.len() - first_line.trim_start().len()];
+        let is_comment = !text.trim().is_empty()
+            && text.lines().all(|line| {
+                let line = line.trim_start();
+                line.is_empty() || line.starts_with(token)
+            });
+        if is_comment {
+            return reflow_comment(text, text_width, indent, token);
+        }
+    }
+    refill(text, text_width).into()
+}
+
+/// Re-wrap a run of comment lines: strip the indentation and comment token,
+/// reflow the contents to the remaining width and prefix every resulting line
+/// with the indentation and token again.
+fn reflow_comment(
+    text: &str,
+    text_width: usize,
+    indent: &str,
+    token: &str,
+) -> SmartString<LazyCompact> {
+    let prefix = format!("{}{} ", indent, token);
+
+    let contents = text
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix(token).unwrap_or(line);
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let inner_width = text_width.saturating_sub(display_width(&prefix)).max(1);
+    let refilled = refill(&contents, inner_width);
+
+    let mut reflowed = SmartString::new();
+    for line in refilled.lines() {
+        if line.is_empty() {
+            reflowed.push_str(prefix.trim_end());
+        } else {
+            reflowed.push_str(&prefix);
+            reflowed.push_str(line);
+        }
+        reflowed.push('\n');
+    }
+    if !text.ends_with('\n') {
+        reflowed.pop();
+    }
+    reflowed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn uncommented_text_uses_textwrap_prefix_detection() {
+        let text = "one two three four five six seven\n";
+        assert_eq!(
+            reflow_hard_wrap(text, 20, Some("#")),
+            "one two three four\nfive six seven\n"
+        );
+    }
+
+    #[test]
+    fn comment_token_is_reapplied() {
+        let text = "-- one two three four five six seven\n-- eight nine\n";
+        assert_eq!(
+            reflow_hard_wrap(text, 20, Some("--")),
+            "-- one two three\n-- four five six\n-- seven eight nine\n"
+        );
+    }
+
+    #[test]
+    fn comment_indentation_is_preserved() {
+        let text = "    ;; alpha beta gamma delta epsilon\n    ;; zeta\n";
+        assert_eq!(
+            reflow_hard_wrap(text, 20, Some(";;")),
+            "    ;; alpha beta\n    ;; gamma delta\n    ;; epsilon zeta\n"
+        );
+    }
 }
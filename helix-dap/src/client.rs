@@ -1,5 +1,5 @@
 use crate::{
-    requests::DisconnectArguments,
+    requests::{DisconnectArguments, TerminateArguments},
     transport::{Payload, Request, Response, Transport},
     types::*,
     Error, Result, ThreadId,
@@ -358,6 +358,13 @@ impl Client {
         self.call::<requests::Disconnect>(args)
     }
 
+    pub fn terminate(
+        &self,
+        args: Option<TerminateArguments>,
+    ) -> impl Future<Output = Result<Value>> {
+        self.call::<requests::Terminate>(args)
+    }
+
     pub fn launch(&mut self, args: serde_json::Value) -> impl Future<Output = Result<Value>> {
         self.connection_type = Some(ConnectionType::Launch);
         self.starting_request_args = Some(args.clone());
@@ -420,6 +420,22 @@ pub mod requests {
         const COMMAND: &'static str = "disconnect";
     }
 
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct TerminateArguments {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub restart: Option<bool>,
+    }
+
+    #[derive(Debug)]
+    pub enum Terminate {}
+
+    impl Request for Terminate {
+        type Arguments = Option<TerminateArguments>;
+        type Result = ();
+        const COMMAND: &'static str = "terminate";
+    }
+
     #[derive(Debug)]
     pub enum ConfigurationDone {}
 
@@ -11,6 +11,30 @@ static RUNTIME_DIRS: once_cell::sync::Lazy<Vec<PathBuf>> =
 
 static CONFIG_FILE: once_cell::sync::OnceCell<PathBuf> = once_cell::sync::OnceCell::new();
 
+static WORKSPACE_MARKERS: once_cell::sync::Lazy<std::sync::RwLock<Vec<String>>> =
+    once_cell::sync::Lazy::new(|| std::sync::RwLock::new(default_workspace_markers()));
+
+fn default_workspace_markers() -> Vec<String> {
+    vec![".git".to_string(), ".helix".to_string()]
+}
+
+/// Set the file/directory names [`find_workspace`] searches for (the
+/// `roots` editor setting). Called whenever the user config is (re)loaded;
+/// an empty list restores the built-in `.git`/`.helix` markers.
+pub fn set_workspace_markers(markers: Vec<String>) {
+    *WORKSPACE_MARKERS.write().unwrap() = if markers.is_empty() {
+        default_workspace_markers()
+    } else {
+        markers
+    };
+}
+
+/// The file/directory names that mark a workspace root, see
+/// [`set_workspace_markers`].
+pub fn workspace_markers() -> Vec<String> {
+    WORKSPACE_MARKERS.read().unwrap().clone()
+}
+
 pub fn initialize_config_file(specified_file: Option<PathBuf>) {
     let config_file = specified_file.unwrap_or_else(|| {
         let config_dir = config_dir();
@@ -121,6 +145,18 @@ pub fn cache_dir() -> PathBuf {
     path
 }
 
+pub fn state_dir() -> PathBuf {
+    let strategy = choose_base_strategy().expect("Unable to find the config directory!");
+    match strategy.state_dir() {
+        Some(mut path) => {
+            path.push("helix");
+            path
+        }
+        // Not all platforms have a state directory, fall back to the cache directory.
+        None => cache_dir(),
+    }
+}
+
 pub fn config_file() -> PathBuf {
     CONFIG_FILE
         .get()
@@ -213,18 +249,28 @@ pub fn merge_toml_values(left: toml::Value, right: toml::Value, merge_depth: usi
 /// Used as a ceiling dir for LSP root resolution, the filepicker and potentially as a future filewatching root
 ///
 /// This function starts searching the FS upward from the CWD
-/// and returns the first directory that contains either `.git` or `.helix`.
+/// and returns the first directory that contains one of the workspace
+/// markers (`.git` and `.helix` unless changed via the `roots` setting,
+/// see [`set_workspace_markers`]).
 /// If no workspace was found returns (CWD, true).
 /// Otherwise (workspace, false) is returned
 pub fn find_workspace() -> (PathBuf, bool) {
     let current_dir = std::env::current_dir().expect("unable to determine current directory");
-    for ancestor in current_dir.ancestors() {
-        if ancestor.join(".git").exists() || ancestor.join(".helix").exists() {
+    find_workspace_in(current_dir)
+}
+
+/// Like [`find_workspace`], but searching upward from `dir` instead of the
+/// current working directory.
+pub fn find_workspace_in(dir: impl AsRef<Path>) -> (PathBuf, bool) {
+    let dir = dir.as_ref();
+    let markers = workspace_markers();
+    for ancestor in dir.ancestors() {
+        if markers.iter().any(|marker| ancestor.join(marker).exists()) {
             return (ancestor.to_owned(), false);
         }
     }
 
-    (current_dir, true)
+    (dir.to_owned(), true)
 }
 
 #[cfg(test)]
@@ -20,7 +20,10 @@ use std::sync::{
     atomic::{AtomicU64, Ordering},
     Arc,
 };
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 use tokio::{
     io::{BufReader, BufWriter},
     process::{Child, Command},
@@ -30,6 +33,42 @@ use tokio::{
     },
 };
 
+/// Sends a `$/cancelRequest` notification for a request when dropped,
+/// unless it was defused first. Used by [`Client::call`] to cancel
+/// server-side work for requests whose response nobody will read anymore.
+struct CancelOnDrop {
+    server_tx: UnboundedSender<Payload>,
+    id: Option<jsonrpc::Id>,
+}
+
+impl CancelOnDrop {
+    fn defuse(&mut self) {
+        self.id = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        use lsp::notification::Notification;
+
+        let id = match self.id.take() {
+            Some(jsonrpc::Id::Num(id)) => lsp::NumberOrString::Number(id as i32),
+            Some(jsonrpc::Id::Str(id)) => lsp::NumberOrString::String(id),
+            Some(jsonrpc::Id::Null) | None => return,
+        };
+        let params = match serde_json::to_value(lsp::CancelParams { id }) {
+            Ok(params) => params,
+            Err(_) => return,
+        };
+        let notification = jsonrpc::Notification {
+            jsonrpc: Some(jsonrpc::Version::V2),
+            method: lsp::notification::Cancel::METHOD.to_string(),
+            params: Client::value_into_params(params),
+        };
+        let _ = self.server_tx.send(Payload::Notification(notification));
+    }
+}
+
 fn workspace_for_uri(uri: lsp::Url) -> WorkspaceFolder {
     lsp::WorkspaceFolder {
         name: uri
@@ -421,6 +460,15 @@ impl Client {
             };
 
             let (tx, mut rx) = channel::<Result<Value>>(1);
+            let sent_at = std::time::Instant::now();
+
+            // if this future is dropped before the response arrives (e.g. a
+            // completion request superseded by further typing) or the
+            // request times out, tell the server to abandon the work
+            let mut cancel_guard = CancelOnDrop {
+                server_tx: server_tx.clone(),
+                id: Some(id.clone()),
+            };
 
             server_tx
                 .send(Payload::Request {
@@ -430,10 +478,16 @@ impl Client {
                 .map_err(|e| Error::Other(e.into()))?;
 
             // TODO: delay other calls until initialize success
-            timeout(Duration::from_secs(timeout_secs), rx.recv())
-                .await
-                .map_err(|_| Error::Timeout(id))? // return Timeout
-                .ok_or(Error::StreamClosed)?
+            match timeout(Duration::from_secs(timeout_secs), rx.recv()).await {
+                // cancel_guard stays armed: the server should stop working
+                // on a request whose response nobody will read
+                Err(_) => Err(Error::Timeout(id)),
+                Ok(response) => {
+                    cancel_guard.defuse();
+                    helix_core::perf::record(helix_core::perf::Metric::LspRequest, sent_at.elapsed());
+                    response.ok_or(Error::StreamClosed)?
+                }
+            }
         }
     }
 
@@ -1412,6 +1466,115 @@ impl Client {
         })
     }
 
+    pub fn will_rename_files(
+        &self,
+        old_path: &Path,
+        new_path: &Path,
+        is_dir: bool,
+    ) -> Option<impl Future<Output = Result<lsp::WorkspaceEdit>>> {
+        // the registry hands out clients that may still be initializing
+        let capabilities = self.capabilities.get()?;
+        let options = capabilities
+            .workspace
+            .as_ref()?
+            .file_operations
+            .as_ref()?
+            .will_rename
+            .as_ref()?;
+        if !file_operation_matches(options, old_path, is_dir) {
+            return None;
+        }
+
+        let files = vec![lsp::FileRename {
+            old_uri: lsp::Url::from_file_path(old_path).ok()?.to_string(),
+            new_uri: lsp::Url::from_file_path(new_path).ok()?.to_string(),
+        }];
+        let request = self.call::<lsp::request::WillRenameFiles>(lsp::RenameFilesParams { files });
+
+        Some(async move {
+            let json = request.await?;
+            let response: Option<lsp::WorkspaceEdit> = serde_json::from_value(json)?;
+            Ok(response.unwrap_or_default())
+        })
+    }
+
+    pub fn did_rename_files(
+        &self,
+        old_path: &Path,
+        new_path: &Path,
+        is_dir: bool,
+    ) -> Option<impl Future<Output = Result<()>>> {
+        let capabilities = self.capabilities.get()?;
+        let options = capabilities
+            .workspace
+            .as_ref()?
+            .file_operations
+            .as_ref()?
+            .did_rename
+            .as_ref()?;
+        if !file_operation_matches(options, new_path, is_dir) {
+            return None;
+        }
+
+        let files = vec![lsp::FileRename {
+            old_uri: lsp::Url::from_file_path(old_path).ok()?.to_string(),
+            new_uri: lsp::Url::from_file_path(new_path).ok()?.to_string(),
+        }];
+        Some(self.notify::<lsp::notification::DidRenameFiles>(lsp::RenameFilesParams { files }))
+    }
+
+    pub fn will_delete_files(
+        &self,
+        path: &Path,
+        is_dir: bool,
+    ) -> Option<impl Future<Output = Result<lsp::WorkspaceEdit>>> {
+        let capabilities = self.capabilities.get()?;
+        let options = capabilities
+            .workspace
+            .as_ref()?
+            .file_operations
+            .as_ref()?
+            .will_delete
+            .as_ref()?;
+        if !file_operation_matches(options, path, is_dir) {
+            return None;
+        }
+
+        let files = vec![lsp::FileDelete {
+            uri: lsp::Url::from_file_path(path).ok()?.to_string(),
+        }];
+        let request = self.call::<lsp::request::WillDeleteFiles>(lsp::DeleteFilesParams { files });
+
+        Some(async move {
+            let json = request.await?;
+            let response: Option<lsp::WorkspaceEdit> = serde_json::from_value(json)?;
+            Ok(response.unwrap_or_default())
+        })
+    }
+
+    pub fn did_delete_files(
+        &self,
+        path: &Path,
+        is_dir: bool,
+    ) -> Option<impl Future<Output = Result<()>>> {
+        let capabilities = self.capabilities.get()?;
+        let options = capabilities
+            .workspace
+            .as_ref()?
+            .file_operations
+            .as_ref()?
+            .did_delete
+            .as_ref()?;
+        if !file_operation_matches(options, path, is_dir) {
+            return None;
+        }
+
+        let files = vec![lsp::FileDelete {
+            uri: lsp::Url::from_file_path(path).ok()?.to_string(),
+        }];
+        Some(self.notify::<lsp::notification::DidDeleteFiles>(lsp::DeleteFilesParams { files }))
+    }
+
     pub fn command(&self, command: lsp::Command) -> Option<impl Future<Output = Result<Value>>> {
         let capabilities = self.capabilities.get().unwrap();
 
@@ -1429,3 +1592,36 @@ impl Client {
         Some(self.call::<lsp::request::ExecuteCommand>(params))
     }
 }
+
+/// Checks whether `path` matches one of the filters a server registered for a
+/// file operation.
+fn file_operation_matches(
+    options: &lsp::FileOperationRegistrationOptions,
+    path: &Path,
+    is_dir: bool,
+) -> bool {
+    options.filters.iter().any(|filter| {
+        if filter
+            .scheme
+            .as_deref()
+            .map_or(false, |scheme| scheme != "file")
+        {
+            return false;
+        }
+        match filter.pattern.matches {
+            Some(lsp::FileOperationPatternKind::File) if is_dir => return false,
+            Some(lsp::FileOperationPatternKind::Folder) if !is_dir => return false,
+            _ => (),
+        }
+        let ignore_case = filter
+            .pattern
+            .options
+            .as_ref()
+            .and_then(|options| options.ignore_case)
+            .unwrap_or(false);
+        globset::GlobBuilder::new(&filter.pattern.glob)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_or(false, |glob| glob.compile_matcher().is_match(path))
+    })
+}
@@ -745,13 +745,19 @@ impl Registry {
         root_dirs: &[PathBuf],
         enable_snippets: bool,
     ) -> Result<HashMap<LanguageServerName, Arc<Client>>> {
+        // languages without their own `roots` fall back to the global ones
+        let root_markers = if language_config.roots.is_empty() {
+            helix_loader::workspace_markers()
+        } else {
+            language_config.roots.clone()
+        };
         language_config
             .language_servers
             .iter()
             .map(|LanguageServerFeatures { name, .. }| {
                 if let Some(clients) = self.inner.get(name) {
                     if let Some((_, client)) = clients.iter().enumerate().find(|(i, client)| {
-                        client.try_add_doc(&language_config.roots, root_dirs, doc_path, *i == 0)
+                        client.try_add_doc(&root_markers, root_dirs, doc_path, *i == 0)
                     }) {
                         return Ok((name.to_owned(), client.clone()));
                     }
@@ -866,12 +872,17 @@ fn start_client(
     root_dirs: &[PathBuf],
     enable_snippets: bool,
 ) -> Result<NewClient> {
+    let root_markers = if config.roots.is_empty() {
+        helix_loader::workspace_markers()
+    } else {
+        config.roots.clone()
+    };
     let (client, incoming, initialize_notify) = Client::start(
         &ls_config.command,
         &ls_config.args,
         ls_config.config.clone(),
         ls_config.environment.clone(),
-        &config.roots,
+        &root_markers,
         config.workspace_lsp_roots.as_deref().unwrap_or(root_dirs),
         id,
         name,
@@ -5,7 +5,7 @@ use helix_core::{
     path::get_relative_path,
     pos_at_coords, syntax, Selection,
 };
-use helix_lsp::{lsp, util::lsp_pos_to_pos, LspProgressMap};
+use helix_lsp::{lsp, util::lsp_pos_to_pos};
 use helix_view::{
     align_view,
     document::DocumentSavedEventResult,
@@ -23,7 +23,7 @@ use crate::{
     commands::apply_workspace_edit,
     compositor::{Compositor, Event},
     config::Config,
-    job::Jobs,
+    job::{self, Jobs},
     keymap::Keymaps,
     ui::{self, overlay::overlaid},
 };
@@ -42,11 +42,56 @@ use anyhow::{Context, Error};
 use crossterm::{event::Event as CrosstermEvent, tty::IsTty};
 #[cfg(not(windows))]
 use {signal_hook::consts::signal, signal_hook_tokio::Signals};
+
+/// Console control events forwarded into the event loop on Windows, the
+/// closest analog to the POSIX signals handled above.
+#[cfg(windows)]
+#[derive(Debug, Clone, Copy)]
+pub enum WindowsSignal {
+    /// `Ctrl-Break` was pressed (`Ctrl-C` arrives as a key event in raw mode).
+    Break,
+    /// The console window is being closed; Windows grants a few seconds to
+    /// clean up before terminating the process.
+    Close,
+}
+
+#[cfg(windows)]
+type Signals = futures_util::stream::BoxStream<'static, WindowsSignal>;
+
 #[cfg(windows)]
-type Signals = futures_util::stream::Empty<()>;
+fn windows_signals() -> std::io::Result<Signals> {
+    use tokio::signal::windows::{ctrl_break, ctrl_close};
+
+    let ctrl_break = ctrl_break()?;
+    let ctrl_close = ctrl_close()?;
+    let ctrl_break = futures_util::stream::unfold(ctrl_break, |mut recv| async move {
+        recv.recv().await.map(|_| (WindowsSignal::Break, recv))
+    });
+    let ctrl_close = futures_util::stream::unfold(ctrl_close, |mut recv| async move {
+        recv.recv().await.map(|_| (WindowsSignal::Close, recv))
+    });
+
+    Ok(Box::pin(futures_util::stream::select(
+        ctrl_break, ctrl_close,
+    )))
+}
 
 const LSP_DEADLINE: Duration = Duration::from_millis(16);
 
+/// The configured theme to use right now: `light-theme` or `dark-theme` when
+/// one is set and the terminal background can be classified, `theme`
+/// otherwise.
+fn preferred_theme(config: &Config) -> Option<&String> {
+    if config.light_theme.is_some() || config.dark_theme.is_some() {
+        match crate::terminal_background_dark() {
+            Some(true) => return config.dark_theme.as_ref().or(config.theme.as_ref()),
+            Some(false) => return config.light_theme.as_ref().or(config.theme.as_ref()),
+            None => (),
+        }
+    }
+    config.theme.as_ref()
+}
+
 #[cfg(not(feature = "integration"))]
 use tui::backend::CrosstermBackend;
 
@@ -75,7 +120,11 @@ pub struct Application {
 
     signals: Signals,
     jobs: Jobs,
-    lsp_progress: LspProgressMap,
+    /// Open requests from `hx --remote` clients; `None` when another
+    /// instance owns the socket.
+    remote_requests: Option<tokio::sync::mpsc::UnboundedReceiver<crate::ipc::Request>>,
+    /// Callbacks queued by background tasks through `job::dispatch`.
+    dispatched_callbacks: tokio::sync::mpsc::UnboundedReceiver<job::Callback>,
     last_render: Instant,
 }
 
@@ -117,9 +166,7 @@ impl Application {
         let theme_loader = std::sync::Arc::new(theme::Loader::new(&theme_parent_dirs));
 
         let true_color = config.editor.true_color || crate::true_color();
-        let theme = config
-            .theme
-            .as_ref()
+        let theme = preferred_theme(&config)
             .and_then(|theme| {
                 theme_loader
                     .load(theme)
@@ -134,6 +181,8 @@ impl Application {
 
         let syn_loader = std::sync::Arc::new(syntax::Loader::new(syn_loader_conf));
 
+        crate::plugin::initialize();
+
         #[cfg(not(feature = "integration"))]
         let backend = CrosstermBackend::new(stdout(), &config.editor);
 
@@ -143,6 +192,7 @@ impl Application {
         let terminal = Terminal::new(backend)?;
         let area = terminal.size().expect("couldn't get terminal size");
         let mut compositor = Compositor::new(area);
+        crate::commands::typed::USER_COMMANDS.store(Arc::new(config.commands.clone()));
         let config = Arc::new(ArcSwap::from_pointee(config));
         let mut editor = Editor::new(
             area,
@@ -159,7 +209,39 @@ impl Application {
         let editor_view = Box::new(ui::EditorView::new(Keymaps::new(keys)));
         compositor.push(editor_view);
 
-        if args.load_tutor {
+        #[cfg(not(feature = "integration"))]
+        crate::history::load(&mut editor.registers);
+        #[cfg(not(feature = "integration"))]
+        editor.frecency.load();
+
+        if let Some(session_name) = args.session {
+            let session = crate::session::load(&session_name)?;
+            crate::session::restore(&mut editor, session)?;
+            if editor.tree.is_empty() {
+                // every file in the session failed to open
+                editor.new_file(Action::VerticalSplit);
+            }
+            editor.set_status(format!("Restored session '{}'.", session_name));
+        } else if let Some((left, right)) = args.diff {
+            // Open the two files side by side, each diffed against the other,
+            // so the diff gutter marks changed hunks in both views and
+            // `]g`/`[g` navigate between them.
+            let left_contents = std::fs::read(&left)
+                .with_context(|| format!("unable to read {}", left.display()))?;
+            let right_contents = std::fs::read(&right)
+                .with_context(|| format!("unable to read {}", right.display()))?;
+            let left_id = editor.open(&left, Action::VerticalSplit)?;
+            let right_id = editor.open(&right, Action::VerticalSplit)?;
+            let redraw_handle = editor.redraw_handle.clone();
+            editor
+                .document_mut(left_id)
+                .unwrap()
+                .set_diff_base(right_contents, redraw_handle.clone());
+            editor
+                .document_mut(right_id)
+                .unwrap()
+                .set_diff_base(left_contents, redraw_handle);
+        } else if args.load_tutor {
             let path = helix_loader::runtime_file(Path::new("tutor"));
             editor.open(&path, Action::VerticalSplit)?;
             // Unset path to prevent accidentally saving to the original tutor file.
@@ -169,7 +251,7 @@ impl Application {
             if first.is_dir() {
                 std::env::set_current_dir(first).context("set current dir")?;
                 editor.new_file(Action::VerticalSplit);
-                let picker = ui::file_picker(".".into(), &config.load().editor);
+                let picker = ui::file_picker(".".into(), &editor);
                 compositor.push(Box::new(overlaid(picker)));
             } else {
                 let nr_of_files = args.files.len();
@@ -215,6 +297,12 @@ impl Application {
             }
         } else if stdin().is_tty() || cfg!(feature = "integration") {
             editor.new_file(Action::VerticalSplit);
+            #[cfg(not(feature = "integration"))]
+            if editor.config().dashboard {
+                if let Some(editor_view) = compositor.find::<ui::EditorView>() {
+                    editor_view.dashboard = Some(ui::Dashboard::new());
+                }
+            }
         } else if cfg!(target_os = "macos") {
             // On Linux and Windows, we allow the output of a command to be piped into the new buffer.
             // This doesn't currently work on macOS because of the following issue:
@@ -229,7 +317,7 @@ impl Application {
         editor.set_theme(theme);
 
         #[cfg(windows)]
-        let signals = futures_util::stream::empty();
+        let signals = windows_signals().context("build signal handler")?;
         #[cfg(not(windows))]
         let signals = Signals::new([
             signal::SIGTSTP,
@@ -240,7 +328,7 @@ impl Application {
         ])
         .context("build signal handler")?;
 
-        let app = Self {
+        let mut app = Self {
             compositor,
             terminal,
             editor,
@@ -252,14 +340,52 @@ impl Application {
 
             signals,
             jobs: Jobs::new(),
-            lsp_progress: LspProgressMap::new(),
+            remote_requests: if cfg!(feature = "integration") {
+                None
+            } else {
+                crate::ipc::spawn_listener()
+            },
+            dispatched_callbacks: job::take_dispatch_receiver(),
             last_render: Instant::now(),
         };
 
+        for command in &args.commands {
+            app.execute_startup_command(command);
+        }
+
         Ok(app)
     }
 
+    /// Runs a typable command given on the command line with `-c`, after the
+    /// UI has been initialized but before the event loop starts.
+    fn execute_startup_command(&mut self, input: &str) {
+        use helix_core::shellwords::Shellwords;
+
+        let input = input.strip_prefix(':').unwrap_or(input);
+        let shellwords = Shellwords::from(input);
+        let words = shellwords.words();
+        let Some(command) = words.first() else { return };
+
+        let mut cx = crate::compositor::Context {
+            editor: &mut self.editor,
+            jobs: &mut self.jobs,
+            scroll: None,
+        };
+        match crate::commands::typed::TYPABLE_COMMAND_MAP.get(&**command) {
+            Some(cmd) => {
+                if let Err(err) = (cmd.fun)(&mut cx, &words[1..], crate::ui::PromptEvent::Validate) {
+                    self.editor.set_error(format!(":{}: {}", input, err));
+                }
+            }
+            None => {
+                self.editor
+                    .set_error(format!("no such command: '{}'", command));
+            }
+        }
+    }
+
     async fn render(&mut self) {
+        let render_start = Instant::now();
         let mut cx = crate::compositor::Context {
             editor: &mut self.editor,
             jobs: &mut self.jobs,
@@ -293,6 +419,7 @@ impl Application {
 
         let pos = pos.map(|pos| (pos.col as u16, pos.row as u16));
         self.terminal.draw(pos, kind).unwrap();
+        helix_core::perf::record(helix_core::perf::Metric::Render, render_start.elapsed());
     }
 
     pub async fn event_loop<S>(&mut self, input_stream: &mut S)
@@ -318,7 +445,7 @@ impl Application {
                 return false;
             }
 
-            use futures_util::StreamExt;
+            use futures_util::{FutureExt, StreamExt};
 
             tokio::select! {
                 biased;
@@ -329,7 +456,19 @@ impl Application {
                     };
                 }
                 Some(event) = input_stream.next() => {
-                    self.handle_terminal_events(event).await;
+                    let mut should_render = self.handle_terminal_event(event);
+                    // Coalesce bursts of pending input (fast typing, pastes,
+                    // mouse drags) into a single recompose instead of
+                    // rendering the whole screen once per event.
+                    while let Some(Some(event)) = input_stream.next().now_or_never() {
+                        should_render |= self.handle_terminal_event(event);
+                        if self.editor.should_close() {
+                            break;
+                        }
+                    }
+                    if should_render && !self.editor.should_close() {
+                        self.render().await;
+                    }
                 }
                 Some(callback) = self.jobs.futures.next() => {
                     self.jobs.handle_callback(&mut self.editor, &mut self.compositor, callback);
@@ -339,6 +478,14 @@ impl Application {
                     self.jobs.handle_callback(&mut self.editor, &mut self.compositor, callback);
                     self.render().await;
                 }
+                Some(callback) = self.dispatched_callbacks.recv() => {
+                    self.jobs.handle_callback(&mut self.editor, &mut self.compositor, Ok(Some(callback)));
+                    self.render().await;
+                }
+                Some(request) = next_remote_request(&mut self.remote_requests) => {
+                    self.handle_remote_request(request);
+                    self.render().await;
+                }
                 event = self.editor.wait_event() => {
                     let _idle_handled = self.handle_editor_event(event).await;
 
@@ -360,6 +507,30 @@ impl Application {
         }
     }
 
+    /// Opens files sent by an `hx --remote` client, placing the cursor at
+    /// the requested position like files given on our own command line.
+    fn handle_remote_request(&mut self, files: crate::ipc::Request) {
+        use helix_view::editor::Action;
+
+        for (file, pos) in files {
+            match self.editor.open(&file, Action::Replace) {
+                Ok(doc_id) => {
+                    let view_id = self.editor.tree.focus;
+                    let doc = doc_mut!(self.editor, &doc_id);
+                    let pos = Selection::point(pos_at_coords(doc.text().slice(..), pos, true));
+                    doc.set_selection(view_id, pos);
+                }
+                Err(err) => {
+                    self.editor
+                        .set_error(format!("open '{}': {}", file.display(), err));
+                    return;
+                }
+            }
+        }
+        let (view, doc) = current!(self.editor);
+        align_view(doc, view, Align::Center);
+    }
+
     pub fn handle_config_events(&mut self, config_event: ConfigEvent) {
         match config_event {
             ConfigEvent::Refresh => self.refresh_config(),
@@ -406,9 +577,7 @@ impl Application {
     /// Refresh theme after config change
     fn refresh_theme(&mut self, config: &Config) -> Result<(), Error> {
         let true_color = config.editor.true_color || crate::true_color();
-        let theme = config
-            .theme
-            .as_ref()
+        let theme = preferred_theme(config)
             .and_then(|theme| {
                 self.theme_loader
                     .load(theme)
@@ -425,6 +594,18 @@ impl Application {
         Ok(())
     }
 
+    /// Re-evaluate the automatic light/dark theme choice after events that
+    /// may follow a system appearance change (SIGCONT, regained focus).
+    /// Does nothing unless `light-theme` or `dark-theme` is configured.
+    fn refresh_auto_theme(&mut self) {
+        let config = self.config.load();
+        if config.light_theme.is_none() && config.dark_theme.is_none() {
+            return;
+        }
+        let config = Config::clone(&config);
+        let _ = self.refresh_theme(&config);
+    }
+
     fn refresh_config(&mut self) {
         let mut refresh_config = || -> Result<(), Error> {
             let default_config = Config::load_default()
@@ -434,6 +615,8 @@ impl Application {
             self.terminal
                 .reconfigure(default_config.editor.clone().into())?;
             // Store new config
+            crate::commands::typed::USER_COMMANDS
+                .store(Arc::new(default_config.commands.clone()));
             self.config.store(Arc::new(default_config));
             Ok(())
         };
@@ -449,9 +632,16 @@ impl Application {
     }
 
     #[cfg(windows)]
-    // no signal handling available on windows
-    pub async fn handle_signals(&mut self, _signal: ()) -> bool {
-        true
+    pub async fn handle_signals(&mut self, signal: WindowsSignal) -> bool {
+        match signal {
+            // Restore the console before exiting, like SIGTERM/SIGINT below;
+            // for `Close` this races the few seconds Windows allows, so the
+            // terminal modes are reset even if the process is then killed.
+            WindowsSignal::Break | WindowsSignal::Close => {
+                self.restore_term().unwrap();
+                false
+            }
+        }
     }
 
     #[cfg(not(windows))]
@@ -487,6 +677,7 @@ impl Application {
                 }
             }
             signal::SIGCONT => {
+                self.refresh_auto_theme();
                 // Copy/Paste from same issue from neovim:
                 // https://github.com/neovim/neovim/issues/12322
                 // https://github.com/neovim/neovim/pull/13084
@@ -622,6 +813,10 @@ impl Application {
                     self.render().await;
                 }
             }
+            EditorEvent::SyntaxParsed(result) => {
+                self.editor.handle_syntax_parse_result(result);
+                self.render().await;
+            }
             EditorEvent::IdleTimer => {
                 self.editor.clear_idle_timer();
                 self.handle_idle_timeout().await;
@@ -636,10 +831,17 @@ impl Application {
         false
     }
 
-    pub async fn handle_terminal_events(
+    /// Handle a single terminal event, returning whether a redraw is needed.
+    pub fn handle_terminal_event(
         &mut self,
         event: Result<CrosstermEvent, crossterm::ErrorKind>,
-    ) {
+    ) -> bool {
+        // A regained focus may follow a system appearance change
+        if matches!(event, Ok(CrosstermEvent::FocusGained)) {
+            self.refresh_auto_theme();
+        }
+
+        let event_start = Instant::now();
         let mut cx = crate::compositor::Context {
             editor: &mut self.editor,
             jobs: &mut self.jobs,
@@ -664,10 +866,30 @@ impl Application {
                 kind: crossterm::event::KeyEventKind::Release,
                 ..
             }) => false,
-            event => self.compositor.handle_event(&event.into(), &mut cx),
+            event => {
+                if let CrosstermEvent::Key(key) = event {
+                    crate::crash_report::record_event(cx.editor, key.into());
+                    // feed the on-screen key display regardless of which
+                    // layer ends up consuming the key
+                    if let Some(editor_view) = self.compositor.find::<ui::EditorView>() {
+                        if let Some(display) = &mut editor_view.key_display {
+                            display.push(key.into());
+                        }
+                    }
+                }
+                self.compositor.handle_event(&event.into(), &mut cx)
+            }
         };
+        helix_core::perf::record(helix_core::perf::Metric::Event, event_start.elapsed());
+
+        should_redraw
+    }
 
-        if should_redraw && !self.editor.should_close() {
+    pub async fn handle_terminal_events(
+        &mut self,
+        event: Result<CrosstermEvent, crossterm::ErrorKind>,
+    ) {
+        if self.handle_terminal_event(event) && !self.editor.should_close() {
             self.render().await;
         }
     }
@@ -813,6 +1035,21 @@ impl Application {
                                                 return None;
                                             }
                                         }
+                                        if let Some(code) = &diagnostic.code {
+                                            let code = match code {
+                                                lsp::NumberOrString::Number(n) => n.to_string(),
+                                                lsp::NumberOrString::String(s) => s.clone(),
+                                            };
+                                            if lang_conf.ignored_diagnostic_codes.contains(&code) {
+                                                return None;
+                                            }
+                                        }
+                                        if let Some(source) = &diagnostic.source {
+                                            if lang_conf.ignored_diagnostic_sources.contains(source)
+                                            {
+                                                return None;
+                                            }
+                                        }
                                     };
 
                                     let code = match diagnostic.code.clone() {
@@ -924,8 +1161,8 @@ impl Application {
                                 if message.is_some() {
                                     (None, message, &None)
                                 } else {
-                                    self.lsp_progress.end_progress(server_id, &token);
-                                    if !self.lsp_progress.is_progressing(server_id) {
+                                    self.editor.lsp_progress.end_progress(server_id, &token);
+                                    if !self.editor.lsp_progress.is_progressing(server_id) {
                                         editor_view.spinners_mut().get_or_create(server_id).stop();
                                     }
                                     self.editor.clear_status();
@@ -967,15 +1204,25 @@ impl Application {
                         };
 
                         if let lsp::WorkDoneProgress::End(_) = work {
-                            self.lsp_progress.end_progress(server_id, &token);
-                            if !self.lsp_progress.is_progressing(server_id) {
+                            self.editor.lsp_progress.end_progress(server_id, &token);
+                            if !self.editor.lsp_progress.is_progressing(server_id) {
                                 editor_view.spinners_mut().get_or_create(server_id).stop();
                             }
                         } else {
-                            self.lsp_progress.update(server_id, token, work);
+                            self.editor.lsp_progress.update(server_id, token, work);
                         }
 
-                        if self.config.load().editor.lsp.display_messages {
+                        let display_messages = self.config.load().editor.lsp.display_messages
+                            || self
+                                .editor
+                                .language_servers
+                                .get_by_id(server_id)
+                                .map(|client| client.name())
+                                .and_then(|name| {
+                                    self.syn_loader.language_server_configs().get(name)
+                                })
+                                .map_or(false, |config| config.display_messages);
+                        if display_messages {
                             self.editor.set_status(status);
                         }
                     }
@@ -1033,7 +1280,7 @@ impl Application {
                         })
                     }
                     Ok(MethodCall::WorkDoneProgressCreate(params)) => {
-                        self.lsp_progress.create(server_id, params.token);
+                        self.editor.lsp_progress.create(server_id, params.token);
 
                         let editor_view = self
                             .compositor
@@ -1144,6 +1391,10 @@ impl Application {
             // So we just ignore the `Result`.
             let _ = TerminalBackend::force_restore();
             hook(info);
+            match crate::crash_report::write_report(info) {
+                Some(path) => eprintln!("crash report written to {}", path.display()),
+                None => eprintln!("failed to write crash report"),
+            }
         }));
 
         self.event_loop(input_stream).await;
@@ -1166,6 +1417,11 @@ impl Application {
         //        errors along the way
         let mut errs = Vec::new();
 
+        #[cfg(not(feature = "integration"))]
+        crate::history::save(&self.editor.registers);
+        #[cfg(not(feature = "integration"))]
+        self.editor.frecency.save();
+
         if let Err(err) = self
             .jobs
             .finish(&mut self.editor, Some(&mut self.compositor))
@@ -1187,6 +1443,21 @@ impl Application {
             ));
         }
 
+        if self.remote_requests.is_some() {
+            crate::ipc::cleanup();
+        }
+
         errs
     }
 }
+
+/// Resolves to the next `--remote` open request, or never when this
+/// instance is not the one serving the socket.
+async fn next_remote_request(
+    requests: &mut Option<tokio::sync::mpsc::UnboundedReceiver<crate::ipc::Request>>,
+) -> Option<crate::ipc::Request> {
+    match requests {
+        Some(requests) => requests.recv().await,
+        None => futures_util::future::pending().await,
+    }
+}
@@ -9,13 +9,18 @@ pub struct Args {
     pub display_version: bool,
     pub health: bool,
     pub health_arg: Option<String>,
+    pub completions: Option<String>,
     pub load_tutor: bool,
     pub fetch_grammars: bool,
     pub build_grammars: bool,
     pub split: Option<Layout>,
+    pub remote: bool,
+    pub session: Option<String>,
+    pub diff: Option<(PathBuf, PathBuf)>,
     pub verbosity: u64,
     pub log_file: Option<PathBuf>,
     pub config_file: Option<PathBuf>,
+    pub commands: Vec<String>,
     pub files: Vec<(PathBuf, Position)>,
 }
 
@@ -51,10 +56,35 @@ impl Args {
                         anyhow::bail!("--grammar must be followed by either 'fetch' or 'build'")
                     }
                 },
-                "-c" | "--config" => match argv.next().as_deref() {
+                "-c" => match argv.next() {
+                    // A leading colon queues a typable command to run once the
+                    // UI has initialized; otherwise `-c` keeps its historical
+                    // meaning of `--config <file>`.
+                    Some(command) if command.starts_with(':') => args.commands.push(command),
+                    Some(path) => args.config_file = Some(path.into()),
+                    None => anyhow::bail!("-c must specify a config file or a :command to run"),
+                },
+                "--config" => match argv.next().as_deref() {
                     Some(path) => args.config_file = Some(path.into()),
                     None => anyhow::bail!("--config must specify a path to read"),
                 },
+                "--command" => match argv.next() {
+                    Some(command) => args.commands.push(command),
+                    None => anyhow::bail!("--command must specify a command to run"),
+                },
+                "--diff" => match (argv.next(), argv.next()) {
+                    (Some(left), Some(right)) => args.diff = Some((left.into(), right.into())),
+                    _ => anyhow::bail!("--diff must specify two files to compare"),
+                },
+                "--completions" => match argv.next() {
+                    Some(shell) => args.completions = Some(shell),
+                    None => anyhow::bail!("--completions must specify a shell: bash, zsh or fish"),
+                },
+                "--remote" => args.remote = true,
+                "--session" => match argv.next() {
+                    Some(name) => args.session = Some(name),
+                    None => anyhow::bail!("--session must specify a session name"),
+                },
                 "--log" => match argv.next().as_deref() {
                     Some(path) => args.log_file = Some(path.into()),
                     None => anyhow::bail!("--log must specify a path to write"),
@@ -1,3 +1,4 @@
+pub(crate) mod ctags;
 pub(crate) mod dap;
 pub(crate) mod lsp;
 pub(crate) mod typed;
@@ -170,6 +171,10 @@ pub enum MappableCommand {
         fun: fn(cx: &mut Context),
         doc: &'static str,
     },
+    Macro {
+        name: String,
+        keys: Vec<KeyEvent>,
+    },
 }
 
 macro_rules! static_commands {
@@ -194,18 +199,41 @@ impl MappableCommand {
         match &self {
             Self::Typable { name, args, doc: _ } => {
                 let args: Vec<Cow<str>> = args.iter().map(Cow::from).collect();
+                let mut cx = compositor::Context {
+                    editor: cx.editor,
+                    jobs: cx.jobs,
+                    scroll: None,
+                };
                 if let Some(command) = typed::TYPABLE_COMMAND_MAP.get(name.as_str()) {
-                    let mut cx = compositor::Context {
-                        editor: cx.editor,
-                        jobs: cx.jobs,
-                        scroll: None,
-                    };
                     if let Err(e) = (command.fun)(&mut cx, &args[..], PromptEvent::Validate) {
                         cx.editor.set_error(format!("{}", e));
                     }
+                } else {
+                    match typed::execute_user_command(&mut cx, name, 0) {
+                        Some(Err(e)) => cx.editor.set_error(format!("{}", e)),
+                        Some(Ok(())) => (),
+                        None => cx.editor.set_error(format!("no such command: '{}'", name)),
+                    }
                 }
             }
             Self::Static { fun, .. } => (fun)(cx),
+            Self::Macro { keys, .. } => {
+                // Protect against recursive macros.
+                if cx.editor.macro_replaying.contains(&'@') {
+                    cx.editor.set_error(
+                        "Cannot execute macro because the [@] register is already playing a macro",
+                    );
+                    return;
+                }
+                cx.editor.macro_replaying.push('@');
+                let keys = keys.clone();
+                cx.callback = Some(Box::new(move |compositor, cx| {
+                    for key in keys {
+                        compositor.handle_event(&compositor::Event::Key(key), cx);
+                    }
+                    cx.editor.macro_replaying.pop();
+                }));
+            }
         }
     }
 
@@ -213,6 +241,7 @@ impl MappableCommand {
         match &self {
             Self::Typable { name, .. } => name,
             Self::Static { name, .. } => name,
+            Self::Macro { name, .. } => name,
         }
     }
 
@@ -220,6 +249,7 @@ impl MappableCommand {
         match &self {
             Self::Typable { doc, .. } => doc,
             Self::Static { doc, .. } => doc,
+            Self::Macro { name, .. } => name,
         }
     }
 
@@ -277,17 +307,23 @@ impl MappableCommand {
         select_regex, "Select all regex matches inside selections",
         split_selection, "Split selections on regex matches",
         split_selection_on_newline, "Split selection on newlines",
+        split_selection_on_column, "Split selection into per-line block selections between the anchor and cursor columns",
         merge_selections, "Merge selections",
         merge_consecutive_selections, "Merge consecutive selections",
         search, "Search for regex pattern",
         rsearch, "Reverse search for regex pattern",
+        search_in_selection, "Search for regex pattern inside the current selections",
         search_next, "Select next search match",
         search_prev, "Select previous search match",
         extend_search_next, "Add next search match to selection",
         extend_search_prev, "Add previous search match to selection",
         search_selection, "Use current selection as search pattern",
         make_search_word_bounded, "Modify current search to make it word bounded",
+        search_selection_next, "Use current selection or word under cursor as search pattern and go to the next occurrence",
+        search_selection_prev, "Use current selection or word under cursor as search pattern and go to the previous occurrence",
+        select_all_occurrences, "Select all occurrences of the current selection or word under cursor",
         global_search, "Global search in workspace folder",
+        todo_picker, "Open picker over TODO/FIXME comments in the workspace",
         extend_line, "Select current line, if already selected, extend to another line based on the anchor",
         extend_line_below, "Select current line, if already selected, extend to next line",
         extend_line_above, "Select current line, if already selected, extend to previous line",
@@ -306,9 +342,12 @@ impl MappableCommand {
         file_picker, "Open file picker",
         file_picker_in_current_buffer_directory, "Open file picker at current buffers's directory",
         file_picker_in_current_directory, "Open file picker at current working directory",
+        recent_file_picker, "Open picker over recently opened files",
+        recent_file_picker_in_workspace, "Open picker over recently opened files in the workspace",
         code_action, "Perform code action",
         buffer_picker, "Open buffer picker",
         jumplist_picker, "Open jumplist picker",
+        undo_history_picker, "Open undo history picker",
         symbol_picker, "Open symbol picker",
         select_references_to_symbol_under_cursor, "Select symbol references",
         workspace_symbol_picker, "Open workspace symbol picker",
@@ -323,6 +362,8 @@ impl MappableCommand {
         select_mode, "Enter selection extend mode",
         exit_select_mode, "Exit selection mode",
         goto_definition, "Goto definition",
+        goto_definition_hsplit, "Goto definition in horizontal split",
+        goto_definition_vsplit, "Goto definition in vertical split",
         goto_declaration, "Goto declaration",
         add_newline_above, "Add newline above",
         add_newline_below, "Add newline below",
@@ -333,7 +374,10 @@ impl MappableCommand {
         goto_file, "Goto files in selection",
         goto_file_hsplit, "Goto files in selection (hsplit)",
         goto_file_vsplit, "Goto files in selection (vsplit)",
+        open_url, "Open URL under cursor with the system opener",
         goto_reference, "Goto references",
+        goto_reference_hsplit, "Goto references in horizontal split",
+        goto_reference_vsplit, "Goto references in vertical split",
         goto_window_top, "Goto window top",
         goto_window_center, "Goto window center",
         goto_window_bottom, "Goto window bottom",
@@ -341,15 +385,21 @@ impl MappableCommand {
         goto_last_modified_file, "Goto last modified file",
         goto_last_modification, "Goto last modification",
         goto_line, "Goto line",
+        goto_line_percentage, "Goto line at <count> percent of the file, select all without a count",
+        goto_column, "Goto column <count> on the current line",
         goto_last_line, "Goto last line",
         goto_first_diag, "Goto first diagnostic",
         goto_last_diag, "Goto last diagnostic",
         goto_next_diag, "Goto next diagnostic",
         goto_prev_diag, "Goto previous diagnostic",
+        goto_next_quickfix, "Goto next quickfix entry",
+        goto_prev_quickfix, "Goto previous quickfix entry",
         goto_next_change, "Goto next change",
         goto_prev_change, "Goto previous change",
         goto_first_change, "Goto first change",
         goto_last_change, "Goto last change",
+        goto_next_conflict, "Goto next merge conflict",
+        goto_prev_conflict, "Goto previous merge conflict",
         goto_line_start, "Goto line start",
         goto_line_end, "Goto line end",
         goto_next_buffer, "Goto next buffer",
@@ -398,6 +448,7 @@ impl MappableCommand {
         keep_selections, "Keep selections matching regex",
         remove_selections, "Remove selections matching regex",
         align_selections, "Align selections in column",
+        align_cursors, "Align cursors to the column of the rightmost cursor by inserting padding",
         keep_primary_selection, "Keep primary selection",
         remove_primary_selection, "Remove primary selection",
         completion, "Invoke completion popup",
@@ -434,6 +485,8 @@ impl MappableCommand {
         wonly, "Close windows except current",
         select_register, "Select register",
         insert_register, "Insert register",
+        insert_unicode, "Insert character by hex codepoint",
+        insert_digraph, "Insert character by digraph",
         align_view_middle, "Align view middle",
         align_view_top, "Align view top",
         align_view_center, "Align view center",
@@ -467,6 +520,7 @@ impl MappableCommand {
         dap_step_out, "Step out",
         dap_next, "Step to next",
         dap_variables, "List variables",
+        dap_evaluate, "Evaluate expression under cursor in the current debug frame",
         dap_terminate, "End debug session",
         dap_edit_condition, "Edit breakpoint condition on current line",
         dap_edit_log, "Edit breakpoint log message on current line",
@@ -500,6 +554,11 @@ impl fmt::Debug for MappableCommand {
                 .field(name)
                 .field(args)
                 .finish(),
+            MappableCommand::Macro { name, keys, .. } => f
+                .debug_tuple("MappableCommand")
+                .field(name)
+                .field(keys)
+                .finish(),
         }
     }
 }
@@ -522,14 +581,26 @@ impl std::str::FromStr for MappableCommand {
             let args = typable_command
                 .map(|s| s.to_owned())
                 .collect::<Vec<String>>();
-            typed::TYPABLE_COMMAND_MAP
-                .get(name)
-                .map(|cmd| MappableCommand::Typable {
+            match typed::TYPABLE_COMMAND_MAP.get(name) {
+                Some(cmd) => Ok(MappableCommand::Typable {
                     name: cmd.name.to_owned(),
                     doc: format!(":{} {:?}", cmd.name, args),
                     args,
-                })
-                .ok_or_else(|| anyhow!("No TypableCommand named '{}'", s))
+                }),
+                // May be a user-defined command from `[commands]`; those are
+                // resolved at execution time since the keymap can be parsed
+                // before them.
+                None => Ok(MappableCommand::Typable {
+                    name: name.to_owned(),
+                    doc: format!(":{} {:?} (user-defined)", name, args),
+                    args,
+                }),
+            }
+        } else if let Some(suffix) = s.strip_prefix('@') {
+            helix_view::input::parse_macro(suffix).map(|keys| Self::Macro {
+                name: s.to_string(),
+                keys,
+            })
         } else {
             MappableCommand::STATIC_COMMAND_LIST
                 .iter()
@@ -573,6 +644,16 @@ impl PartialEq for MappableCommand {
                     name: second_name, ..
                 },
             ) => first_name == second_name,
+            (
+                MappableCommand::Macro {
+                    name: first_name,
+                    keys: first_keys,
+                },
+                MappableCommand::Macro {
+                    name: second_name,
+                    keys: second_keys,
+                },
+            ) => first_name == second_name && first_keys == second_keys,
             _ => false,
         }
     }
@@ -982,6 +1063,60 @@ fn align_selections(cx: &mut Context) {
     doc.apply(&transaction, view.id);
 }
 
+// pads with spaces, so like `align_selections` it only cares about "text
+// visual position" and uses the deprecated `visual_coords_at_pos`
+#[allow(deprecated)]
+fn align_cursors(cx: &mut Context) {
+    use helix_core::visual_coords_at_pos;
+
+    let (view, doc) = current!(cx.editor);
+    let text = doc.text().slice(..);
+    let selection = doc.selection(view.id);
+
+    let tab_width = doc.tab_width();
+    let cursors: Vec<_> = selection
+        .iter()
+        .map(|range| {
+            let cursor = range.cursor(text);
+            (cursor, visual_coords_at_pos(text, cursor, tab_width))
+        })
+        .collect();
+
+    let max_col = cursors
+        .iter()
+        .map(|(_, coords)| coords.col)
+        .max()
+        .unwrap_or(0);
+
+    // Selections are sorted, so cursors sharing a line are visited in order
+    // and earlier insertions on the same line can be accounted for.
+    let mut changes = Vec::with_capacity(cursors.len());
+    let mut last_line = text.len_lines() + 1;
+    let mut off = 0;
+
+    for (pos, coords) in cursors {
+        if coords.row != last_line {
+            off = 0;
+            last_line = coords.row;
+        }
+
+        let ins_count = max_col.saturating_sub(coords.col + off);
+        if ins_count == 0 {
+            continue;
+        }
+        off += ins_count;
+
+        changes.push((pos, pos, Some(" ".repeat(ins_count).into())));
+    }
+
+    if changes.is_empty() {
+        return;
+    }
+
+    let transaction = Transaction::change(doc.text(), changes.into_iter());
+    doc.apply(&transaction, view.id);
+}
+
 fn goto_window(cx: &mut Context, align: Align) {
     let count = cx.count() - 1;
     let config = cx.editor.config();
@@ -1182,16 +1317,122 @@ fn goto_file_impl(cx: &mut Context, action: Action) {
                 .to_string(),
         );
     }
+    let doc_dir = doc
+        .path()
+        .and_then(|path| path.parent().map(Path::to_path_buf));
+    let include_dirs = cx.editor.config().goto_file_include_dirs.clone();
+
     for sel in paths {
         let p = sel.trim();
-        if !p.is_empty() {
-            if let Err(e) = cx.editor.open(&PathBuf::from(p), action) {
-                cx.editor.set_error(format!("Open file failed: {:?}", e));
+        if p.is_empty() {
+            continue;
+        }
+        // compiler-output-style `file:line:col` suffixes move the cursor
+        // after opening
+        let (path, pos) = crate::args::parse_file(p);
+        let path = resolve_path_reference(&path, doc_dir.as_deref(), &include_dirs)
+            // let open() report the failure for unresolvable paths
+            .unwrap_or(path);
+        match cx.editor.open(&path, action) {
+            Ok(_) => {
+                if pos != Position::default() {
+                    let (view, doc) = current!(cx.editor);
+                    let selection = Selection::point(pos_at_coords(doc.text().slice(..), pos, true));
+                    doc.set_selection(view.id, selection);
+                    align_view(doc, view, Align::Center);
+                }
             }
+            Err(e) => cx.editor.set_error(format!("Open file failed: {:?}", e)),
         }
     }
 }
 
+/// Resolve a relative path reference against the working directory, the
+/// document's directory, the workspace root, and the configured include
+/// directories, in that order.
+fn resolve_path_reference(
+    path: &Path,
+    doc_dir: Option<&Path>,
+    include_dirs: &[PathBuf],
+) -> Option<PathBuf> {
+    if path.is_absolute() || path.exists() {
+        return Some(path.to_path_buf());
+    }
+    let roots = doc_dir
+        .into_iter()
+        .map(Path::to_path_buf)
+        .chain(std::iter::once(helix_loader::find_workspace().0))
+        .chain(include_dirs.iter().cloned());
+    for root in roots {
+        let candidate = root.join(path);
+        if candidate.exists() {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+/// Open the URL in the selection (or the WORD under the cursor) with the
+/// platform opener.
+fn open_url(cx: &mut Context) {
+    let (view, doc) = current_ref!(cx.editor);
+    let text = doc.text();
+    let primary = doc.selection(view.id).primary();
+
+    let url = if primary.len() > 1 {
+        text.slice(primary.from()..primary.to()).to_string()
+    } else {
+        let text_slice = text.slice(..);
+        let word = textobject::textobject_word(
+            text_slice,
+            primary,
+            textobject::TextObject::Inside,
+            1,
+            true,
+        );
+        word.fragment(text_slice).to_string()
+    };
+    // Trim quoting and punctuation that tends to surround URLs in prose
+    // and config files.
+    let surrounding_chars: &[_] = &['\'', '"', '<', '>', '(', ')', '[', ']', ',', ';', '.'];
+    let url = url.trim().trim_matches(surrounding_chars).to_string();
+
+    if !(url.contains("://") || url.starts_with("www.")) {
+        cx.editor.set_error("no URL under cursor");
+        return;
+    }
+
+    use std::process::{Command, Stdio};
+    let result = if cfg!(windows) {
+        // `start` is a cmd builtin; the empty string is the window title
+        Command::new("cmd")
+            .args(["/c", "start", ""])
+            .arg(&url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    } else {
+        let opener = if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+        Command::new(opener)
+            .arg(&url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    };
+
+    match result {
+        Ok(_) => cx.editor.set_status(format!("opened {}", url)),
+        // no opener available; at least surface the URL for copying
+        Err(_) => cx.editor.set_status(format!("unable to spawn opener, URL: {}", url)),
+    }
+}
+
 fn extend_word_impl<F>(cx: &mut Context, extend_fn: F)
 where
     F: Fn(RopeSlice, Range, usize) -> Range,
@@ -1495,9 +1736,52 @@ fn switch_to_lowercase(cx: &mut Context) {
     });
 }
 
+/// Apply the same visual-line scroll `offset` to every other view that has
+/// scroll binding enabled, see `:scroll-bind`.
+fn scroll_bound_views(editor: &mut Editor, focused: ViewId, offset: isize) {
+    let bound: Vec<ViewId> = editor
+        .tree
+        .views()
+        .filter(|(view, _)| view.scroll_bind && view.id != focused)
+        .map(|(view, _)| view.id)
+        .collect();
+
+    for id in bound {
+        let doc_id = editor.tree.get(id).doc;
+        let doc = match editor.documents.get(&doc_id) {
+            Some(doc) => doc,
+            None => continue,
+        };
+        let view = editor.tree.get_mut(id);
+        let doc_text = doc.text().slice(..);
+        let viewport = view.inner_area(doc);
+        let text_fmt = doc.text_format(viewport.width, None);
+        let annotations = view.text_annotations(doc, None);
+        (view.offset.anchor, view.offset.vertical_offset) = char_idx_at_visual_offset(
+            doc_text,
+            view.offset.anchor,
+            view.offset.vertical_offset as isize + offset,
+            0,
+            &text_fmt,
+            &annotations,
+        );
+    }
+}
+
 pub fn scroll(cx: &mut Context, offset: usize, direction: Direction) {
     use Direction::*;
     let config = cx.editor.config();
+
+    let offset = match direction {
+        Forward => offset as isize,
+        Backward => -(offset as isize),
+    };
+
+    if view!(cx.editor).scroll_bind {
+        let focused = view!(cx.editor).id;
+        scroll_bound_views(cx.editor, focused, offset);
+    }
+
     let (view, doc) = current!(cx.editor);
 
     let range = doc.selection(view.id).primary();
@@ -1507,10 +1791,6 @@ pub fn scroll(cx: &mut Context, offset: usize, direction: Direction) {
     let height = view.inner_height();
 
     let scrolloff = config.scrolloff.min(height.saturating_sub(1) / 2);
-    let offset = match direction {
-        Forward => offset as isize,
-        Backward => -(offset as isize),
-    };
 
     let doc_text = doc.text().slice(..);
     let viewport = view.inner_area(doc);
@@ -1574,25 +1854,25 @@ pub fn scroll(cx: &mut Context, offset: usize, direction: Direction) {
 
 fn page_up(cx: &mut Context) {
     let view = view!(cx.editor);
-    let offset = view.inner_height();
+    let offset = view.inner_height() * cx.count();
     scroll(cx, offset, Direction::Backward);
 }
 
 fn page_down(cx: &mut Context) {
     let view = view!(cx.editor);
-    let offset = view.inner_height();
+    let offset = view.inner_height() * cx.count();
     scroll(cx, offset, Direction::Forward);
 }
 
 fn half_page_up(cx: &mut Context) {
     let view = view!(cx.editor);
-    let offset = view.inner_height() / 2;
+    let offset = view.inner_height() / 2 * cx.count();
     scroll(cx, offset, Direction::Backward);
 }
 
 fn half_page_down(cx: &mut Context) {
     let view = view!(cx.editor);
-    let offset = view.inner_height() / 2;
+    let offset = view.inner_height() / 2 * cx.count();
     scroll(cx, offset, Direction::Forward);
 }
 
@@ -1750,6 +2030,69 @@ fn split_selection_on_newline(cx: &mut Context) {
     doc.set_selection(view.id, selection);
 }
 
+// Classic block selection on tabular data: every line of a multi-line
+// selection gets its own range covering the anchor-column..cursor-column
+// rectangle. Lines that end inside the rectangle are clamped to their line
+// ending and lines that end before its left edge are skipped, so the result
+// stays a valid selection without virtual padding.
+//
+// like `copy_selection_on_line` this uses the deprecated
+// `visual_coords_at_pos`/`pos_at_visual_coords` functions as it ignores
+// softwrapping (and virtual text) and only cares about "text visual position"
+#[allow(deprecated)]
+fn split_selection_on_column(cx: &mut Context) {
+    use helix_core::{pos_at_visual_coords, visual_coords_at_pos};
+
+    let (view, doc) = current!(cx.editor);
+    let text = doc.text().slice(..);
+    let selection = doc.selection(view.id);
+    let tab_width = doc.tab_width();
+
+    let mut ranges = SmallVec::with_capacity(selection.ranges().len());
+    let mut primary_index = 0;
+
+    for range in selection.iter() {
+        let is_primary = *range == selection.primary();
+
+        // The range is always head exclusive
+        let (head, anchor) = if range.anchor < range.head {
+            (range.head - 1, range.anchor)
+        } else {
+            (range.head, range.anchor.saturating_sub(1))
+        };
+
+        let head_pos = visual_coords_at_pos(text, head, tab_width);
+        let anchor_pos = visual_coords_at_pos(text, anchor, tab_width);
+
+        let first_row = std::cmp::min(head_pos.row, anchor_pos.row);
+        let last_row = std::cmp::max(head_pos.row, anchor_pos.row);
+        let left_col = std::cmp::min(head_pos.col, anchor_pos.col);
+        let right_col = std::cmp::max(head_pos.col, anchor_pos.col);
+
+        for row in first_row..=last_row {
+            let left = pos_at_visual_coords(text, Position::new(row, left_col), tab_width);
+
+            // skip lines that end before the left edge of the block
+            if visual_coords_at_pos(text, left, tab_width).col != left_col {
+                continue;
+            }
+
+            let right = pos_at_visual_coords(text, Position::new(row, right_col), tab_width);
+            let range = Range::new(left, graphemes::next_grapheme_boundary(text, right));
+            if is_primary && row == head_pos.row {
+                primary_index = ranges.len();
+            }
+            ranges.push(range);
+        }
+    }
+
+    if ranges.is_empty() {
+        return;
+    }
+
+    doc.set_selection(view.id, Selection::new(ranges, primary_index));
+}
+
 fn merge_selections(cx: &mut Context) {
     let (view, doc) = current!(cx.editor);
     let selection = doc.selection(view.id).clone().merge_ranges();
@@ -1773,6 +2116,11 @@ fn search_impl(
     wrap_around: bool,
     show_warnings: bool,
 ) {
+    // Keep all matches highlighted while the search is "active", i.e. until
+    // `:clear-search-highlight` is used.
+    editor.search_matches = Some(regex.clone());
+    editor.search_wrapped_match = None;
+
     let (view, doc) = current!(editor);
     let text = doc.text().slice(..);
     let selection = doc.selection(view.id);
@@ -1820,6 +2168,11 @@ fn search_impl(
                 editor.set_error("No more matches");
             }
         }
+        // Highlight the match we wrapped around to with a distinct scope.
+        editor.search_wrapped_match = mat.map(|mat| {
+            let text = doc!(editor).text();
+            text.byte_to_char(mat.start() + offset)..text.byte_to_char(mat.end() + offset)
+        });
     }
 
     let (view, doc) = current!(editor);
@@ -1866,6 +2219,99 @@ fn rsearch(cx: &mut Context) {
     searcher(cx, Direction::Backward)
 }
 
+fn search_in_selection(cx: &mut Context) {
+    let reg = cx.register.unwrap_or('/');
+    let config = cx.editor.config();
+    let scrolloff = config.scrolloff;
+    let wrap_around = config.search.wrap_around;
+
+    let (view, doc) = current_ref!(cx.editor);
+    let text = doc.text().slice(..);
+    let contents = text.to_string();
+
+    // Snapshot the current selections; matches outside of them are ignored.
+    // `regex_prompt` restores the selection before every update, so the
+    // byte ranges stay valid while the prompt is open.
+    let bounds: Vec<(usize, usize)> = doc
+        .selection(view.id)
+        .ranges()
+        .iter()
+        .map(|range| (text.char_to_byte(range.from()), text.char_to_byte(range.to())))
+        .collect();
+
+    let completions = search_completions(cx, Some(reg));
+
+    ui::regex_prompt(
+        cx,
+        "search-in-selection:".into(),
+        Some(reg),
+        move |_editor: &Editor, input: &str| {
+            completions
+                .iter()
+                .filter(|comp| comp.starts_with(input))
+                .map(|comp| (0.., std::borrow::Cow::Owned(comp.clone())))
+                .collect()
+        },
+        move |editor, regex, event| {
+            if !matches!(event, PromptEvent::Update | PromptEvent::Validate) {
+                return;
+            }
+
+            let (view, doc) = current!(editor);
+            let text = doc.text().slice(..);
+
+            let cursor = text.char_to_byte(graphemes::ensure_grapheme_boundary_next(
+                text,
+                doc.selection(view.id).primary().to(),
+            ));
+
+            let next_match = bounds
+                .iter()
+                .flat_map(|&(start, end)| {
+                    regex
+                        .find_iter(&contents[start..end])
+                        .map(move |mat| (start + mat.start(), start + mat.end()))
+                })
+                .filter(|&(_, end)| end != 0)
+                .fold((None, None), |(first, next), mat| {
+                    (
+                        first.or(Some(mat)),
+                        next.or_else(|| (mat.0 >= cursor).then_some(mat)),
+                    )
+                });
+
+            let (mat, wrapped) = match next_match {
+                (_, Some(mat)) => (mat, false),
+                (Some(first), None) if wrap_around => (first, true),
+                (first, None) => {
+                    if event == PromptEvent::Validate {
+                        if first.is_some() {
+                            editor.set_error("No more matches inside selection");
+                        } else {
+                            editor.set_error("No matches inside selection");
+                        }
+                    }
+                    return;
+                }
+            };
+
+            if wrapped && event == PromptEvent::Validate {
+                editor.set_status("Wrapped around selection");
+            }
+
+            let (view, doc) = current!(editor);
+            let text = doc.text().slice(..);
+            let selection = doc.selection(view.id);
+            let range = Range::new(text.byte_to_char(mat.0), text.byte_to_char(mat.1))
+                .with_direction(selection.primary().direction());
+            let selection = selection.clone().replace(selection.primary_index(), range);
+
+            doc.set_selection(view.id, selection);
+            view.ensure_cursor_in_view_center(doc, scrolloff);
+        },
+    );
+}
+
 fn searcher(cx: &mut Context, direction: Direction) {
     let reg = cx.register.unwrap_or('/');
     let config = cx.editor.config();
@@ -1904,7 +2350,9 @@ fn searcher(cx: &mut Context, direction: Direction) {
                 direction,
                 scrolloff,
                 wrap_around,
-                false,
+                // Only report wrapping or a missing match once the pattern is
+                // confirmed, interactive updates would be too noisy.
+                event == PromptEvent::Validate,
             );
         },
     );
@@ -1925,7 +2373,15 @@ fn search_next_or_prev_impl(cx: &mut Context, movement: Movement, direction: Dir
             false
         };
         let wrap_around = search_config.wrap_around;
-        if let Ok(regex) = RegexBuilder::new(query)
+        let mut pattern = if search_config.literal {
+            regex::escape(query)
+        } else {
+            query.clone()
+        };
+        if search_config.whole_word {
+            pattern = format!(r"\b(?:{})\b", pattern);
+        }
+        if let Ok(regex) = RegexBuilder::new(&pattern)
             .case_insensitive(case_insensitive)
             .multi_line(true)
             .build()
@@ -2011,6 +2467,75 @@ fn make_search_word_bounded(cx: &mut Context) {
     cx.editor.set_status(msg);
 }
 
+// Build a regex pattern matching the current selections, expanding 1-width
+// cursors to the word underneath them. Expanded words are word bounded so
+// `foo` does not match inside `foobar`.
+fn selection_search_pattern(doc: &Document, view: &View) -> String {
+    let contents = doc.text().slice(..);
+    doc.selection(view.id)
+        .iter()
+        .map(|range| {
+            if range.len() <= 1 {
+                let word = textobject::textobject_word(
+                    contents,
+                    *range,
+                    textobject::TextObject::Inside,
+                    1,
+                    false,
+                );
+                format!(r"\b(?:{})\b", regex::escape(&word.fragment(contents)))
+            } else {
+                regex::escape(&range.fragment(contents))
+            }
+        })
+        .collect::<HashSet<_>>() // Collect into hashset to deduplicate identical regexes
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+fn search_selection_jump(cx: &mut Context, direction: Direction) {
+    let (view, doc) = current!(cx.editor);
+    let pattern = selection_search_pattern(doc, view);
+    let msg = format!("register '{}' set to '{}'", '/', &pattern);
+    cx.editor.registers.push('/', pattern);
+    cx.editor.set_status(msg);
+    search_next_or_prev_impl(cx, Movement::Move, direction);
+}
+
+fn search_selection_next(cx: &mut Context) {
+    search_selection_jump(cx, Direction::Forward);
+}
+
+fn search_selection_prev(cx: &mut Context) {
+    search_selection_jump(cx, Direction::Backward);
+}
+
+fn select_all_occurrences(cx: &mut Context) {
+    let (view, doc) = current!(cx.editor);
+    let pattern = selection_search_pattern(doc, view);
+    let regex = match RegexBuilder::new(&pattern).multi_line(true).build() {
+        Ok(regex) => regex,
+        Err(err) => {
+            cx.editor.set_error(format!("Invalid regex: {}", err));
+            return;
+        }
+    };
+
+    let text = doc.text().slice(..);
+    let Some(selection) =
+        selection::select_on_matches(text, &Selection::single(0, text.len_chars()), &regex)
+    else {
+        cx.editor.set_error("No occurrences found");
+        return;
+    };
+
+    let count = selection.len();
+    doc.set_selection(view.id, selection);
+    cx.editor
+        .set_status(format!("{} occurrences selected", count));
+}
+
 fn global_search(cx: &mut Context) {
     #[derive(Debug)]
     struct FileResult {
@@ -2195,6 +2720,172 @@ fn global_search(cx: &mut Context) {
     cx.jobs.callback(show_picker);
 }
 
+/// Scan the workspace for the configured `todo-keywords` (TODO, FIXME, ...)
+/// and open a picker over the hits with file:line context.
+fn todo_picker(cx: &mut Context) {
+    #[derive(Debug)]
+    struct TodoResult {
+        path: PathBuf,
+        /// 0 indexed lines
+        line_num: usize,
+        /// the matching line, trimmed, for display in the picker
+        line: String,
+    }
+
+    impl ui::menu::Item for TodoResult {
+        type Data = ();
+
+        fn format(&self, _data: &Self::Data) -> Row {
+            let relative_path = helix_core::path::get_relative_path(&self.path)
+                .to_string_lossy()
+                .into_owned();
+            format!("{}:{}: {}", relative_path, self.line_num + 1, self.line).into()
+        }
+    }
+
+    let config = cx.editor.config();
+    let file_picker_config = config.file_picker.clone();
+
+    if config.todo_keywords.is_empty() {
+        cx.editor.set_error("todo-keywords is empty");
+        return;
+    }
+    // Match the keywords as whole words so e.g. HACK does not hit
+    // "HACKERNEWS"; the keywords themselves are matched literally.
+    let pattern = format!(
+        "\\b(?:{})\\b",
+        config
+            .todo_keywords
+            .iter()
+            .map(|keyword| regex::escape(keyword))
+            .collect::<Vec<_>>()
+            .join("|")
+    );
+    let matcher = match RegexMatcherBuilder::new().build(&pattern) {
+        Ok(matcher) => matcher,
+        Err(err) => {
+            cx.editor.set_error(format!("invalid todo-keywords: {}", err));
+            return;
+        }
+    };
+
+    let (all_matches_sx, all_matches_rx) = tokio::sync::mpsc::unbounded_channel::<TodoResult>();
+
+    let searcher = SearcherBuilder::new()
+        .binary_detection(BinaryDetection::quit(b'\x00'))
+        .build();
+
+    let search_root =
+        std::env::current_dir().expect("Todo picker error: Failed to get current dir");
+    let dedup_symlinks = file_picker_config.deduplicate_links;
+    let absolute_root = search_root
+        .canonicalize()
+        .unwrap_or_else(|_| search_root.clone());
+
+    WalkBuilder::new(search_root)
+        .hidden(file_picker_config.hidden)
+        .parents(file_picker_config.parents)
+        .ignore(file_picker_config.ignore)
+        .follow_links(file_picker_config.follow_symlinks)
+        .git_ignore(file_picker_config.git_ignore)
+        .git_global(file_picker_config.git_global)
+        .git_exclude(file_picker_config.git_exclude)
+        .max_depth(file_picker_config.max_depth)
+        .filter_entry(move |entry| filter_picker_entry(entry, &absolute_root, dedup_symlinks))
+        .build_parallel()
+        .run(|| {
+            let mut searcher = searcher.clone();
+            let matcher = matcher.clone();
+            let all_matches_sx = all_matches_sx.clone();
+            Box::new(move |entry: Result<DirEntry, ignore::Error>| -> WalkState {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(_) => return WalkState::Continue,
+                };
+
+                match entry.file_type() {
+                    Some(entry) if entry.is_file() => {}
+                    // skip everything else
+                    _ => return WalkState::Continue,
+                };
+
+                let result = searcher.search_path(
+                    &matcher,
+                    entry.path(),
+                    sinks::UTF8(|line_num, line| {
+                        all_matches_sx
+                            .send(TodoResult {
+                                path: entry.path().to_path_buf(),
+                                line_num: line_num as usize - 1,
+                                line: line.trim().to_string(),
+                            })
+                            .unwrap();
+
+                        Ok(true)
+                    }),
+                );
+
+                if let Err(err) = result {
+                    log::error!("Todo picker error: {}, {}", entry.path().display(), err);
+                }
+                WalkState::Continue
+            })
+        });
+
+    let show_picker = async move {
+        let mut all_matches: Vec<TodoResult> =
+            UnboundedReceiverStream::new(all_matches_rx).collect().await;
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |editor: &mut Editor, compositor: &mut Compositor| {
+                if all_matches.is_empty() {
+                    editor.set_status("No todo comments found");
+                    return;
+                }
+                all_matches.sort_unstable_by(|a, b| {
+                    (&a.path, a.line_num).cmp(&(&b.path, b.line_num))
+                });
+
+                let picker = Picker::new(
+                    all_matches,
+                    (),
+                    move |cx, TodoResult { path, line_num, .. }, action| {
+                        match cx.editor.open(path, action) {
+                            Ok(_) => {}
+                            Err(e) => {
+                                cx.editor.set_error(format!(
+                                    "Failed to open file '{}': {}",
+                                    path.display(),
+                                    e
+                                ));
+                                return;
+                            }
+                        }
+
+                        let line_num = *line_num;
+                        let (view, doc) = current!(cx.editor);
+                        let text = doc.text();
+                        if line_num >= text.len_lines() {
+                            cx.editor.set_error("The line you jumped to does not exist anymore because the file has changed.");
+                            return;
+                        }
+                        let start = text.line_to_char(line_num);
+                        let end = text.line_to_char((line_num + 1).min(text.len_lines()));
+
+                        doc.set_selection(view.id, Selection::single(start, end));
+                        align_view(doc, view, Align::Center);
+                    },
+                )
+                .with_preview(|_editor, TodoResult { path, line_num, .. }| {
+                    Some((path.clone().into(), Some((*line_num, *line_num))))
+                });
+                compositor.push(Box::new(overlaid(picker)));
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(show_picker);
+}
+
 enum Extend {
     Above,
     Below,
@@ -2497,7 +3188,7 @@ fn append_mode(cx: &mut Context) {
 
 fn file_picker(cx: &mut Context) {
     let root = find_workspace().0;
-    let picker = ui::file_picker(root, &cx.editor.config());
+    let picker = ui::file_picker(root, cx.editor);
     cx.push_layer(Box::new(overlaid(picker)));
 }
 
@@ -2514,12 +3205,51 @@ fn file_picker_in_current_buffer_directory(cx: &mut Context) {
         }
     };
 
-    let picker = ui::file_picker(path, &cx.editor.config());
+    let picker = ui::file_picker(path, cx.editor);
     cx.push_layer(Box::new(overlaid(picker)));
 }
 fn file_picker_in_current_directory(cx: &mut Context) {
     let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("./"));
-    let picker = ui::file_picker(cwd, &cx.editor.config());
+    let picker = ui::file_picker(cwd, cx.editor);
+    cx.push_layer(Box::new(overlaid(picker)));
+}
+
+fn recent_file_picker(cx: &mut Context) {
+    recent_file_picker_impl(cx, None);
+}
+
+fn recent_file_picker_in_workspace(cx: &mut Context) {
+    let root = find_workspace().0;
+    recent_file_picker_impl(cx, Some(root));
+}
+
+fn recent_file_picker_impl(cx: &mut Context, scope: Option<PathBuf>) {
+    // The frecency history stores canonicalized paths, so stale entries can
+    // be dropped by checking for existence directly.
+    let files: Vec<PathBuf> = cx
+        .editor
+        .frecency
+        .recent_paths(scope.as_deref())
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect();
+    if files.is_empty() {
+        cx.editor.set_status("no recently opened files");
+        return;
+    }
+
+    let root = std::env::current_dir().unwrap_or_default();
+    let picker = Picker::new(files, root, move |cx, path: &PathBuf, action| {
+        if let Err(e) = cx.editor.open(path, action) {
+            let err = if let Some(err) = e.source() {
+                format!("{}", err)
+            } else {
+                format!("unable to open \"{}\"", path.display())
+            };
+            cx.editor.set_error(err);
+        }
+    })
+    .with_preview(|_editor, path| Some((path.clone().into(), None)));
     cx.push_layer(Box::new(overlaid(picker)));
 }
 
@@ -2597,6 +3327,7 @@ fn jumplist_picker(cx: &mut Context) {
         id: DocumentId,
         path: Option<PathBuf>,
         selection: Selection,
+        line: usize,
         text: String,
         is_current: bool,
     }
@@ -2624,7 +3355,7 @@ fn jumplist_picker(cx: &mut Context) {
             } else {
                 format!(" ({})", flags.join(""))
             };
-            format!("{} {}{} {}", self.id, path, flag, self.text).into()
+            format!("{} {}:{}{} {}", self.id, path, self.line + 1, flag, self.text).into()
         }
     }
 
@@ -2637,18 +3368,27 @@ fn jumplist_picker(cx: &mut Context) {
 
     let new_meta = |view: &View, doc_id: DocumentId, selection: Selection| {
         let doc = &cx.editor.documents.get(&doc_id);
+        // keep the preview on a single row even when the jump selection
+        // spans multiple lines
         let text = doc.map_or("".into(), |d| {
             selection
                 .fragments(d.text().slice(..))
                 .map(Cow::into_owned)
                 .collect::<Vec<_>>()
                 .join(" ")
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+        let line = doc.map_or(0, |d| {
+            selection.primary().cursor_line(d.text().slice(..))
         });
 
         JumpMeta {
             id: doc_id,
             path: doc.and_then(|d| d.path().cloned()),
             selection,
+            line,
             text,
             is_current: view.doc == doc_id,
         }
@@ -2659,8 +3399,11 @@ fn jumplist_picker(cx: &mut Context) {
             .tree
             .views()
             .flat_map(|(view, _)| {
+                // most recent jumps first, mirroring the order `jump_backward`
+                // would visit them in
                 view.jumps
                     .iter()
+                    .rev()
                     .map(|(doc_id, selection)| new_meta(view, *doc_id, selection.clone()))
             })
             .collect(),
@@ -2681,6 +3424,183 @@ fn jumplist_picker(cx: &mut Context) {
     cx.push_layer(Box::new(overlaid(picker)));
 }
 
+fn undo_history_picker(cx: &mut Context) {
+    struct RevisionMeta {
+        revision: usize,
+        age: String,
+        summary: String,
+        is_current: bool,
+        preview: PathBuf,
+    }
+
+    impl ui::menu::Item for RevisionMeta {
+        type Data = ();
+
+        fn format(&self, _data: &Self::Data) -> Row {
+            let flag = if self.is_current { " (*)" } else { "" };
+            Row::new([
+                format!("{}{}", self.revision, flag),
+                self.age.clone(),
+                self.summary.clone(),
+            ])
+        }
+    }
+
+    /// The whole lines containing the char range `from..to`, as a half-open
+    /// line range. Empty for an empty char range, so pure insertions don't
+    /// list untouched lines of the other revision.
+    fn line_span(text: &Rope, from: usize, to: usize) -> (usize, usize) {
+        let start = text.char_to_line(from.min(text.len_chars()));
+        if to <= from {
+            return (start, start);
+        }
+        let to = to.min(text.len_chars());
+        let line = text.char_to_line(to);
+        let end = if text.line_to_char(line) == to {
+            line
+        } else {
+            line + 1
+        };
+        (start, end.max(start))
+    }
+
+    /// Render the difference between two revisions as a unified-diff-like
+    /// listing of whole lines, returning it together with the number of
+    /// added and removed lines.
+    fn revision_diff(old: &Rope, new: &Rope) -> (String, usize, usize) {
+        use std::fmt::Write;
+
+        let transaction = helix_core::diff::compare_ropes(old, new);
+
+        // Merge the character-level changes into whole-line hunks. Changes
+        // are in ascending order, so only adjacent hunks need merging.
+        let mut hunks: Vec<(usize, usize, usize, usize)> = Vec::new();
+        let mut offset = 0isize;
+        for (from, to, fragment) in transaction.changes_iter() {
+            let fragment_len = fragment.as_ref().map_or(0, |f| f.chars().count());
+            let new_from = (from as isize + offset) as usize;
+            let new_to = new_from + fragment_len;
+            offset += fragment_len as isize - (to - from) as isize;
+
+            let (old_start, old_end) = line_span(old, from, to);
+            let (new_start, new_end) = line_span(new, new_from, new_to);
+            match hunks.last_mut() {
+                Some(hunk) if old_start <= hunk.1 => {
+                    hunk.1 = old_end.max(hunk.1);
+                    hunk.3 = new_end.max(hunk.3);
+                }
+                _ => hunks.push((old_start, old_end, new_start, new_end)),
+            }
+        }
+
+        let mut diff = String::new();
+        let (mut added, mut removed) = (0, 0);
+        for &(old_start, old_end, new_start, new_end) in &hunks {
+            let _ = writeln!(
+                diff,
+                "@@ -{},{} +{},{} @@",
+                old_start + 1,
+                old_end - old_start,
+                new_start + 1,
+                new_end - new_start
+            );
+            for line in old_start..old_end {
+                removed += 1;
+                diff.push('-');
+                diff.extend(old.line(line).chunks());
+                if !diff.ends_with('\n') {
+                    diff.push('\n');
+                }
+            }
+            for line in new_start..new_end {
+                added += 1;
+                diff.push('+');
+                diff.extend(new.line(line).chunks());
+                if !diff.ends_with('\n') {
+                    diff.push('\n');
+                }
+            }
+        }
+        (diff, added, removed)
+    }
+
+    fn format_age(duration: std::time::Duration) -> String {
+        let secs = duration.as_secs();
+        if secs < 5 {
+            "just now".to_string()
+        } else if secs < 60 {
+            format!("{}s ago", secs)
+        } else if secs < 3600 {
+            format!("{}m ago", secs / 60)
+        } else if secs < 86400 {
+            format!("{}h ago", secs / 3600)
+        } else {
+            format!("{}d ago", secs / 86400)
+        }
+    }
+
+    let config = cx.editor.config.clone();
+    let doc = doc_mut!(cx.editor);
+    let text = doc.text().clone();
+    let history = doc.history.take();
+    let current = history.current_revision();
+
+    let now = std::time::Instant::now();
+    let mut metas = Vec::with_capacity(history.revision_count());
+    let mut previews = Vec::with_capacity(history.revision_count());
+    for revision in 0..history.revision_count() {
+        let mut revision_text = text.clone();
+        if let Some(transaction) = history.changes_until(revision) {
+            transaction.apply(&mut revision_text);
+        }
+        let (diff, added, removed) = revision_diff(&text, &revision_text);
+        let summary = if revision == current {
+            "current state".to_string()
+        } else {
+            format!("+{} -{}", added, removed)
+        };
+        let age = format_age(now.saturating_duration_since(history.revision_timestamp(revision)));
+
+        // The path only keys the preview cache and provides the extension
+        // for language detection, no file is ever read from it.
+        let preview = PathBuf::from(format!("/undo/revision-{}.diff", revision));
+        let mut preview_doc = Document::from(Rope::from(diff), None, config.clone());
+        let _ = preview_doc.set_path(Some(&preview));
+        previews.push((preview.clone(), preview_doc));
+
+        metas.push(RevisionMeta {
+            revision,
+            age,
+            summary,
+            is_current: revision == current,
+            preview,
+        });
+    }
+    doc.history.set(history);
+
+    // most recent revision on top
+    metas.reverse();
+
+    let mut picker = Picker::new(metas, (), |cx, meta, _action| {
+        let (view, doc) = current!(cx.editor);
+        let current = doc.history.get_mut().current_revision();
+        use std::cmp::Ordering;
+        let success = match meta.revision.cmp(&current) {
+            Ordering::Less => doc.earlier(view, UndoKind::Steps(current - meta.revision)),
+            Ordering::Greater => doc.later(view, UndoKind::Steps(meta.revision - current)),
+            Ordering::Equal => return,
+        };
+        if !success {
+            cx.editor.set_error("Failed to jump to revision");
+        }
+    })
+    .with_preview(|_editor, meta| Some((meta.preview.clone().into(), None)));
+    for (path, preview_doc) in previews {
+        picker = picker.with_preview_document(path, preview_doc);
+    }
+    cx.push_layer(Box::new(overlaid(picker)));
+}
+
 impl ui::menu::Item for MappableCommand {
     type Data = ReverseKeymap;
 
@@ -2706,6 +3626,10 @@ impl ui::menu::Item for MappableCommand {
                 Some(bindings) => format!("{} ({}) [{}]", doc, fmt_binding(bindings), name).into(),
                 None => format!("{} [{}]", doc, name).into(),
             },
+            MappableCommand::Macro { name, .. } => match keymap.get(name as &String) {
+                Some(bindings) => format!("{} ({})", name, fmt_binding(bindings)).into(),
+                None => name.clone().into(),
+            },
         }
     }
 }
@@ -2907,12 +3831,16 @@ pub enum Open {
 
 fn open(cx: &mut Context, open: Open) {
     let count = cx.count();
+    let continue_comments = cx.editor.config().continue_comments;
     enter_insert_mode(cx);
     let (view, doc) = current!(cx.editor);
 
     let text = doc.text().slice(..);
     let contents = doc.text();
     let selection = doc.selection(view.id);
+    let comment_token = doc
+        .language_config()
+        .and_then(|config| config.comment_token.clone());
 
     let mut ranges = SmallVec::with_capacity(selection.len());
     let mut offs = 0;
@@ -2950,10 +3878,29 @@ fn open(cx: &mut Context, open: Open) {
             line_end_index,
             cursor_line,
         );
-        let indent_len = indent.len();
+        // If the line the cursor is on is a line comment, continue the
+        // comment on the opened line.
+        let continuation = if continue_comments {
+            comment_token.as_deref().and_then(|token| {
+                let token_len = token.chars().count();
+                let line_start = text.line_to_char(cursor_line);
+                let line_end = line_end_char_index(&text, cursor_line);
+                let first = movement::skip_while(text, line_start, |ch| matches!(ch, ' ' | '\t'))
+                    .unwrap_or(line_start);
+                (first + token_len <= line_end && text.slice(first..first + token_len) == token)
+                    .then(|| format!("{} ", token))
+            })
+        } else {
+            None
+        };
+
+        let indent_len = indent.len() + continuation.as_ref().map_or(0, String::len);
         let mut text = String::with_capacity(1 + indent_len);
         text.push_str(doc.line_ending.as_str());
         text.push_str(&indent);
+        if let Some(continuation) = &continuation {
+            text.push_str(continuation);
+        }
         let text = text.repeat(count);
 
         // calculate new selection ranges
@@ -3025,6 +3972,50 @@ fn goto_line_without_jumplist(editor: &mut Editor, count: Option<NonZeroUsize>)
     }
 }
 
+fn goto_line_percentage(cx: &mut Context) {
+    let Some(count) = cx.count else {
+        // without a count `%` keeps its original meaning
+        select_all(cx);
+        return;
+    };
+
+    let (view, doc) = current!(cx.editor);
+    push_jump(view, doc);
+
+    let (view, doc) = current!(cx.editor);
+    let text = doc.text().slice(..);
+    let max_line = if text.line(text.len_lines() - 1).len_chars() == 0 {
+        // If the last line is blank, don't jump to it.
+        text.len_lines().saturating_sub(2)
+    } else {
+        text.len_lines() - 1
+    };
+    let percent = count.get().min(100);
+    // round up so that 100% always reaches the last line
+    let line_idx = ((max_line + 1) * percent + 99) / 100 - 1;
+    let pos = text.line_to_char(line_idx);
+    let selection = doc
+        .selection(view.id)
+        .clone()
+        .transform(|range| range.put_cursor(text, pos, cx.editor.mode == Mode::Select));
+
+    doc.set_selection(view.id, selection);
+}
+
+fn goto_column(cx: &mut Context) {
+    let count = cx.count();
+    let (view, doc) = current!(cx.editor);
+    let text = doc.text().slice(..);
+    let selection = doc.selection(view.id).clone().transform(|range| {
+        let line = range.cursor_line(text);
+        let line_start = text.line_to_char(line);
+        let pos = graphemes::nth_next_grapheme_boundary(text, line_start, count - 1)
+            .min(line_end_char_index(&text, line));
+        range.put_cursor(text, pos, cx.editor.mode == Mode::Select)
+    });
+    doc.set_selection(view.id, selection);
+}
+
 fn goto_last_line(cx: &mut Context) {
     let (view, doc) = current!(cx.editor);
     let text = doc.text().slice(..);
@@ -3168,6 +4159,64 @@ fn goto_prev_diag(cx: &mut Context) {
     doc.set_selection(view.id, selection);
 }
 
+fn goto_next_quickfix(cx: &mut Context) {
+    goto_quickfix_impl(cx, Direction::Forward);
+}
+
+fn goto_prev_quickfix(cx: &mut Context) {
+    goto_quickfix_impl(cx, Direction::Backward);
+}
+
+fn goto_quickfix_impl(cx: &mut Context, direction: Direction) {
+    let len = cx.editor.quickfix.len();
+    if len == 0 {
+        cx.editor.set_error("quickfix list is empty");
+        return;
+    }
+
+    // cycle through the list, starting at its closest end
+    let index = match (cx.editor.quickfix_index, direction) {
+        (None, Direction::Forward) => 0,
+        (None, Direction::Backward) => len - 1,
+        (Some(index), Direction::Forward) => (index + 1) % len,
+        (Some(index), Direction::Backward) => (index + len - 1) % len,
+    };
+    jump_to_quickfix_entry(cx.editor, index, Action::Replace);
+}
+
+/// Open the quickfix entry at `index`, place the cursor on its location and
+/// report the position in the list.
+pub(crate) fn jump_to_quickfix_entry(editor: &mut Editor, index: usize, action: Action) {
+    let entry = match editor.quickfix.get(index) {
+        Some(entry) => entry.clone(),
+        None => return,
+    };
+    editor.quickfix_index = Some(index);
+
+    if let Err(e) = editor.open(&entry.path, action) {
+        editor.set_error(format!(
+            "Failed to open file '{}': {}",
+            entry.path.display(),
+            e
+        ));
+        return;
+    }
+
+    let (view, doc) = current!(editor);
+    let text = doc.text().slice(..);
+    if entry.line >= text.len_lines() {
+        editor.set_error("The line of this quickfix entry does not exist anymore because the file has changed.");
+        return;
+    }
+    let line_start = text.line_to_char(entry.line);
+    let pos = (line_start + entry.column.unwrap_or(0)).min(line_end_char_index(&text, entry.line));
+    doc.set_selection(view.id, Selection::point(pos));
+    align_view(doc, view, Align::Center);
+
+    let len = editor.quickfix.len();
+    editor.set_status(format!("({}/{}) {}", index + 1, len, entry.message));
+}
+
 fn goto_first_change(cx: &mut Context) {
     goto_first_change_impl(cx, false);
 }
@@ -3266,6 +4315,47 @@ fn hunk_range(hunk: Hunk, text: RopeSlice) -> Range {
     Range::new(anchor, head)
 }
 
+fn goto_next_conflict(cx: &mut Context) {
+    goto_conflict_impl(cx, Direction::Forward)
+}
+
+fn goto_prev_conflict(cx: &mut Context) {
+    goto_conflict_impl(cx, Direction::Backward)
+}
+
+fn goto_conflict_impl(cx: &mut Context, direction: Direction) {
+    let motion = move |editor: &mut Editor| {
+        let (view, doc) = current!(editor);
+        let text = doc.text().slice(..);
+        let conflicts = helix_core::conflict::find_conflicts(text);
+        if conflicts.is_empty() {
+            editor.set_status("No merge conflicts in current buffer");
+            return;
+        }
+
+        let selection = doc.selection(view.id).clone().transform(|range| {
+            let cursor_line = range.cursor_line(text);
+            let conflict = match direction {
+                Direction::Forward => conflicts.iter().find(|c| c.start > cursor_line),
+                Direction::Backward => conflicts.iter().rev().find(|c| c.end < cursor_line),
+            };
+            match conflict {
+                // select the whole conflict, marker lines included
+                Some(conflict) => {
+                    let anchor = text.line_to_char(conflict.start);
+                    let head = text.line_to_char(conflict.end + 1);
+                    Range::new(anchor, head).with_direction(direction)
+                }
+                None => range,
+            }
+        });
+
+        doc.set_selection(view.id, selection);
+    };
+    motion(cx.editor);
+    cx.editor.last_motion = Some(Motion(Box::new(motion)));
+}
+
 pub mod insert {
     use super::*;
     pub type Hook = fn(&Rope, &Selection, char) -> Option<Transaction>;
@@ -3310,16 +4400,24 @@ pub mod insert {
         }
 
         use helix_lsp::lsp;
-        // if ch matches completion char, trigger completion
-        let doc = doc_mut!(cx.editor);
+        // if the text before the cursor now ends with a server-declared
+        // trigger sequence, which may be several characters long (`::`,
+        // `->`), trigger completion
+        let (view, doc) = current_ref!(cx.editor);
+        let text = doc.text().slice(..);
+        let cursor = doc.selection(view.id).primary().cursor(text);
         let trigger_completion = doc
             .language_servers_with_feature(LanguageServerFeature::Completion)
             .any(|ls| {
-                // TODO: what if trigger is multiple chars long
                 matches!(&ls.capabilities().completion_provider, Some(lsp::CompletionOptions {
                     trigger_characters: Some(triggers),
                     ..
-                }) if triggers.iter().any(|trigger| trigger.contains(ch)))
+                }) if triggers.iter().any(|trigger| {
+                    let len = trigger.chars().count();
+                    trigger.ends_with(ch)
+                        && len <= cursor
+                        && text.slice(cursor - len..cursor) == trigger.as_str()
+                }))
             });
 
         if trigger_completion {
@@ -3396,11 +4494,59 @@ pub mod insert {
         // TODO: need a post insert hook too for certain triggers (autocomplete, signature help, etc)
         // this could also generically look at Transaction, but it's a bit annoying to look at
         // Operation instead of Change.
-        for hook in &[language_server_completion, signature_help] {
+        for hook in &[expand_abbreviation, language_server_completion, signature_help] {
             hook(cx, c);
         }
     }
 
+    /// Replace the word just finished by a non-word character with its
+    /// configured abbreviation, if any. A `\` directly in front of the word
+    /// suppresses the expansion and is removed instead.
+    fn expand_abbreviation(cx: &mut Context, ch: char) {
+        use helix_core::chars::char_is_word;
+        if char_is_word(ch) {
+            return;
+        }
+
+        let config = cx.editor.config();
+        if config.abbreviations.is_empty() {
+            return;
+        }
+
+        let (view, doc) = current!(cx.editor);
+        let text = doc.text().slice(..);
+        let selection = doc.selection(view.id).clone();
+        let mut changed = false;
+        let transaction = Transaction::change_by_selection(doc.text(), &selection, |range| {
+            let cursor = range.cursor(text);
+            // the cursor sits right after the just-inserted boundary char
+            let word_end = cursor.saturating_sub(1);
+            let mut word_start = word_end;
+            while word_start > 0 && char_is_word(text.char(word_start - 1)) {
+                word_start -= 1;
+            }
+            if word_start == word_end {
+                return (cursor, cursor, None);
+            }
+            let word: String = text.slice(word_start..word_end).into();
+            if !config.abbreviations.contains_key(&word) {
+                return (cursor, cursor, None);
+            }
+            changed = true;
+            if word_start > 0 && text.char(word_start - 1) == '\\' {
+                // suppressed: just drop the backslash
+                (word_start - 1, word_start, None)
+            } else {
+                let expansion = &config.abbreviations[&word];
+                (word_start, word_end, Some(expansion.as_str().into()))
+            }
+        });
+
+        if changed {
+            doc.apply(&transaction, view.id);
+        }
+    }
+
     pub fn insert_tab(cx: &mut Context) {
         let (view, doc) = current!(cx.editor);
         // TODO: round out to nearest indentation level (for example a line with 3 spaces should
@@ -3416,8 +4562,12 @@ pub mod insert {
     }
 
     pub fn insert_newline(cx: &mut Context) {
+        let continue_comments = cx.editor.config().continue_comments;
         let (view, doc) = current_ref!(cx.editor);
         let text = doc.text().slice(..);
+        let comment_token = doc
+            .language_config()
+            .and_then(|config| config.comment_token.clone());
 
         let contents = doc.text();
         let selection = doc.selection(view.id).clone();
@@ -3464,6 +4614,22 @@ pub mod insert {
                     current_line,
                 );
 
+                // If the cursor is inside a line comment, continue the
+                // comment on the new line.
+                let continuation = if continue_comments {
+                    comment_token.as_deref().and_then(|token| {
+                        let token_len = token.chars().count();
+                        let line_start = text.line_to_char(current_line);
+                        let first =
+                            movement::skip_while(text, line_start, |ch| matches!(ch, ' ' | '\t'))
+                                .unwrap_or(line_start);
+                        (first + token_len <= pos && text.slice(first..first + token_len) == token)
+                            .then_some(token)
+                    })
+                } else {
+                    None
+                };
+
                 // If we are between pairs (such as brackets), we want to
                 // insert an additional line which is indented one level
                 // more and place the cursor there
@@ -3472,7 +4638,14 @@ pub mod insert {
                     .and_then(|pairs| pairs.get(prev))
                     .map_or(false, |pair| pair.open == prev && pair.close == curr);
 
-                let local_offs = if on_auto_pair {
+                let local_offs = if let Some(token) = continuation {
+                    new_text.reserve_exact(2 + indent.len() + token.len());
+                    new_text.push_str(doc.line_ending.as_str());
+                    new_text.push_str(&indent);
+                    new_text.push_str(token);
+                    new_text.push(' ');
+                    new_text.chars().count()
+                } else if on_auto_pair {
                     let inner_indent = indent.clone() + doc.indent_style.as_str();
                     new_text.reserve_exact(2 + indent.len() + inner_indent.len());
                     new_text.push_str(doc.line_ending.as_str());
@@ -4055,11 +5228,11 @@ fn replace_selections_with_primary_clipboard(cx: &mut Context) {
 fn paste(cx: &mut Context, pos: Paste) {
     let count = cx.count();
     let reg_name = cx.register.unwrap_or('"');
-    let (view, doc) = current!(cx.editor);
-    let registers = &mut cx.editor.registers;
 
-    if let Some(values) = registers.read(reg_name) {
-        paste_impl(values, doc, view, pos, count, cx.editor.mode);
+    if let Some(values) = cx.editor.read_register(reg_name) {
+        let mode = cx.editor.mode;
+        let (view, doc) = current!(cx.editor);
+        paste_impl(&values, doc, view, pos, count, mode);
     }
 }
 
@@ -4210,9 +5383,23 @@ fn format_selections(cx: &mut Context) {
 
 fn join_selections_impl(cx: &mut Context, select_space: bool) {
     use movement::skip_while;
+    let count = cx.count();
     let (view, doc) = current!(cx.editor);
     let text = doc.text();
     let slice = doc.text().slice(..);
+    let comment_token = doc
+        .language_config()
+        .and_then(|config| config.comment_token.clone());
+
+    let starts_with_comment = |line: usize| {
+        comment_token.as_deref().map_or(false, |token| {
+            let token_len = token.chars().count();
+            let start = text.line_to_char(line);
+            let end = line_end_char_index(&slice, line);
+            let first = skip_while(slice, start, |ch| matches!(ch, ' ' | '\t')).unwrap_or(start);
+            first + token_len <= end && slice.slice(first..first + token_len) == token
+        })
+    };
 
     let mut changes = Vec::new();
     let fragment = Tendril::from(" ");
@@ -4220,7 +5407,7 @@ fn join_selections_impl(cx: &mut Context, select_space: bool) {
     for selection in doc.selection(view.id) {
         let (start, mut end) = selection.line_range(slice);
         if start == end {
-            end = (end + 1).min(text.len_lines() - 1);
+            end = (end + count).min(text.len_lines() - 1);
         }
         let lines = start..end;
 
@@ -4231,8 +5418,29 @@ fn join_selections_impl(cx: &mut Context, select_space: bool) {
             let mut end = text.line_to_char(line + 1);
             end = skip_while(slice, end, |ch| matches!(ch, ' ' | '\t')).unwrap_or(end);
 
+            // joining comment lines also drops the repeated comment prefix so
+            // the result reads as a single comment
+            if let Some(token) = comment_token.as_deref() {
+                let token_len = token.chars().count();
+                if end + token_len <= line_end_char_index(&slice, line + 1)
+                    && slice.slice(end..end + token_len) == token
+                    && starts_with_comment(line)
+                {
+                    end = skip_while(slice, end + token_len, |ch| matches!(ch, ' ' | '\t'))
+                        .unwrap_or(end + token_len);
+                }
+            }
+
+            // no space before a closing bracket; the space selection variant
+            // keeps it so there is always a space to select
+            let insert = if !select_space && matches!(slice.get_char(end), Some(')' | ']' | '}')) {
+                Tendril::new()
+            } else {
+                fragment.clone()
+            };
+
             // need to skip from start, not end
-            let change = (start, end, Some(fragment.clone()));
+            let change = (start, end, Some(insert));
             changes.push(change);
         }
     }
@@ -4330,6 +5538,88 @@ fn remove_primary_selection(cx: &mut Context) {
     doc.set_selection(view.id, selection);
 }
 
+/// Minimum length for a buffer word to be suggested, and a cap on the number
+/// of suggestions to keep the fallback menu manageable.
+const WORD_COMPLETION_MIN_LEN: usize = 4;
+const WORD_COMPLETION_LIMIT: usize = 500;
+/// Documents larger than this are not scanned for completion words.
+const WORD_COMPLETION_MAX_DOC_BYTES: usize = 4 * 1024 * 1024;
+
+/// Collect words from all open buffers that could complete `prefix`, used as
+/// a completion source when no language server is available.
+fn buffer_word_completions(editor: &Editor, prefix: &str) -> Vec<CompletionItem> {
+    use helix_core::chars::char_is_word;
+    use helix_lsp::lsp;
+
+    let prefix_lower = prefix.to_lowercase();
+    let mut words = HashSet::new();
+    for doc in editor.documents() {
+        let text = doc.text();
+        if text.len_bytes() > WORD_COMPLETION_MAX_DOC_BYTES {
+            continue;
+        }
+        let mut word = String::new();
+        // the trailing space flushes a word ending at EOF
+        for ch in text.chars().chain(std::iter::once(' ')) {
+            if char_is_word(ch) {
+                word.push(ch);
+                continue;
+            }
+            if word.len() >= WORD_COMPLETION_MIN_LEN
+                && word != prefix
+                && word.to_lowercase().starts_with(&prefix_lower)
+            {
+                words.insert(std::mem::take(&mut word));
+            } else {
+                word.clear();
+            }
+        }
+    }
+
+    let mut words: Vec<_> = words.into_iter().collect();
+    words.sort_unstable();
+    words.truncate(WORD_COMPLETION_LIMIT);
+    words
+        .into_iter()
+        .map(|word| CompletionItem {
+            item: lsp::CompletionItem {
+                label: word,
+                kind: Some(lsp::CompletionItemKind::TEXT),
+                ..Default::default()
+            },
+            language_server_id: CompletionItem::WORD_SOURCE,
+            resolved: true,
+        })
+        .collect()
+}
+
+/// User snippets for the document's language whose prefix starts with
+/// `prefix`, as completion items that expand through the LSP snippet engine.
+fn snippet_completions(doc: &Document, prefix: &str) -> Vec<CompletionItem> {
+    use helix_lsp::lsp;
+
+    let Some(language) = doc.language_name() else {
+        return Vec::new();
+    };
+
+    crate::snippets::language_snippets(language)
+        .into_iter()
+        .filter(|snippet| snippet.prefix.starts_with(prefix))
+        .map(|snippet| CompletionItem {
+            item: lsp::CompletionItem {
+                label: snippet.prefix,
+                kind: Some(lsp::CompletionItemKind::SNIPPET),
+                detail: snippet.description,
+                insert_text: Some(snippet.body),
+                insert_text_format: Some(lsp::InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
+            language_server_id: CompletionItem::SNIPPET_SOURCE,
+            resolved: true,
+        })
+        .collect()
+}
+
 pub fn completion(cx: &mut Context) {
     use helix_lsp::{lsp, util::pos_to_lsp_pos};
 
@@ -4450,6 +5740,23 @@ pub fn completion(cx: &mut Context) {
                 return;
             }
 
+            let text = doc.text().slice(..);
+            let cursor = doc.selection(view.id).primary().cursor(text);
+            if start_offset > cursor {
+                return;
+            }
+            let prefix = Cow::from(text.slice(start_offset..cursor));
+
+            // If no language server provided completions, fall back to words
+            // from the open buffers so plain text files still get completion.
+            let mut items = if items.is_empty() {
+                buffer_word_completions(editor, &prefix)
+            } else {
+                items
+            };
+            // User snippets are offered regardless of the language servers.
+            items.extend(snippet_completions(doc, &prefix));
+
             if items.is_empty() {
                 // editor.set_error("No completion available");
                 return;
@@ -4833,9 +6140,127 @@ fn insert_register(cx: &mut Context) {
     })
 }
 
+/// Built-in digraph table, a small RFC 1345 inspired subset. Entries can be
+/// added or overridden with the `[editor.digraphs]` config section.
+static DEFAULT_DIGRAPHS: &[(&str, &str)] = &[
+    ("a:", "ä"),
+    ("a'", "á"),
+    ("a!", "à"),
+    ("a>", "â"),
+    ("a?", "ã"),
+    ("aa", "å"),
+    ("ae", "æ"),
+    ("e:", "ë"),
+    ("e'", "é"),
+    ("e!", "è"),
+    ("e>", "ê"),
+    ("i:", "ï"),
+    ("i'", "í"),
+    ("i!", "ì"),
+    ("i>", "î"),
+    ("o:", "ö"),
+    ("o'", "ó"),
+    ("o!", "ò"),
+    ("o>", "ô"),
+    ("o?", "õ"),
+    ("o/", "ø"),
+    ("u:", "ü"),
+    ("u'", "ú"),
+    ("u!", "ù"),
+    ("u>", "û"),
+    ("n?", "ñ"),
+    ("c,", "ç"),
+    ("ss", "ß"),
+    ("Eu", "€"),
+    ("Pd", "£"),
+    ("Ye", "¥"),
+    ("SE", "§"),
+    ("DG", "°"),
+    ("+-", "±"),
+    ("*X", "×"),
+    ("-:", "÷"),
+    ("<<", "«"),
+    (">>", "»"),
+    ("->", "→"),
+    ("<-", "←"),
+    ("..", "…"),
+    ("Ok", "✓"),
+    ("XX", "✗"),
+];
+
+fn insert_text_at_cursors(editor: &mut Editor, text: &str) {
+    let (view, doc) = current!(editor);
+    let transaction =
+        Transaction::insert(doc.text(), doc.selection(view.id), Tendril::from(text));
+    doc.apply(&transaction, view.id);
+}
+
+fn insert_unicode(cx: &mut Context) {
+    ui::prompt(
+        cx,
+        "codepoint:".into(),
+        None,
+        ui::completers::none,
+        move |cx, input: &str, event: PromptEvent| {
+            if event != PromptEvent::Validate {
+                return;
+            }
+            let digits = input
+                .trim()
+                .trim_start_matches("U+")
+                .trim_start_matches("u+")
+                .trim_start_matches("0x");
+            match u32::from_str_radix(digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+            {
+                Some(ch) => insert_text_at_cursors(cx.editor, &ch.to_string()),
+                None => cx
+                    .editor
+                    .set_error(format!("invalid codepoint '{}'", input)),
+            }
+        },
+    );
+}
+
+fn insert_digraph(cx: &mut Context) {
+    cx.on_next_key(move |cx, event| {
+        let first = match event.char() {
+            Some(ch) => ch,
+            None => return,
+        };
+        cx.on_next_key(move |cx, event| {
+            let second = match event.char() {
+                Some(ch) => ch,
+                None => return,
+            };
+            let key: String = [first, second].iter().collect();
+            let replacement = cx
+                .editor
+                .config()
+                .digraphs
+                .get(&key)
+                .cloned()
+                .or_else(|| {
+                    DEFAULT_DIGRAPHS
+                        .iter()
+                        .find(|(mnemonic, _)| *mnemonic == key)
+                        .map(|(_, replacement)| replacement.to_string())
+                });
+            match replacement {
+                Some(replacement) => insert_text_at_cursors(cx.editor, &replacement),
+                None => cx.editor.set_error(format!("unknown digraph '{}'", key)),
+            }
+        });
+    });
+}
+
 fn align_view_top(cx: &mut Context) {
+    let scrolloff = cx.editor.config().scrolloff;
     let (view, doc) = current!(cx.editor);
     align_view(doc, view, Align::Top);
+    // re-apply the scrolloff margin so the cursor keeps its context lines
+    view.ensure_cursor_in_view(doc, scrolloff);
 }
 
 fn align_view_center(cx: &mut Context) {
@@ -4844,8 +6269,10 @@ fn align_view_center(cx: &mut Context) {
 }
 
 fn align_view_bottom(cx: &mut Context) {
+    let scrolloff = cx.editor.config().scrolloff;
     let (view, doc) = current!(cx.editor);
     align_view(doc, view, Align::Bottom);
+    view.ensure_cursor_in_view(doc, scrolloff);
 }
 
 fn align_view_middle(cx: &mut Context) {
@@ -5324,6 +6751,15 @@ async fn shell_impl_async(
 }
 
 fn shell(cx: &mut compositor::Context, cmd: &str, behavior: &ShellBehavior) {
+    let cmd = match typed::expand_variables(cx.editor, cmd) {
+        Ok(cmd) => cmd.into_owned(),
+        Err(err) => {
+            cx.editor.set_error(err.to_string());
+            return;
+        }
+    };
+    let cmd = cmd.as_str();
+
     let pipe = match behavior {
         ShellBehavior::Replace | ShellBehavior::Ignore => true,
         ShellBehavior::Insert | ShellBehavior::Append => false,
@@ -5418,6 +6854,11 @@ fn shell_prompt(cx: &mut Context, prompt: Cow<'static, str>, behavior: ShellBeha
 fn suspend(_cx: &mut Context) {
     #[cfg(not(windows))]
     signal_hook::low_level::raise(signal_hook::consts::signal::SIGTSTP).unwrap();
+    // Windows consoles have no SIGTSTP equivalent; say so instead of
+    // swallowing the keypress silently.
+    #[cfg(windows)]
+    _cx.editor
+        .set_status("suspend is not supported on Windows, minimize the terminal window instead");
 }
 
 fn add_newline_above(cx: &mut Context) {
@@ -5482,6 +6923,12 @@ fn increment_impl(cx: &mut Context, increment_direction: IncrementDirection) {
     let (view, doc) = current!(cx.editor);
     let selection = doc.selection(view.id);
     let text = doc.text().slice(..);
+    // Word lists configured for the document's language take precedence over
+    // the built-in ones.
+    let language_cycles = doc
+        .language_config()
+        .map(|config| config.increment_cycles.as_slice())
+        .unwrap_or(&[]);
 
     let mut new_selection_ranges = SmallVec::new();
     let mut cumulative_length_diff: i128 = 0;
@@ -5490,9 +6937,12 @@ fn increment_impl(cx: &mut Context, increment_direction: IncrementDirection) {
     for range in selection {
         let selected_text: Cow<str> = range.fragment(text);
         let new_from = ((range.from() as i128) + cumulative_length_diff) as usize;
-        let incremented = [increment::integer, increment::date_time]
-            .iter()
-            .find_map(|incrementor| incrementor(selected_text.as_ref(), amount));
+        let incremented = increment::cycle_in(language_cycles, selected_text.as_ref(), amount)
+            .or_else(|| {
+                [increment::integer, increment::date_time, increment::cycle]
+                    .iter()
+                    .find_map(|incrementor| incrementor(selected_text.as_ref(), amount))
+            });
 
         amount += increase_by;
 
@@ -0,0 +1,185 @@
+//! Goto definition fallback based on vi-style `tags` files.
+//!
+//! When no language server offers goto-definition for a document, the word
+//! under the cursor is looked up in a `tags` file (as generated by
+//! universal-ctags) found by walking up from the current working directory.
+//! A single match jumps directly, multiple matches open a picker.
+
+use std::path::{Path, PathBuf};
+
+use super::{align_view, Align, Context};
+use crate::ui::{self, overlay::overlaid, Picker};
+use helix_core::Selection;
+use helix_view::editor::Action;
+use tui::widgets::Row;
+
+/// A single entry parsed from a tags file.
+pub struct Tag {
+    pub name: String,
+    /// Absolute path to the tagged file.
+    pub path: PathBuf,
+    pub address: TagAddress,
+}
+
+/// The "ex command" part of a tag line: either a line number or a literal
+/// line to search for.
+pub enum TagAddress {
+    Line(usize),
+    Pattern(String),
+}
+
+impl ui::menu::Item for Tag {
+    /// Current working directory.
+    type Data = PathBuf;
+
+    fn format(&self, cwdir: &Self::Data) -> Row {
+        let path = self.path.strip_prefix(cwdir).unwrap_or(&self.path);
+        let context = match &self.address {
+            TagAddress::Line(line) => format!("line {}", line),
+            TagAddress::Pattern(pattern) => pattern.trim().to_string(),
+        };
+        Row::new([
+            self.name.clone(),
+            path.to_string_lossy().into_owned(),
+            context,
+        ])
+    }
+}
+
+/// Find the nearest `tags` file, walking up from `dir`.
+fn find_tags_file(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|ancestor| ancestor.join("tags"))
+        .find(|path| path.is_file())
+}
+
+/// Parse the address field: `123;"` or `/^pattern$/;"`.
+fn parse_address(address: &str) -> Option<TagAddress> {
+    let address = address
+        .trim_end_matches(|ch| ch == '"' || ch == ';')
+        .trim_end();
+    if let Ok(line) = address.parse::<usize>() {
+        return Some(TagAddress::Line(line));
+    }
+    let pattern = address
+        .strip_prefix('/')?
+        .trim_end_matches('/')
+        .trim_start_matches('^')
+        .trim_end_matches('$');
+    // unescape the characters ctags escapes in patterns
+    let pattern = pattern
+        .replace("\\/", "/")
+        .replace("\\^", "^")
+        .replace("\\$", "$")
+        .replace("\\\\", "\\");
+    Some(TagAddress::Pattern(pattern))
+}
+
+/// All tags matching `name` exactly. Paths are resolved relative to the
+/// directory containing the tags file.
+fn lookup(tags_file: &Path, name: &str) -> Vec<Tag> {
+    let base = tags_file.parent().unwrap_or(Path::new("."));
+    let Ok(contents) = std::fs::read_to_string(tags_file) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .filter(|line| !line.starts_with('!'))
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let tag_name = fields.next()?;
+            if tag_name != name {
+                return None;
+            }
+            let file = fields.next()?;
+            let address = parse_address(fields.next()?)?;
+            let path = base.join(file);
+            Some(Tag {
+                name: tag_name.to_string(),
+                path: helix_core::path::get_canonicalized_path(&path).unwrap_or(path),
+                address,
+            })
+        })
+        .collect()
+}
+
+fn jump_to_tag(cx_editor: &mut helix_view::Editor, tag: &Tag, action: Action) {
+    let doc_id = match cx_editor.open(&tag.path, action) {
+        Ok(id) => id,
+        Err(err) => {
+            cx_editor.set_error(format!("open '{}': {}", tag.path.display(), err));
+            return;
+        }
+    };
+    let view_id = cx_editor.tree.focus;
+    let doc = doc_mut!(cx_editor, &doc_id);
+    let text = doc.text();
+
+    let line = match &tag.address {
+        TagAddress::Line(line) => line.saturating_sub(1),
+        TagAddress::Pattern(pattern) => {
+            let mut found = 0;
+            for (i, line) in text.lines().enumerate() {
+                if line.to_string().trim_end_matches(['\n', '\r']) == pattern.as_str() {
+                    found = i;
+                    break;
+                }
+            }
+            found
+        }
+    };
+    let line = line.min(text.len_lines().saturating_sub(1));
+    let pos = text.line_to_char(line);
+    doc.set_selection(view_id, Selection::point(pos));
+    let (view, doc) = current!(cx_editor);
+    align_view(doc, view, Align::Center);
+}
+
+/// Goto definition of the word under the cursor via the nearest tags file.
+pub fn goto_definition(cx: &mut Context) {
+    let cwdir = std::env::current_dir().unwrap_or_default();
+    let Some(tags_file) = find_tags_file(&cwdir) else {
+        cx.editor
+            .set_error("No language server supports goto-definition and no tags file was found");
+        return;
+    };
+
+    let (view, doc) = current_ref!(cx.editor);
+    let text = doc.text().slice(..);
+    let cursor = doc.selection(view.id).primary().cursor(text);
+    let word = helix_core::textobject::textobject_word(
+        text,
+        helix_core::Range::point(cursor),
+        helix_core::textobject::TextObject::Inside,
+        1,
+        false,
+    );
+    let word = text.slice(word.from()..word.to()).to_string();
+    if word.trim().is_empty() {
+        cx.editor.set_error("No word under the cursor");
+        return;
+    }
+
+    let tags = lookup(&tags_file, &word);
+    match tags.as_slice() {
+        [] => {
+            cx.editor
+                .set_error(format!("No tag found for '{}'", word));
+        }
+        [tag] => jump_to_tag(cx.editor, tag, Action::Replace),
+        _ => {
+            let picker = Picker::new(tags, cwdir, |cx, tag, action| {
+                jump_to_tag(cx.editor, tag, action);
+            })
+            .with_preview(|_editor, tag| {
+                let line = match &tag.address {
+                    TagAddress::Line(line) => line.saturating_sub(1),
+                    TagAddress::Pattern(_) => 0,
+                };
+                Some((tag.path.clone().into(), Some((line, line))))
+            });
+            cx.push_layer(Box::new(overlaid(picker)));
+        }
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
 };
 use dap::{StackFrame, Thread, ThreadStates};
 use helix_core::syntax::{DebugArgumentValue, DebugConfigCompletion, DebugTemplate};
-use helix_dap::{self as dap, Client};
+use helix_dap::{self as dap, Client, ConnectionType};
 use helix_lsp::block_on;
 use helix_view::editor::Breakpoint;
 
@@ -15,6 +15,7 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use tui::{text::Spans, widgets::Row};
 
 use std::collections::HashMap;
+use std::fmt::Write;
 use std::future::Future;
 use std::path::PathBuf;
 
@@ -288,33 +289,90 @@ pub fn dap_launch(cx: &mut Context) {
 }
 
 pub fn dap_restart(cx: &mut Context) {
-    let debugger = match &cx.editor.debugger {
-        Some(debugger) => debugger,
-        None => {
-            cx.editor.set_error("Debugger is not running");
-            return;
-        }
+    let mut cx = crate::compositor::Context {
+        editor: cx.editor,
+        scroll: None,
+        jobs: cx.jobs,
     };
-    if !debugger
+    if let Err(e) = dap_restart_impl(&mut cx) {
+        cx.editor.set_error(e.to_string());
+    }
+}
+
+pub fn dap_restart_impl(cx: &mut compositor::Context) -> Result<(), anyhow::Error> {
+    let debugger = cx
+        .editor
+        .debugger
+        .as_ref()
+        .ok_or_else(|| anyhow!("Debugger is not running"))?;
+    let args = debugger
+        .starting_request_args()
+        .clone()
+        .ok_or_else(|| anyhow!("No arguments found with which to restart the session"))?;
+
+    if debugger
         .capabilities()
         .supports_restart_request
         .unwrap_or(false)
     {
-        cx.editor
-            .set_error("Debugger does not support session restarts");
-        return;
+        dap_callback(
+            cx.jobs,
+            debugger.restart(),
+            |editor, _compositor, _resp: ()| editor.set_status("Debugging session restarted"),
+        );
+        return Ok(());
     }
-    if debugger.starting_request_args().is_none() {
-        cx.editor
-            .set_error("No arguments found with which to restart the sessions");
-        return;
+
+    // The adapter cannot restart in place: tear the session down and launch
+    // a new one with the same starting arguments.
+    let connection_type = debugger
+        .connection_type()
+        .ok_or_else(|| anyhow!("Debug session is not running"))?;
+    let debugger = cx.editor.debugger.as_mut().unwrap();
+    let _ = block_on(debugger.disconnect(None));
+    cx.editor.debugger = None;
+
+    let doc = doc!(cx.editor);
+    let config = doc
+        .language_config()
+        .and_then(|config| config.debugger.as_ref())
+        .ok_or_else(|| anyhow!("No debug adapter available for language"))?;
+
+    let result = block_on(Client::process(
+        &config.transport,
+        &config.command,
+        config.args.iter().map(|arg| arg.as_str()).collect(),
+        config.port_arg.as_deref(),
+        0,
+    ));
+    let (mut debugger, events) = match result {
+        Ok(r) => r,
+        Err(e) => bail!("Failed to restart debug session: {}", e),
+    };
+
+    if let Err(e) = block_on(debugger.initialize(config.name.clone())) {
+        bail!("Failed to initialize debug adapter: {}", e);
     }
+    debugger.quirks = config.quirks.clone();
 
-    dap_callback(
-        cx.jobs,
-        debugger.restart(),
-        |editor, _compositor, _resp: ()| editor.set_status("Debugging session restarted"),
-    );
+    let callback = |editor: &mut Editor, _compositor: &mut Compositor, _response: Value| {
+        editor.set_status("Debugging session restarted")
+    };
+    match connection_type {
+        ConnectionType::Launch => {
+            let call = debugger.launch(args);
+            dap_callback(cx.jobs, call, callback);
+        }
+        ConnectionType::Attach => {
+            let call = debugger.attach(args);
+            dap_callback(cx.jobs, call, callback);
+        }
+    };
+
+    cx.editor.debugger = Some(debugger);
+    let stream = UnboundedReceiverStream::new(events);
+    cx.editor.debugger_events.push(stream);
+    Ok(())
 }
 
 fn debug_parameter_prompt(
@@ -581,14 +639,106 @@ pub fn dap_variables(cx: &mut Context) {
     cx.replace_or_push_layer("dap-variables", popup);
 }
 
-pub fn dap_terminate(cx: &mut Context) {
+pub fn dap_evaluate(cx: &mut Context) {
     let debugger = debugger!(cx.editor);
+    let (frame, thread_id) = match (debugger.active_frame, debugger.thread_id) {
+        (Some(frame), Some(thread_id)) => (frame, thread_id),
+        _ => {
+            cx.editor
+                .set_error("Cannot find current stack frame to access variables");
+            return;
+        }
+    };
+    let frame_id = debugger.stack_frames[&thread_id][frame].id;
 
-    let request = debugger.disconnect(None);
-    dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
-        // editor.set_error(format!("Failed to disconnect: {}", e));
-        editor.debugger = None;
-    });
+    // evaluate the selection, or the word under the cursor when it is just
+    // a point
+    let (view, doc) = current_ref!(cx.editor);
+    let text = doc.text().slice(..);
+    let primary = doc.selection(view.id).primary();
+    let expression: String = if primary.len() > 1 {
+        primary
+    } else {
+        use helix_core::textobject::{textobject_word, TextObject};
+        textobject_word(text, primary, TextObject::Inside, 1, false)
+    }
+    .fragment(text)
+    .into();
+
+    if expression.trim().is_empty() {
+        cx.editor.set_error("No expression under cursor");
+        return;
+    }
+
+    let debugger = debugger!(cx.editor);
+    let response = match block_on(debugger.eval(expression.clone(), Some(frame_id))) {
+        Ok(response) => response,
+        Err(e) => {
+            cx.editor
+                .set_error(format!("Failed to evaluate '{}': {}", expression, e));
+            return;
+        }
+    };
+
+    let mut contents = format!("{} = {}", expression, response.result);
+    if let Some(ty) = response.ty {
+        write!(contents, ": {}", ty).unwrap();
+    }
+    let contents = Text::from(tui::text::Text::from(contents));
+    let popup = Popup::new("dap-eval", contents);
+    cx.replace_or_push_layer("dap-eval", popup);
+}
+
+pub fn dap_terminate(cx: &mut Context) {
+    let mut cx = crate::compositor::Context {
+        editor: cx.editor,
+        scroll: None,
+        jobs: cx.jobs,
+    };
+    if let Err(e) = dap_stop_impl(&mut cx) {
+        cx.editor.set_error(e.to_string());
+    }
+}
+
+pub fn dap_stop_impl(cx: &mut compositor::Context) -> Result<(), anyhow::Error> {
+    let debugger = cx
+        .editor
+        .debugger
+        .as_mut()
+        .ok_or_else(|| anyhow!("Debugger is not running"))?;
+
+    if debugger
+        .capabilities()
+        .supports_terminate_request
+        .unwrap_or(false)
+    {
+        // Ask the adapter to shut the debuggee down gracefully; the
+        // `Terminated` event handler takes care of the disconnect.
+        let request = debugger.terminate(None);
+        dap_callback(cx.jobs, request, |editor, _compositor, _response: Value| {
+            dap_session_ended(editor);
+        });
+    } else {
+        let request = debugger.disconnect(None);
+        dap_callback(cx.jobs, request, |editor, _compositor, _response: ()| {
+            editor.debugger = None;
+            dap_session_ended(editor);
+        });
+    }
+    Ok(())
+}
+
+/// Resets session-specific UI state once the debugger is gone: breakpoints
+/// stay configured but lose the verification state the old session gave them.
+fn dap_session_ended(editor: &mut Editor) {
+    for breakpoints in editor.breakpoints.values_mut() {
+        for breakpoint in breakpoints {
+            breakpoint.id = None;
+            breakpoint.verified = false;
+            breakpoint.message = None;
+        }
+    }
+    editor.set_status("Debugging session terminated");
 }
 
 pub fn dap_enable_exceptions(cx: &mut Context) {
@@ -41,7 +41,7 @@ use std::{
     collections::{BTreeMap, HashSet},
     fmt::Write,
     future::Future,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
@@ -1027,17 +1027,72 @@ pub fn apply_workspace_edit(
     Ok(())
 }
 
+/// Asks every running language server that registered interest in file
+/// renames about `old_path` becoming `new_path`, applying any workspace edits
+/// the servers respond with (e.g. rust-analyzer fixing up `mod` declarations).
+/// Must be called before the rename happens on disk.
+pub fn will_rename_files(editor: &mut Editor, old_path: &Path, new_path: &Path, is_dir: bool) {
+    let clients: Vec<_> = editor.language_servers.iter_clients().cloned().collect();
+    for client in clients {
+        let Some(future) = client.will_rename_files(old_path, new_path, is_dir) else {
+            continue;
+        };
+        match block_on(future) {
+            Ok(edit) => {
+                let _ = apply_workspace_edit(editor, client.offset_encoding(), &edit);
+            }
+            Err(err) => log::error!("willRenameFiles request failed: {}", err),
+        }
+    }
+}
+
+/// Notifies interested language servers that `old_path` was renamed to `new_path`.
+pub fn did_rename_files(editor: &Editor, old_path: &Path, new_path: &Path, is_dir: bool) {
+    for client in editor.language_servers.iter_clients() {
+        if let Some(future) = client.did_rename_files(old_path, new_path, is_dir) {
+            tokio::spawn(future);
+        }
+    }
+}
+
+/// The deletion counterpart of [`will_rename_files`]: asks interested servers
+/// about the upcoming deletion of `path` and applies any returned edits.
+pub fn will_delete_files(editor: &mut Editor, path: &Path, is_dir: bool) {
+    let clients: Vec<_> = editor.language_servers.iter_clients().cloned().collect();
+    for client in clients {
+        let Some(future) = client.will_delete_files(path, is_dir) else {
+            continue;
+        };
+        match block_on(future) {
+            Ok(edit) => {
+                let _ = apply_workspace_edit(editor, client.offset_encoding(), &edit);
+            }
+            Err(err) => log::error!("willDeleteFiles request failed: {}", err),
+        }
+    }
+}
+
+/// Notifies interested language servers that `path` was deleted.
+pub fn did_delete_files(editor: &Editor, path: &Path, is_dir: bool) {
+    for client in editor.language_servers.iter_clients() {
+        if let Some(future) = client.did_delete_files(path, is_dir) {
+            tokio::spawn(future);
+        }
+    }
+}
+
 fn goto_impl(
     editor: &mut Editor,
     compositor: &mut Compositor,
     locations: Vec<lsp::Location>,
     offset_encoding: OffsetEncoding,
+    action: Action,
 ) {
     let cwdir = std::env::current_dir().unwrap_or_default();
 
     match locations.as_slice() {
         [location] => {
-            jump_to_location(editor, location, offset_encoding, Action::Replace);
+            jump_to_location(editor, location, offset_encoding, action);
         }
         [] => {
             editor.set_error("No definition found.");
@@ -1067,8 +1122,12 @@ fn to_locations(definitions: Option<lsp::GotoDefinitionResponse>) -> Vec<lsp::Lo
     }
 }
 
-fn goto_single_impl<P, F>(cx: &mut Context, feature: LanguageServerFeature, request_provider: P)
-where
+fn goto_single_impl<P, F>(
+    cx: &mut Context,
+    feature: LanguageServerFeature,
+    request_provider: P,
+    action: Action,
+) where
     P: Fn(&Client, lsp::Position, lsp::TextDocumentIdentifier) -> Option<F>,
     F: Future<Output = helix_lsp::Result<serde_json::Value>> + 'static + Send,
 {
@@ -1083,44 +1142,88 @@ where
         future,
         move |editor, compositor, response: Option<lsp::GotoDefinitionResponse>| {
             let items = to_locations(response);
-            goto_impl(editor, compositor, items, offset_encoding);
+            goto_impl(editor, compositor, items, offset_encoding, action);
         },
     );
 }
 
 pub fn goto_declaration(cx: &mut Context) {
+    let action = cx.editor.config().goto_action.into();
     goto_single_impl(
         cx,
         LanguageServerFeature::GotoDeclaration,
         |ls, pos, doc_id| ls.goto_declaration(doc_id, pos, None),
+        action,
     );
 }
 
 pub fn goto_definition(cx: &mut Context) {
+    let action = cx.editor.config().goto_action.into();
+    goto_definition_impl(cx, action);
+}
+
+pub fn goto_definition_hsplit(cx: &mut Context) {
+    goto_definition_impl(cx, Action::HorizontalSplit);
+}
+
+pub fn goto_definition_vsplit(cx: &mut Context) {
+    goto_definition_impl(cx, Action::VerticalSplit);
+}
+
+fn goto_definition_impl(cx: &mut Context, action: Action) {
+    // without a capable language server, fall back to a tags file lookup
+    let doc = doc!(cx.editor);
+    if doc
+        .language_servers_with_feature(LanguageServerFeature::GotoDefinition)
+        .next()
+        .is_none()
+    {
+        super::ctags::goto_definition(cx);
+        return;
+    }
+
     goto_single_impl(
         cx,
         LanguageServerFeature::GotoDefinition,
         |ls, pos, doc_id| ls.goto_definition(doc_id, pos, None),
+        action,
     );
 }
 
 pub fn goto_type_definition(cx: &mut Context) {
+    let action = cx.editor.config().goto_action.into();
     goto_single_impl(
         cx,
         LanguageServerFeature::GotoTypeDefinition,
         |ls, pos, doc_id| ls.goto_type_definition(doc_id, pos, None),
+        action,
     );
 }
 
 pub fn goto_implementation(cx: &mut Context) {
+    let action = cx.editor.config().goto_action.into();
     goto_single_impl(
         cx,
         LanguageServerFeature::GotoImplementation,
         |ls, pos, doc_id| ls.goto_implementation(doc_id, pos, None),
+        action,
     );
 }
 
 pub fn goto_reference(cx: &mut Context) {
+    let action = cx.editor.config().goto_action.into();
+    goto_reference_impl(cx, action);
+}
+
+pub fn goto_reference_hsplit(cx: &mut Context) {
+    goto_reference_impl(cx, Action::HorizontalSplit);
+}
+
+pub fn goto_reference_vsplit(cx: &mut Context) {
+    goto_reference_impl(cx, Action::VerticalSplit);
+}
+
+fn goto_reference_impl(cx: &mut Context, action: Action) {
     let config = cx.editor.config();
     let (view, doc) = current!(cx.editor);
 
@@ -1143,7 +1246,7 @@ pub fn goto_reference(cx: &mut Context) {
         future,
         move |editor, compositor, response: Option<Vec<lsp::Location>>| {
             let items = response.unwrap_or_default();
-            goto_impl(editor, compositor, items, offset_encoding);
+            goto_impl(editor, compositor, items, offset_encoding, action);
         },
     );
 }
@@ -1375,7 +1478,8 @@ pub fn rename_symbol(cx: &mut Context) {
             Some(lsp::PrepareRenameResponse::DefaultBehavior { .. }) => {
                 Ok(get_prefill_from_word_boundary(editor))
             }
-            None => Err("lsp did not respond to prepare rename request"),
+            // a null response means the position is not valid for renaming
+            None => Err("language server cannot rename this symbol"),
         }
     }
 
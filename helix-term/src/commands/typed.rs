@@ -74,7 +74,38 @@ fn quit(cx: &mut compositor::Context, args: &[Cow<str>], event: PromptEvent) ->
 
     // last view and we have unsaved changes
     if cx.editor.tree.views().count() == 1 {
-        buffers_remaining_impl(cx.editor)?
+        let modified: Vec<_> = cx
+            .editor
+            .documents()
+            .filter(|doc| doc.is_modified())
+            .map(|doc| doc.id())
+            .collect();
+        if !modified.is_empty() {
+            let callback = async move {
+                let call: job::Callback = job::Callback::EditorCompositor(Box::new(
+                    move |editor: &mut Editor, compositor: &mut Compositor| {
+                        confirm_modified_buffers(
+                            compositor,
+                            editor,
+                            modified,
+                            false,
+                            Box::new(|_compositor, cx| {
+                                if let Err(err) = cx.block_try_flush_writes() {
+                                    cx.editor.set_error(err.to_string());
+                                    return;
+                                }
+                                if !cx.editor.tree.is_empty() {
+                                    cx.editor.close(view!(cx.editor).id);
+                                }
+                            }),
+                        );
+                    },
+                ));
+                Ok(call)
+            };
+            cx.jobs.callback(callback);
+            return Ok(());
+        }
     }
 
     cx.block_try_flush_writes()?;
@@ -100,6 +131,18 @@ fn force_quit(
     Ok(())
 }
 
+/// Expand `~` and environment variables in a path argument from the prompt
+/// and resolve a leading `./` against the current document's directory
+/// instead of the working directory.
+fn expand_path_arg(editor: &Editor, arg: &str) -> PathBuf {
+    if let Some(suffix) = arg.strip_prefix("./") {
+        if let Some(dir) = doc!(editor).path().and_then(|path| path.parent()) {
+            return dir.join(helix_core::path::expand(Path::new(suffix)));
+        }
+    }
+    helix_core::path::expand(Path::new(arg))
+}
+
 fn open(cx: &mut compositor::Context, args: &[Cow<str>], event: PromptEvent) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
@@ -108,14 +151,14 @@ fn open(cx: &mut compositor::Context, args: &[Cow<str>], event: PromptEvent) ->
     ensure!(!args.is_empty(), "wrong argument count");
     for arg in args {
         let (path, pos) = args::parse_file(arg);
-        let path = helix_core::path::expand_tilde(&path);
+        let path = expand_path_arg(cx.editor, &path.to_string_lossy());
         // If the path is a directory, open a file picker on that directory and update the status
         // message
         if let Ok(true) = std::fs::canonicalize(&path).map(|p| p.is_dir()) {
             let callback = async move {
                 let call: job::Callback = job::Callback::EditorCompositor(Box::new(
                     move |editor: &mut Editor, compositor: &mut Compositor| {
-                        let picker = ui::file_picker(path, &editor.config());
+                        let picker = ui::file_picker(path, editor);
                         compositor.push(Box::new(overlaid(picker)));
                     },
                 ));
@@ -123,13 +166,39 @@ fn open(cx: &mut compositor::Context, args: &[Cow<str>], event: PromptEvent) ->
             };
             cx.jobs.callback(callback);
         } else {
-            // Otherwise, just open the file
-            let _ = cx.editor.open(&path, Action::Replace)?;
-            let (view, doc) = current!(cx.editor);
-            let pos = Selection::point(pos_at_coords(doc.text().slice(..), pos, true));
-            doc.set_selection(view.id, pos);
-            // does not affect opening a buffer without pos
-            align_view(doc, view, Align::Center);
+            // Otherwise open the file, reading it on a blocking task so a
+            // huge file or slow (network) filesystem doesn't freeze the
+            // event loop and every other view.
+            let callback = async move {
+                let (path, contents) = tokio::task::spawn_blocking(move || {
+                    let contents = if path.exists() {
+                        let mut file = std::fs::File::open(&path)
+                            .map_err(|err| anyhow!("unable to open {:?}: {}", path, err))?;
+                        Some(helix_view::document::from_reader(&mut file, None)?)
+                    } else {
+                        None
+                    };
+                    Ok::<_, anyhow::Error>((path, contents))
+                })
+                .await??;
+                let call: job::Callback = job::Callback::EditorCompositor(Box::new(
+                    move |editor: &mut Editor, _compositor: &mut Compositor| {
+                        if let Err(err) =
+                            editor.open_with_contents(&path, Action::Replace, contents)
+                        {
+                            editor.set_error(format!("open '{}': {}", path.display(), err));
+                            return;
+                        }
+                        let (view, doc) = current!(editor);
+                        let pos = Selection::point(pos_at_coords(doc.text().slice(..), pos, true));
+                        doc.set_selection(view.id, pos);
+                        // does not affect opening a buffer without pos
+                        align_view(doc, view, Align::Center);
+                    },
+                ));
+                Ok(call)
+            };
+            cx.jobs.callback(callback);
         }
     }
     Ok(())
@@ -142,29 +211,35 @@ fn buffer_close_by_ids_impl(
 ) -> anyhow::Result<()> {
     cx.block_try_flush_writes()?;
 
-    let (modified_ids, modified_names): (Vec<_>, Vec<_>) = doc_ids
+    let modified_ids: Vec<_> = doc_ids
         .iter()
         .filter_map(|&doc_id| {
-            if let Err(CloseError::BufferModified(name)) = cx.editor.close_document(doc_id, force) {
-                Some((doc_id, name))
+            if let Err(CloseError::BufferModified(_)) = cx.editor.close_document(doc_id, force) {
+                Some(doc_id)
             } else {
                 None
             }
         })
-        .unzip();
+        .collect();
 
-    if let Some(first) = modified_ids.first() {
-        let current = doc!(cx.editor);
-        // If the current document is unmodified, and there are modified
-        // documents, switch focus to the first modified doc.
-        if !modified_ids.contains(&current.id()) {
-            cx.editor.switch(*first, Action::Replace);
-        }
-        bail!(
-            "{} unsaved buffer(s) remaining: {:?}",
-            modified_names.len(),
-            modified_names
-        );
+    if !modified_ids.is_empty() {
+        // Instead of failing with an error, ask per buffer whether to write
+        // or discard the changes before closing it.
+        let callback = async move {
+            let call: job::Callback = job::Callback::EditorCompositor(Box::new(
+                move |editor: &mut Editor, compositor: &mut Compositor| {
+                    confirm_modified_buffers(
+                        compositor,
+                        editor,
+                        modified_ids,
+                        true,
+                        Box::new(|_compositor, _cx| {}),
+                    );
+                },
+            ));
+            Ok(call)
+        };
+        cx.jobs.callback(callback);
     }
 
     Ok(())
@@ -330,9 +405,9 @@ fn write_impl(
     force: bool,
 ) -> anyhow::Result<()> {
     let editor_auto_fmt = cx.editor.config().auto_format;
+    let path = path.map(|arg| expand_path_arg(cx.editor, arg));
     let jobs = &mut cx.jobs;
     let (view, doc) = current!(cx.editor);
-    let path = path.map(AsRef::as_ref);
 
     let fmt = if editor_auto_fmt {
         doc.auto_format().map(|fmt| {
@@ -341,7 +416,7 @@ fn write_impl(
                 doc.version(),
                 view.id,
                 fmt,
-                Some((path.map(Into::into), force)),
+                Some((path.clone(), force)),
             );
 
             jobs.add(Job::with_callback(callback).wait_before_exiting());
@@ -367,6 +442,46 @@ fn write(
         return Ok(());
     }
 
+    // If the target changed on disk since the last save, ask before
+    // overwriting instead of failing the async write with a `:w!` hint.
+    let doc = doc!(cx.editor);
+    let target = args
+        .first()
+        .map(|arg| expand_path_arg(cx.editor, arg))
+        .or_else(|| doc.path().cloned());
+    let externally_modified = target
+        .and_then(|path| std::fs::metadata(path).ok())
+        .and_then(|metadata| metadata.modified().ok())
+        .map(|mtime| doc.last_saved_time() < mtime)
+        .unwrap_or(false);
+    if externally_modified {
+        let name = doc.display_name().into_owned();
+        let path_arg = args.first().map(|arg| arg.to_string());
+        let callback = async move {
+            let call: job::Callback = job::Callback::EditorCompositor(Box::new(
+                move |_editor: &mut Editor, compositor: &mut Compositor| {
+                    let message = format!(
+                        "'{}' has changed on disk since the last save, overwrite? (y)es (Esc to cancel)",
+                        name
+                    );
+                    compositor.push(Box::new(ui::Confirm::new(
+                        message,
+                        &['y'],
+                        Box::new(move |_compositor, cx, _choice| {
+                            let path = path_arg.map(Cow::Owned);
+                            if let Err(err) = write_impl(cx, path.as_ref(), true) {
+                                cx.editor.set_error(err.to_string());
+                            }
+                        }),
+                    )));
+                },
+            ));
+            Ok(call)
+        };
+        cx.jobs.callback(callback);
+        return Ok(());
+    }
+
     write_impl(cx, args.first(), false)
 }
 
@@ -426,6 +541,165 @@ fn new_file(
     Ok(())
 }
 
+fn create_file(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() == 1, ":new-file takes one argument");
+    let path = helix_core::path::expand_tilde(Path::new(args[0].as_ref()));
+    ensure!(!path.exists(), "path '{}' already exists", path.display());
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("unable to create directory '{}'", parent.display()))?;
+    }
+    std::fs::File::create(&path)
+        .with_context(|| format!("unable to create file '{}'", path.display()))?;
+
+    cx.editor.open(&path, Action::Replace)?;
+    cx.editor
+        .set_status(format!("created {}", path.display()));
+
+    Ok(())
+}
+
+fn rename_file(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() == 1, ":rename-file takes one argument");
+    let new_path = helix_core::path::expand_tilde(Path::new(args[0].as_ref()));
+
+    let doc = doc!(cx.editor);
+    let doc_id = doc.id();
+    let old_path = doc
+        .path()
+        .context("current buffer has no file to rename")?
+        .clone();
+    ensure!(
+        !new_path.exists(),
+        "path '{}' already exists",
+        new_path.display()
+    );
+
+    // give language servers a chance to fix up references before the
+    // rename happens on disk
+    lsp::will_rename_files(cx.editor, &old_path, &new_path, false);
+
+    if let Some(parent) = new_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("unable to create directory '{}'", parent.display()))?;
+    }
+    std::fs::rename(&old_path, &new_path).with_context(|| {
+        format!(
+            "unable to rename '{}' to '{}'",
+            old_path.display(),
+            new_path.display()
+        )
+    })?;
+
+    let syn_loader = cx.editor.syn_loader.clone();
+    let doc = doc_mut!(cx.editor, &doc_id);
+    // Close the document under its old URI; `refresh_language_servers`
+    // below reopens it under the new one (and picks up a language change
+    // from the new extension).
+    doc.close_language_servers();
+    doc.set_path(Some(&new_path))?;
+    doc.detect_language(syn_loader);
+    cx.editor.refresh_language_servers(doc_id);
+    lsp::did_rename_files(cx.editor, &old_path, &new_path, false);
+
+    cx.editor
+        .set_status(format!("renamed to {}", new_path.display()));
+
+    Ok(())
+}
+
+fn delete_file(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":delete-file takes no arguments");
+
+    let doc = doc!(cx.editor);
+    let doc_id = doc.id();
+    let path = doc
+        .path()
+        .context("current buffer has no file to delete")?
+        .clone();
+
+    lsp::will_delete_files(cx.editor, &path, false);
+
+    std::fs::remove_file(&path)
+        .with_context(|| format!("unable to delete '{}'", path.display()))?;
+    lsp::did_delete_files(cx.editor, &path, false);
+
+    // The file is gone, unsaved changes included; force the close.
+    if let Err(CloseError::SaveError(err)) = cx.editor.close_document(doc_id, true) {
+        return Err(err);
+    }
+    cx.editor
+        .set_status(format!("deleted {}", path.display()));
+
+    Ok(())
+}
+
+fn copy_file(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() == 1, ":copy-file takes one argument");
+    let dest = helix_core::path::expand_tilde(Path::new(args[0].as_ref()));
+
+    let doc = doc!(cx.editor);
+    let path = doc
+        .path()
+        .context("current buffer has no file to copy")?
+        .clone();
+    ensure!(!dest.exists(), "path '{}' already exists", dest.display());
+    if doc.is_modified() {
+        cx.editor
+            .set_status("buffer has unsaved changes, copying the file on disk");
+    }
+
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("unable to create directory '{}'", parent.display()))?;
+    }
+    std::fs::copy(&path, &dest).with_context(|| {
+        format!(
+            "unable to copy '{}' to '{}'",
+            path.display(),
+            dest.display()
+        )
+    })?;
+
+    cx.editor.open(&dest, Action::Replace)?;
+    cx.editor.set_status(format!("copied to {}", dest.display()));
+
+    Ok(())
+}
+
 fn format(
     cx: &mut compositor::Context,
     _args: &[Cow<str>],
@@ -651,6 +925,89 @@ pub(super) fn buffers_remaining_impl(editor: &mut Editor) -> anyhow::Result<()>
     Ok(())
 }
 
+/// Pushes a [`ui::Confirm`] asking what to do with the first document in
+/// `doc_ids` that still has unsaved changes: `(w)rite` it, `(d)iscard` the
+/// changes by force-closing the buffer, or cancel the whole operation with
+/// Escape. The remaining ids are then handled the same way, and `and_then`
+/// runs once none of them are modified anymore. Callers must ensure at least
+/// one of the ids is modified, otherwise `and_then` is silently dropped.
+fn confirm_modified_buffers(
+    compositor: &mut Compositor,
+    editor: &mut Editor,
+    mut doc_ids: Vec<DocumentId>,
+    close_after_write: bool,
+    and_then: Box<dyn FnOnce(&mut Compositor, &mut compositor::Context)>,
+) {
+    let mut next = None;
+    while let Some(&doc_id) = doc_ids.first() {
+        match editor.document(doc_id) {
+            Some(doc) if doc.is_modified() => {
+                next = Some((doc_id, doc.display_name().into_owned(), doc.path().is_some()));
+                break;
+            }
+            // already closed or saved in the meantime
+            _ => {
+                doc_ids.remove(0);
+            }
+        }
+    }
+    let (doc_id, name, has_path) = match next {
+        Some(next) => next,
+        None => return,
+    };
+
+    // focus the buffer in question so the user sees what they are deciding about
+    editor.switch(doc_id, Action::Replace);
+
+    let message = format!(
+        "Save changes to '{}'? (w)rite (d)iscard (Esc to cancel)",
+        name
+    );
+    compositor.push(Box::new(ui::Confirm::new(
+        message,
+        &['w', 'd'],
+        Box::new(move |compositor, cx, choice| {
+            match choice {
+                'w' => {
+                    if !has_path {
+                        cx.editor
+                            .set_error("cannot write a buffer without a filename");
+                        return;
+                    }
+                    if let Err(err) = write_impl(cx, None, false) {
+                        cx.editor.set_error(err.to_string());
+                        return;
+                    }
+                    if close_after_write {
+                        if let Err(err) = cx.block_try_flush_writes() {
+                            cx.editor.set_error(err.to_string());
+                            return;
+                        }
+                        let _ = cx.editor.close_document(doc_id, false);
+                    }
+                }
+                'd' => {
+                    let _ = cx.editor.close_document(doc_id, true);
+                }
+                _ => return,
+            }
+
+            doc_ids.remove(0);
+            let remaining_modified = doc_ids.iter().any(|&id| {
+                cx.editor
+                    .document(id)
+                    .map(|doc| doc.is_modified())
+                    .unwrap_or(false)
+            });
+            if remaining_modified {
+                confirm_modified_buffers(compositor, cx.editor, doc_ids, close_after_write, and_then);
+            } else {
+                and_then(compositor, cx);
+            }
+        }),
+    )));
+}
+
 pub fn write_all_impl(
     cx: &mut compositor::Context,
     force: bool,
@@ -764,10 +1121,43 @@ fn force_write_all_quit(
 }
 
 fn quit_all_impl(cx: &mut compositor::Context, force: bool) -> anyhow::Result<()> {
-    cx.block_try_flush_writes()?;
     if !force {
-        buffers_remaining_impl(cx.editor)?;
+        let modified: Vec<_> = cx
+            .editor
+            .documents()
+            .filter(|doc| doc.is_modified())
+            .map(|doc| doc.id())
+            .collect();
+        if !modified.is_empty() {
+            let callback = async move {
+                let call: job::Callback = job::Callback::EditorCompositor(Box::new(
+                    move |editor: &mut Editor, compositor: &mut Compositor| {
+                        confirm_modified_buffers(
+                            compositor,
+                            editor,
+                            modified,
+                            false,
+                            Box::new(|_compositor, cx| {
+                                if let Err(err) = cx.block_try_flush_writes() {
+                                    cx.editor.set_error(err.to_string());
+                                    return;
+                                }
+                                let views: Vec<_> =
+                                    cx.editor.tree.views().map(|(view, _)| view.id).collect();
+                                for view_id in views {
+                                    cx.editor.close(view_id);
+                                }
+                            }),
+                        );
+                    },
+                ));
+                Ok(call)
+            };
+            cx.jobs.callback(callback);
+            return Ok(());
+        }
     }
+    cx.block_try_flush_writes()?;
 
     // close all views
     let views: Vec<_> = cx.editor.tree.views().map(|(view, _)| view.id).collect();
@@ -1633,6 +2023,30 @@ fn debug_start(
     dap_start_impl(cx, name.as_deref(), None, Some(args))
 }
 
+fn debug_restart(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    ensure!(args.is_empty(), ":debug-restart takes no arguments");
+    dap_restart_impl(cx)
+}
+
+fn debug_stop(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    ensure!(args.is_empty(), ":debug-stop takes no arguments");
+    dap_stop_impl(cx)
+}
+
 fn debug_remote(
     cx: &mut compositor::Context,
     args: &[Cow<str>],
@@ -1770,10 +2184,11 @@ fn set_option(
         return Ok(());
     }
 
-    if args.len() != 2 {
+    if args.len() < 2 {
         anyhow::bail!("Bad arguments. Usage: `:set key field`");
     }
-    let (key, arg) = (&args[0].to_lowercase(), &args[1]);
+    // Values like arrays can contain spaces, e.g. `:set rulers [80, 100]`.
+    let (key, arg) = (&args[0].to_lowercase(), &args[1..].join(" "));
 
     let key_error = || anyhow::anyhow!("Unknown key `{}`", key);
     let field_error = |_| anyhow::anyhow!("Could not parse field `{}`", arg);
@@ -1788,12 +2203,14 @@ fn set_option(
     } else {
         arg.parse().map_err(field_error)?
     };
+    let status = format!("'{}' is now set to {}", key, value);
     let config = serde_json::from_value(config).map_err(field_error)?;
 
     cx.editor
         .config_events
         .0
         .send(ConfigEvent::Update(config))?;
+    cx.editor.set_status(status);
     Ok(())
 }
 
@@ -1892,6 +2309,8 @@ fn language(
 
     let id = doc.id();
     cx.editor.refresh_language_servers(id);
+    // large documents are parsed on a background task
+    cx.editor.launch_syntax_parse(id);
     Ok(())
 }
 
@@ -1951,7 +2370,7 @@ fn sort_impl(
     Ok(())
 }
 
-fn reflow(
+fn align_columns(
     cx: &mut compositor::Context,
     args: &[Cow<str>],
     event: PromptEvent,
@@ -1960,29 +2379,20 @@ fn reflow(
         return Ok(());
     }
 
+    // Without an argument columns are split on runs of whitespace,
+    // otherwise on the given delimiter (e.g. `|` or `,`).
+    let delimiter = args.get(0).map(|delim| delim.to_string());
+
     let scrolloff = cx.editor.config().scrolloff;
-    let cfg_text_width: usize = cx.editor.config().text_width;
     let (view, doc) = current!(cx.editor);
-
-    // Find the text_width by checking the following sources in order:
-    //   - The passed argument in `args`
-    //   - The configured text-width for this language in languages.toml
-    //   - The configured text-width in the config.toml
-    let text_width: usize = args
-        .get(0)
-        .map(|num| num.parse::<usize>())
-        .transpose()?
-        .or_else(|| doc.language_config().and_then(|config| config.text_width))
-        .unwrap_or(cfg_text_width);
-
     let rope = doc.text();
 
     let selection = doc.selection(view.id);
     let transaction = Transaction::change_by_selection(rope, selection, |range| {
         let fragment = range.fragment(rope.slice(..));
-        let reflowed_text = helix_core::wrap::reflow_hard_wrap(&fragment, text_width);
+        let aligned = align_fragment_columns(&fragment, delimiter.as_deref());
 
-        (range.from(), range.to(), Some(reflowed_text))
+        (range.from(), range.to(), Some(aligned.into()))
     });
 
     doc.apply(&transaction, view.id);
@@ -1992,118 +2402,267 @@ fn reflow(
     Ok(())
 }
 
-fn tree_sitter_subtree(
-    cx: &mut compositor::Context,
-    _args: &[Cow<str>],
-    event: PromptEvent,
-) -> anyhow::Result<()> {
-    if event != PromptEvent::Validate {
-        return Ok(());
-    }
-
-    let (view, doc) = current!(cx.editor);
+/// Pad the cells of every line in `fragment` so that the columns line up,
+/// splitting on `delimiter` (or runs of whitespace when `None`).
+fn align_fragment_columns(fragment: &str, delimiter: Option<&str>) -> String {
+    let ends_with_newline = fragment.ends_with('\n');
 
-    if let Some(syntax) = doc.syntax() {
-        let primary_selection = doc.selection(view.id).primary();
-        let text = doc.text();
-        let from = text.char_to_byte(primary_selection.from());
-        let to = text.char_to_byte(primary_selection.to());
-        if let Some(selected_node) = syntax
-            .tree()
-            .root_node()
-            .descendant_for_byte_range(from, to)
-        {
-            let mut contents = String::from("```tsq\n");
-            helix_core::syntax::pretty_print_tree(&mut contents, selected_node)?;
-            contents.push_str("\n```");
+    let rows: Vec<Vec<&str>> = fragment
+        .lines()
+        .map(|line| match delimiter {
+            Some(delimiter) => line.split(delimiter).map(str::trim).collect(),
+            None => line.split_whitespace().collect(),
+        })
+        .collect();
 
-            let callback = async move {
-                let call: job::Callback = Callback::EditorCompositor(Box::new(
-                    move |editor: &mut Editor, compositor: &mut Compositor| {
-                        let contents = ui::Markdown::new(contents, editor.syn_loader.clone());
-                        let popup = Popup::new("hover", contents).auto_close(true);
-                        compositor.replace_or_push("hover", popup);
-                    },
-                ));
-                Ok(call)
-            };
+    let mut widths: Vec<usize> = Vec::new();
+    for row in &rows {
+        for (col, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            if col == widths.len() {
+                widths.push(width);
+            } else if width > widths[col] {
+                widths[col] = width;
+            }
+        }
+    }
 
-            cx.jobs.callback(callback);
+    let mut out = String::with_capacity(fragment.len());
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let mut line = String::new();
+        for (col, cell) in row.iter().enumerate() {
+            line.push_str(cell);
+            if col + 1 < row.len() {
+                let pad = widths[col] - cell.chars().count();
+                line.extend(std::iter::repeat(' ').take(pad));
+                match delimiter {
+                    Some(delimiter) => {
+                        // A leading delimiter (markdown `| a | b |` rows
+                        // split into an empty first cell) stays flush left.
+                        if !(col == 0 && cell.is_empty()) {
+                            line.push(' ');
+                        }
+                        line.push_str(delimiter);
+                        line.push(' ');
+                    }
+                    None => line.push(' '),
+                }
+            }
         }
+        out.push_str(line.trim_end());
+    }
+    if ends_with_newline {
+        out.push('\n');
     }
 
-    Ok(())
+    out
 }
 
-fn open_config(
+fn eval_selections(
     cx: &mut compositor::Context,
-    _args: &[Cow<str>],
+    args: &[Cow<str>],
     event: PromptEvent,
 ) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
     }
 
-    cx.editor
-        .open(&helix_loader::config_file(), Action::Replace)?;
-    Ok(())
-}
+    // Optional number of decimal places for the results.
+    let precision = args.get(0).map(|arg| arg.parse::<usize>()).transpose()?;
 
-fn open_workspace_config(
-    cx: &mut compositor::Context,
-    _args: &[Cow<str>],
-    event: PromptEvent,
-) -> anyhow::Result<()> {
-    if event != PromptEvent::Validate {
-        return Ok(());
+    let scrolloff = cx.editor.config().scrolloff;
+    let (view, doc) = current!(cx.editor);
+    let rope = doc.text();
+
+    let selection = doc.selection(view.id);
+    // Evaluate every selection up front so that a single invalid
+    // expression aborts the command without touching the document.
+    let mut results: Vec<Tendril> = Vec::with_capacity(selection.len());
+    for range in selection {
+        let fragment = range.fragment(rope.slice(..));
+        let value = helix_core::eval::eval(&fragment)
+            .map_err(|err| anyhow!("invalid expression '{}': {}", fragment.trim(), err))?;
+        results.push(match precision {
+            Some(precision) => format!("{:.*}", precision, value).into(),
+            None => value.to_string().into(),
+        });
     }
 
-    cx.editor
-        .open(&helix_loader::workspace_config_file(), Action::Replace)?;
+    let transaction = Transaction::change(
+        rope,
+        selection
+            .iter()
+            .zip(results)
+            .map(|(range, result)| (range.from(), range.to(), Some(result))),
+    );
+
+    doc.apply(&transaction, view.id);
+    doc.append_changes_to_history(view);
+    view.ensure_cursor_in_view(doc, scrolloff);
+
     Ok(())
 }
 
-fn open_log(
+/// An encode/decode pair for `:encode`/`:decode`, applied per selection.
+struct SelectionTransform {
+    name: &'static str,
+    encode: fn(&str) -> anyhow::Result<String>,
+    decode: fn(&str) -> anyhow::Result<String>,
+}
+
+const SELECTION_TRANSFORMS: &[SelectionTransform] = &[
+    SelectionTransform {
+        name: "base64",
+        encode: |input| Ok(helix_view::base64::encode(input.as_bytes())),
+        decode: |input| {
+            let bytes = helix_view::base64::decode(input.trim())
+                .ok_or_else(|| anyhow!("invalid base64"))?;
+            String::from_utf8(bytes).map_err(|_| anyhow!("decoded base64 is not valid UTF-8"))
+        },
+    },
+    SelectionTransform {
+        name: "url",
+        encode: |input| {
+            let mut out = String::with_capacity(input.len());
+            for byte in input.bytes() {
+                match byte {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                        out.push(byte as char)
+                    }
+                    _ => write!(out, "%{:02X}", byte).unwrap(),
+                }
+            }
+            Ok(out)
+        },
+        decode: |input| {
+            let mut bytes = Vec::with_capacity(input.len());
+            let mut iter = input.bytes();
+            while let Some(byte) = iter.next() {
+                if byte == b'%' {
+                    let hex = [
+                        iter.next().ok_or_else(|| anyhow!("truncated % escape"))?,
+                        iter.next().ok_or_else(|| anyhow!("truncated % escape"))?,
+                    ];
+                    let hex = std::str::from_utf8(&hex).map_err(|_| anyhow!("invalid % escape"))?;
+                    bytes.push(
+                        u8::from_str_radix(hex, 16).map_err(|_| anyhow!("invalid % escape"))?,
+                    );
+                } else {
+                    bytes.push(byte);
+                }
+            }
+            String::from_utf8(bytes).map_err(|_| anyhow!("decoded URL is not valid UTF-8"))
+        },
+    },
+    SelectionTransform {
+        name: "json",
+        encode: |input| {
+            let quoted = serde_json::to_string(input)?;
+            // serde_json produces a quoted string literal; the selection
+            // should only be replaced by its contents.
+            Ok(quoted[1..quoted.len() - 1].to_string())
+        },
+        decode: |input| {
+            serde_json::from_str(&format!("\"{}\"", input))
+                .map_err(|_| anyhow!("invalid JSON string escapes"))
+        },
+    },
+    SelectionTransform {
+        name: "hex",
+        encode: |input| {
+            let mut out = String::with_capacity(input.len() * 2);
+            for byte in input.bytes() {
+                write!(out, "{:02x}", byte).unwrap();
+            }
+            Ok(out)
+        },
+        decode: |input| {
+            let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+            ensure!(input.len() % 2 == 0, "odd number of hex digits");
+            let bytes = (0..input.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&input[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| anyhow!("invalid hex digit"))?;
+            String::from_utf8(bytes).map_err(|_| anyhow!("decoded hex is not valid UTF-8"))
+        },
+    },
+];
+
+fn encode_selections(
     cx: &mut compositor::Context,
-    _args: &[Cow<str>],
+    args: &[Cow<str>],
     event: PromptEvent,
 ) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
     }
 
-    cx.editor.open(&helix_loader::log_file(), Action::Replace)?;
-    Ok(())
+    transform_selections_impl(cx, args, false)
 }
 
-fn refresh_config(
+fn decode_selections(
     cx: &mut compositor::Context,
-    _args: &[Cow<str>],
+    args: &[Cow<str>],
     event: PromptEvent,
 ) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
     }
 
-    cx.editor.config_events.0.send(ConfigEvent::Refresh)?;
-    Ok(())
+    transform_selections_impl(cx, args, true)
 }
 
-fn append_output(
+fn transform_selections_impl(
     cx: &mut compositor::Context,
     args: &[Cow<str>],
-    event: PromptEvent,
+    decode: bool,
 ) -> anyhow::Result<()> {
-    if event != PromptEvent::Validate {
-        return Ok(());
+    let name = args
+        .get(0)
+        .context("transform name required (base64, url, json, hex)")?;
+    let transform = SELECTION_TRANSFORMS
+        .iter()
+        .find(|transform| transform.name == name.as_ref())
+        .ok_or_else(|| anyhow!("unknown transform '{}' (base64, url, json, hex)", name))?;
+    let transform = if decode {
+        transform.decode
+    } else {
+        transform.encode
+    };
+
+    let scrolloff = cx.editor.config().scrolloff;
+    let (view, doc) = current!(cx.editor);
+    let rope = doc.text();
+
+    let selection = doc.selection(view.id);
+    // As with `:eval`: transform everything first so a single failure
+    // leaves the document untouched.
+    let mut results: Vec<Tendril> = Vec::with_capacity(selection.len());
+    for range in selection {
+        let fragment = range.fragment(rope.slice(..));
+        results.push(transform(&fragment)?.into());
     }
 
-    ensure!(!args.is_empty(), "Shell command required");
-    shell(cx, &args.join(" "), &ShellBehavior::Append);
+    let transaction = Transaction::change(
+        rope,
+        selection
+            .iter()
+            .zip(results)
+            .map(|(range, result)| (range.from(), range.to(), Some(result))),
+    );
+
+    doc.apply(&transaction, view.id);
+    doc.append_changes_to_history(view);
+    view.ensure_cursor_in_view(doc, scrolloff);
+
     Ok(())
 }
 
-fn insert_output(
+fn reflow(
     cx: &mut compositor::Context,
     args: &[Cow<str>],
     event: PromptEvent,
@@ -2112,113 +2671,548 @@ fn insert_output(
         return Ok(());
     }
 
-    ensure!(!args.is_empty(), "Shell command required");
-    shell(cx, &args.join(" "), &ShellBehavior::Insert);
+    let scrolloff = cx.editor.config().scrolloff;
+    let cfg_text_width: usize = cx.editor.config().text_width;
+    let (view, doc) = current!(cx.editor);
+
+    // Find the text_width by checking the following sources in order:
+    //   - The passed argument in `args`
+    //   - The configured text-width for this language in languages.toml
+    //   - The configured text-width in the config.toml
+    let text_width: usize = args
+        .get(0)
+        .map(|num| num.parse::<usize>())
+        .transpose()?
+        .or_else(|| doc.language_config().and_then(|config| config.text_width))
+        .unwrap_or(cfg_text_width);
+
+    let rope = doc.text();
+    let comment_token = doc
+        .language_config()
+        .and_then(|config| config.comment_token.clone());
+
+    let selection = doc.selection(view.id);
+    let transaction = Transaction::change_by_selection(rope, selection, |range| {
+        let fragment = range.fragment(rope.slice(..));
+        let reflowed_text =
+            helix_core::wrap::reflow_hard_wrap(&fragment, text_width, comment_token.as_deref());
+
+        (range.from(), range.to(), Some(reflowed_text))
+    });
+
+    doc.apply(&transaction, view.id);
+    doc.append_changes_to_history(view);
+    view.ensure_cursor_in_view(doc, scrolloff);
+
     Ok(())
 }
 
-fn pipe_to(
+fn replace_interactive(
     cx: &mut compositor::Context,
     args: &[Cow<str>],
     event: PromptEvent,
 ) -> anyhow::Result<()> {
-    pipe_impl(cx, args, event, &ShellBehavior::Ignore)
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    ensure!(
+        args.len() == 2,
+        ":replace-interactive takes a pattern and a replacement"
+    );
+
+    let regex = Regex::new(&args[0]).map_err(|err| anyhow!("invalid regex: {}", err))?;
+    let replacement = args[1].to_string();
+
+    let (view, doc) = current!(cx.editor);
+    let text = doc.text().slice(..);
+    let contents = text.to_string();
+    let matches: Vec<(usize, usize)> = regex
+        .find_iter(&contents)
+        .map(|mat| (text.byte_to_char(mat.start()), text.byte_to_char(mat.end())))
+        .filter(|(from, to)| from != to)
+        .collect();
+    ensure!(!matches.is_empty(), "no matches");
+
+    // the stepper moves the selection from match to match
+    push_jump(view, doc);
+
+    let call: job::Callback = Callback::EditorCompositor(Box::new(
+        move |editor: &mut Editor, compositor: &mut Compositor| {
+            replace_interactive_step(compositor, editor, matches, replacement, 0);
+        },
+    ));
+    cx.jobs.callback(async move { Ok(call) });
+
+    Ok(())
 }
 
-fn pipe(cx: &mut compositor::Context, args: &[Cow<str>], event: PromptEvent) -> anyhow::Result<()> {
-    pipe_impl(cx, args, event, &ShellBehavior::Replace)
+/// Asks about the first entry of `matches` and recurses on the rest via the
+/// dialog callback until the matches run out or the user quits. `matches`
+/// holds char ranges in document order, already mapped through any
+/// replacements applied so far; `replaced` counts them for the final report.
+fn replace_interactive_step(
+    compositor: &mut Compositor,
+    editor: &mut Editor,
+    mut matches: Vec<(usize, usize)>,
+    replacement: String,
+    replaced: usize,
+) {
+    let Some(&(from, to)) = matches.first() else {
+        editor.set_status(format!("{} replacements", replaced));
+        return;
+    };
+
+    // highlight the match under consideration by selecting it
+    let scrolloff = editor.config().scrolloff;
+    let (view, doc) = current!(editor);
+    doc.set_selection(view.id, Selection::single(from, to));
+    view.ensure_cursor_in_view_center(doc, scrolloff);
+
+    let message = format!(
+        "replace with '{}'? (y)es (n)o (a)ll (q)uit (l)ast",
+        replacement
+    );
+    compositor.push(Box::new(ui::Confirm::new(
+        message,
+        &['y', 'n', 'a', 'q', 'l'],
+        Box::new(move |compositor, cx, choice| {
+            let mut replaced = replaced;
+            match choice {
+                'y' | 'l' => {
+                    matches.remove(0);
+                    let (view, doc) = current!(cx.editor);
+                    let transaction = Transaction::change(
+                        doc.text(),
+                        std::iter::once((from, to, Some(replacement.as_str().into()))),
+                    );
+                    doc.apply(&transaction, view.id);
+                    doc.append_changes_to_history(view);
+                    replaced += 1;
+
+                    // keep the remaining matches in step with the edit
+                    let changes = transaction.changes();
+                    for (from, to) in &mut matches {
+                        *from = changes.map_pos(*from, helix_core::Assoc::After);
+                        *to = changes.map_pos(*to, helix_core::Assoc::After);
+                    }
+
+                    if choice == 'l' {
+                        cx.editor.set_status(format!("{} replacements", replaced));
+                        return;
+                    }
+                }
+                'n' => {
+                    matches.remove(0);
+                }
+                'a' => {
+                    let (view, doc) = current!(cx.editor);
+                    let transaction = Transaction::change(
+                        doc.text(),
+                        matches
+                            .iter()
+                            .map(|&(from, to)| (from, to, Some(replacement.as_str().into()))),
+                    );
+                    doc.apply(&transaction, view.id);
+                    doc.append_changes_to_history(view);
+                    replaced += matches.len();
+                    cx.editor.set_status(format!("{} replacements", replaced));
+                    return;
+                }
+                _ => {
+                    cx.editor.set_status(format!("{} replacements", replaced));
+                    return;
+                }
+            }
+            replace_interactive_step(compositor, cx.editor, matches, replacement, replaced);
+        }),
+    )));
 }
 
-fn pipe_impl(
+fn tree_sitter_subtree(
     cx: &mut compositor::Context,
-    args: &[Cow<str>],
+    _args: &[Cow<str>],
     event: PromptEvent,
-    behavior: &ShellBehavior,
 ) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
     }
 
-    ensure!(!args.is_empty(), "Shell command required");
-    shell(cx, &args.join(" "), behavior);
+    let (view, doc) = current!(cx.editor);
+
+    if let Some(syntax) = doc.syntax() {
+        let primary_selection = doc.selection(view.id).primary();
+        let text = doc.text();
+        let from = text.char_to_byte(primary_selection.from());
+        let to = text.char_to_byte(primary_selection.to());
+        if let Some(selected_node) = syntax
+            .tree()
+            .root_node()
+            .descendant_for_byte_range(from, to)
+        {
+            let mut contents = String::new();
+            helix_core::syntax::pretty_print_tree(&mut contents, selected_node)?;
+            contents.push('\n');
+
+            let callback = async move {
+                let call: job::Callback = Callback::EditorCompositor(Box::new(
+                    move |editor: &mut Editor, _compositor: &mut Compositor| {
+                        // Reuse the previous subtree buffer when it is still
+                        // open so rerunning the command updates in place.
+                        match editor
+                            .subtree_doc
+                            .filter(|id| editor.documents.contains_key(id))
+                        {
+                            Some(id) => editor.switch(id, Action::VerticalSplit),
+                            None => {
+                                let id = editor.new_file(Action::VerticalSplit);
+                                editor.subtree_doc = Some(id);
+                                let syn_loader = editor.syn_loader.clone();
+                                let doc = doc_mut!(editor, &id);
+                                // tsq highlighting, same as the queries the
+                                // subtree is used to debug
+                                let _ = doc.set_language_by_language_id("tsq", syn_loader);
+                            }
+                        }
+
+                        let (view, doc) = current!(editor);
+                        let transaction = Transaction::change(
+                            doc.text(),
+                            [(0, doc.text().len_chars(), Some(contents.into()))].into_iter(),
+                        );
+                        doc.apply(&transaction, view.id);
+                        doc.append_changes_to_history(view);
+                    },
+                ));
+                Ok(call)
+            };
+
+            cx.jobs.callback(callback);
+        }
+    }
+
     Ok(())
 }
 
-fn run_shell_command(
+fn tree_sitter_highlights(
     cx: &mut compositor::Context,
-    args: &[Cow<str>],
+    _args: &[Cow<str>],
     event: PromptEvent,
 ) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
     }
 
-    let shell = cx.editor.config().shell.clone();
-    let args = args.join(" ");
+    // The longest theme scope that is a dot-separated prefix of the capture,
+    // mirroring how captures are resolved when highlight queries are
+    // configured.
+    fn best_theme_scope<'a>(scopes: &'a [String], capture: &str) -> Option<&'a str> {
+        scopes
+            .iter()
+            .map(String::as_str)
+            .filter(|scope| {
+                capture == *scope
+                    || (capture.starts_with(*scope) && capture[scope.len()..].starts_with('.'))
+            })
+            .max_by_key(|scope| scope.len())
+    }
+
+    let (view, doc) = current_ref!(cx.editor);
+    let text = doc.text().slice(..);
+    let cursor = doc.selection(view.id).primary().cursor(text);
+    let byte = text.char_to_byte(cursor);
+
+    let captures = match doc.syntax() {
+        Some(syntax) => syntax.highlight_captures_at(text, byte),
+        None => Vec::new(),
+    };
+
+    if captures.is_empty() {
+        cx.editor.set_status("no highlight captures at cursor");
+        return Ok(());
+    }
+
+    let theme_scopes = cx.editor.theme.scopes();
+    let mut contents = String::from("```text\n");
+    for capture in &captures {
+        match best_theme_scope(theme_scopes, capture) {
+            Some(scope) => contents.push_str(&format!("@{} -> {}\n", capture, scope)),
+            None => contents.push_str(&format!("@{} -> (no theme scope)\n", capture)),
+        }
+    }
+    contents.push_str("```");
 
     let callback = async move {
-        let (output, success) = shell_impl_async(&shell, &args, None).await?;
         let call: job::Callback = Callback::EditorCompositor(Box::new(
             move |editor: &mut Editor, compositor: &mut Compositor| {
-                if !output.is_empty() {
-                    let contents = ui::Markdown::new(
-                        format!("```sh\n{}\n```", output),
-                        editor.syn_loader.clone(),
-                    );
-                    let popup = Popup::new("shell", contents).position(Some(
-                        helix_core::Position::new(editor.cursor().0.unwrap_or_default().row, 2),
-                    ));
-                    compositor.replace_or_push("shell", popup);
-                }
-                if success {
-                    editor.set_status("Command succeeded");
-                } else {
-                    editor.set_error("Command failed");
-                }
+                let contents = ui::Markdown::new(contents, editor.syn_loader.clone());
+                let popup = Popup::new("hover", contents).auto_close(true);
+                compositor.replace_or_push("hover", popup);
             },
         ));
         Ok(call)
     };
+
     cx.jobs.callback(callback);
 
     Ok(())
 }
 
-fn reset_diff_change(
+fn open_config(
     cx: &mut compositor::Context,
-    args: &[Cow<str>],
+    _args: &[Cow<str>],
     event: PromptEvent,
 ) -> anyhow::Result<()> {
     if event != PromptEvent::Validate {
         return Ok(());
     }
-    ensure!(args.is_empty(), ":reset-diff-change takes no arguments");
 
-    let editor = &mut cx.editor;
-    let scrolloff = editor.config().scrolloff;
+    cx.editor
+        .open(&helix_loader::config_file(), Action::Replace)?;
+    Ok(())
+}
 
-    let (view, doc) = current!(editor);
-    let Some(handle) = doc.diff_handle() else {
-        bail!("Diff is not available in the current buffer")
-    };
+fn open_workspace_config(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
 
-    let diff = handle.load();
-    let doc_text = doc.text().slice(..);
-    let line = doc.selection(view.id).primary().cursor_line(doc_text);
+    cx.editor
+        .open(&helix_loader::workspace_config_file(), Action::Replace)?;
+    Ok(())
+}
 
-    let Some(hunk_idx) = diff.hunk_at(line as u32, true) else {
-        bail!("There is no change at the cursor")
-    };
-    let hunk = diff.nth_hunk(hunk_idx);
-    let diff_base = diff.diff_base();
-    let before_start = diff_base.line_to_char(hunk.before.start as usize);
-    let before_end = diff_base.line_to_char(hunk.before.end as usize);
-    let text: Tendril = diff
-        .diff_base()
-        .slice(before_start..before_end)
-        .chunks()
-        .collect();
-    let anchor = doc_text.line_to_char(hunk.after.start as usize);
+fn open_log(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    cx.editor.open(&helix_loader::log_file(), Action::Replace)?;
+    Ok(())
+}
+
+fn refresh_config(
+    cx: &mut compositor::Context,
+    _args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    cx.editor.config_events.0.send(ConfigEvent::Refresh)?;
+    Ok(())
+}
+
+fn append_output(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(!args.is_empty(), "Shell command required");
+    shell(cx, &args.join(" "), &ShellBehavior::Append);
+    Ok(())
+}
+
+fn insert_output(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(!args.is_empty(), "Shell command required");
+    shell(cx, &args.join(" "), &ShellBehavior::Insert);
+    Ok(())
+}
+
+fn pipe_to(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    pipe_impl(cx, args, event, &ShellBehavior::Ignore)
+}
+
+fn pipe(cx: &mut compositor::Context, args: &[Cow<str>], event: PromptEvent) -> anyhow::Result<()> {
+    pipe_impl(cx, args, event, &ShellBehavior::Replace)
+}
+
+fn pipe_impl(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+    behavior: &ShellBehavior,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(!args.is_empty(), "Shell command required");
+    shell(cx, &args.join(" "), behavior);
+    Ok(())
+}
+
+/// Expand `%{variable}` references in a command or shell argument string
+/// against the current editor context: `file` (absolute path of the focused
+/// document), `dir` (its parent directory), `line` and `col` (1-based
+/// primary cursor position) and `selection` (primary selection text).
+/// `%%` escapes a literal `%`.
+pub fn expand_variables<'a>(editor: &Editor, input: &'a str) -> anyhow::Result<Cow<'a, str>> {
+    if !input.contains('%') {
+        return Ok(Cow::Borrowed(input));
+    }
+
+    let (view, doc) = current_ref!(editor);
+    let text = doc.text().slice(..);
+    let primary = doc.selection(view.id).primary();
+
+    let mut expanded = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            expanded.push(ch);
+            continue;
+        }
+        match chars.peek() {
+            Some('%') => {
+                chars.next();
+                expanded.push('%');
+            }
+            Some('{') => {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => name.push(ch),
+                        None => {
+                            return Err(anyhow!("unclosed variable reference '%{{{}'", name))
+                        }
+                    }
+                }
+                match name.as_str() {
+                    "file" => {
+                        let path = doc
+                            .path()
+                            .ok_or_else(|| anyhow!("%{{file}}: buffer has no path"))?;
+                        expanded.push_str(&path.to_string_lossy());
+                    }
+                    "dir" => {
+                        let dir = doc
+                            .path()
+                            .and_then(|path| path.parent())
+                            .ok_or_else(|| anyhow!("%{{dir}}: buffer has no path"))?;
+                        expanded.push_str(&dir.to_string_lossy());
+                    }
+                    "line" => {
+                        let line = text.char_to_line(primary.cursor(text)) + 1;
+                        expanded.push_str(&line.to_string());
+                    }
+                    "col" => {
+                        let col = helix_core::coords_at_pos(text, primary.cursor(text)).col + 1;
+                        expanded.push_str(&col.to_string());
+                    }
+                    "selection" => expanded.push_str(&primary.fragment(text)),
+                    _ => return Err(anyhow!("unknown variable '%{{{}}}'", name)),
+                }
+            }
+            // a stray `%` is passed through, e.g. in printf format strings
+            _ => expanded.push('%'),
+        }
+    }
+    Ok(Cow::Owned(expanded))
+}
+
+fn run_shell_command(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let shell = cx.editor.config().shell.clone();
+    let args = expand_variables(cx.editor, &args.join(" "))?.into_owned();
+    let job_name = format!("sh {}", args);
+
+    let callback = async move {
+        let (output, success) = shell_impl_async(&shell, &args, None).await?;
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |editor: &mut Editor, compositor: &mut Compositor| {
+                if !output.is_empty() {
+                    let contents = ui::Markdown::new(
+                        format!("```sh\n{}\n```", output),
+                        editor.syn_loader.clone(),
+                    );
+                    let popup = Popup::new("shell", contents).position(Some(
+                        helix_core::Position::new(editor.cursor().0.unwrap_or_default().row, 2),
+                    ));
+                    compositor.replace_or_push("shell", popup);
+                }
+                if success {
+                    editor.set_status("Command succeeded");
+                } else {
+                    editor.set_error("Command failed");
+                }
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.add(Job::with_callback(callback).name(job_name));
+
+    Ok(())
+}
+
+fn reset_diff_change(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+    ensure!(args.is_empty(), ":reset-diff-change takes no arguments");
+
+    let editor = &mut cx.editor;
+    let scrolloff = editor.config().scrolloff;
+
+    let (view, doc) = current!(editor);
+    let Some(handle) = doc.diff_handle() else {
+        bail!("Diff is not available in the current buffer")
+    };
+
+    let diff = handle.load();
+    let doc_text = doc.text().slice(..);
+    let line = doc.selection(view.id).primary().cursor_line(doc_text);
+
+    let Some(hunk_idx) = diff.hunk_at(line as u32, true) else {
+        bail!("There is no change at the cursor")
+    };
+    let hunk = diff.nth_hunk(hunk_idx);
+    let diff_base = diff.diff_base();
+    let before_start = diff_base.line_to_char(hunk.before.start as usize);
+    let before_end = diff_base.line_to_char(hunk.before.end as usize);
+    let text: Tendril = diff
+        .diff_base()
+        .slice(before_start..before_end)
+        .chunks()
+        .collect();
+    let anchor = doc_text.line_to_char(hunk.after.start as usize);
     let transaction = Transaction::change(
         doc.text(),
         [(
@@ -2385,6 +3379,34 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
             //       set the path of the newly created buffer.
             signature: CommandSignature::positional(&[completers::filename]),
         },
+        TypableCommand {
+            name: "new-file",
+            aliases: &[],
+            doc: "Create a new file (and any missing parent directories) at the given path and open it.",
+            fun: create_file,
+            signature: CommandSignature::positional(&[completers::filename]),
+        },
+        TypableCommand {
+            name: "rename-file",
+            aliases: &[],
+            doc: "Rename the file of the current buffer, updating the buffer's path and reopening it with the language servers.",
+            fun: rename_file,
+            signature: CommandSignature::positional(&[completers::filename]),
+        },
+        TypableCommand {
+            name: "delete-file",
+            aliases: &[],
+            doc: "Delete the file of the current buffer from disk and close the buffer.",
+            fun: delete_file,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "copy-file",
+            aliases: &[],
+            doc: "Copy the file of the current buffer to the given path and open the copy.",
+            fun: copy_file,
+            signature: CommandSignature::positional(&[completers::filename]),
+        },
         TypableCommand {
             name: "format",
             aliases: &["fmt"],
@@ -2668,6 +3690,20 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
             fun: debug_remote,
             signature: CommandSignature::none(),
         },
+        TypableCommand {
+            name: "debug-restart",
+            aliases: &[],
+            doc: "Restart the current debug session, relaunching the adapter with the same arguments if it cannot restart in place.",
+            fun: debug_restart,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "debug-stop",
+            aliases: &[],
+            doc: "Stop the current debug session, terminating the debuggee when the adapter supports it.",
+            fun: debug_stop,
+            signature: CommandSignature::none(),
+        },
         TypableCommand {
             name: "debug-eval",
             aliases: &[],
@@ -2760,6 +3796,34 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
             fun: sort_reverse,
             signature: CommandSignature::none(),
         },
+        TypableCommand {
+            name: "align-columns",
+            aliases: &["align"],
+            doc: "Align the lines of each selection into columns, splitting on the given delimiter (runs of whitespace if omitted).",
+            fun: align_columns,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "eval",
+            aliases: &[],
+            doc: "Replace each selection with the result of evaluating it as an arithmetic expression. Takes an optional number of decimal places for the results.",
+            fun: eval_selections,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "encode",
+            aliases: &[],
+            doc: "Encode each selection with the given transform: base64, url, json or hex.",
+            fun: encode_selections,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "decode",
+            aliases: &[],
+            doc: "Decode each selection with the given transform: base64, url, json or hex.",
+            fun: decode_selections,
+            signature: CommandSignature::none(),
+        },
         TypableCommand {
             name: "reflow",
             aliases: &[],
@@ -2767,13 +3831,27 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
             fun: reflow,
             signature: CommandSignature::none(),
         },
+        TypableCommand {
+            name: "replace-interactive",
+            aliases: &["ri"],
+            doc: "Step through matches of a regex pattern, confirming each replacement: (y)es (n)o (a)ll (q)uit (l)ast.",
+            fun: replace_interactive,
+            signature: CommandSignature::none(),
+        },
         TypableCommand {
             name: "tree-sitter-subtree",
             aliases: &["ts-subtree"],
-            doc: "Display tree sitter subtree under cursor, primarily for debugging queries.",
+            doc: "Show the tree-sitter subtree covering the primary selection in a scratch buffer, primarily for debugging queries. Rerun to update after edits.",
             fun: tree_sitter_subtree,
             signature: CommandSignature::none(),
         },
+        TypableCommand {
+            name: "tree-sitter-highlights",
+            aliases: &["ts-highlights"],
+            doc: "Show the highlight captures and the theme scopes they resolve to at the primary cursor, for theme and query debugging.",
+            fun: tree_sitter_highlights,
+            signature: CommandSignature::none(),
+        },
         TypableCommand {
             name: "config-reload",
             aliases: &[],
@@ -2833,7 +3911,7 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
         TypableCommand {
             name: "run-shell-command",
             aliases: &["sh"],
-            doc: "Run a shell command",
+            doc: "Run a shell command. Context variables like %{file}, %{dir}, %{line}, %{col} and %{selection} are expanded; %% escapes a literal %.",
             fun: run_shell_command,
             signature: CommandSignature::all(completers::filename)
         },
@@ -2849,9 +3927,1301 @@ pub const TYPABLE_COMMAND_LIST: &[TypableCommand] = &[
             aliases: &[],
             doc: "Clear given register. If no argument is provided, clear all registers.",
             fun: clear_register,
+            signature: CommandSignature::positional(&[completers::register]),
+        },
+        TypableCommand {
+            name: "keymap",
+            aliases: &[],
+            doc: "Open a picker of the effective keybindings per mode, including user overrides, searchable by key or command name.",
+            fun: keymap,
             signature: CommandSignature::none(),
         },
-    ];
+        TypableCommand {
+            name: "register-edit",
+            aliases: &[],
+            doc: "Edit the contents of a register in the prompt, e.g. to fix a recorded macro without re-recording it. Multiple values are separated by newlines.",
+            fun: register_edit,
+            signature: CommandSignature::positional(&[completers::register]),
+        },
+        TypableCommand {
+            name: "remote-open",
+            aliases: &[],
+            doc: "Open a file from a remote host over SSH: remote-open [user@]host:path.",
+            fun: remote_open,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "remote-save",
+            aliases: &[],
+            doc: "Write the current buffer back to its remote host over SSH.",
+            fun: remote_save,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "spell-check",
+            aliases: &[],
+            doc: "Open a picker with misspelled words in the current buffer and their suggested corrections.",
+            fun: spell_check,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "spell-add",
+            aliases: &[],
+            doc: "Add a word (argument or current selection) to the personal spelling dictionary.",
+            fun: spell_add,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "session-save",
+            aliases: &[],
+            doc: "Save the open buffers, split layout and cursors as a named session (default name: 'default'). Restore with hx --session <name>.",
+            fun: session_save,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "plugin",
+            aliases: &[],
+            doc: "Invoke a command exported by a loaded WASM plugin: plugin <plugin> <command> [args…].",
+            fun: plugin,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "job-cancel",
+            aliases: &[],
+            doc: "Open a picker of running background jobs and cancel the selected one.",
+            fun: job_cancel,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "run-task",
+            aliases: &[],
+            doc: "Run a task discovered from the workspace (.helix/tasks.toml, Makefile, package.json or Cargo.toml), streaming its output into a scratch buffer. Without an argument, pick the task from a list.",
+            fun: run_task,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "make",
+            aliases: &[],
+            doc: "Run a build command (`make` if not given), parse file:line:col locations out of its output into the quickfix list and report how many were found. Navigate the list with ]q/[q or :quickfix.",
+            fun: make,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "quickfix",
+            aliases: &["qf"],
+            doc: "Open a picker over the quickfix list filled by :make.",
+            fun: quickfix_picker,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "messages",
+            aliases: &[],
+            doc: "Open a scratch buffer containing the status message history.",
+            fun: messages,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "clear-search-highlight",
+            aliases: &["nohl"],
+            doc: "Stop highlighting matches of the last search pattern.",
+            fun: clear_search_highlight,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "diagnostics-toggle",
+            aliases: &[],
+            doc: "Toggle display of diagnostics for the current buffer; the diagnostics themselves are kept.",
+            fun: diagnostics_toggle,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "diagnostics-panel",
+            aliases: &[],
+            doc: "Toggle a bottom panel listing diagnostics across all open documents, grouped by file.",
+            fun: diagnostics_panel,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "perf",
+            aliases: &[],
+            doc: "Toggle an overlay showing recent render, event handling, tree-sitter reparse and language server timings.",
+            fun: perf,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "key-display",
+            aliases: &[],
+            doc: "Toggle an on-screen display of pressed keys, useful for screencasts and pairing.",
+            fun: key_display,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "scroll-bind",
+            aliases: &[],
+            doc: "Toggle scroll binding for the current view. Scroll-bound views scroll together, useful for comparing files side by side.",
+            fun: scroll_bind,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "diff-open",
+            aliases: &[],
+            doc: "Open a file in a vertical split and diff both buffers against each other.",
+            fun: diff_open,
+            signature: CommandSignature::positional(&[completers::filename]),
+        },
+        TypableCommand {
+            name: "merge-keep-ours",
+            aliases: &[],
+            doc: "Resolve the merge conflict under the cursor by keeping our side.",
+            fun: merge_keep_ours,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "merge-keep-theirs",
+            aliases: &[],
+            doc: "Resolve the merge conflict under the cursor by keeping their side.",
+            fun: merge_keep_theirs,
+            signature: CommandSignature::none(),
+        },
+        TypableCommand {
+            name: "merge-keep-both",
+            aliases: &[],
+            doc: "Resolve the merge conflict under the cursor by keeping both sides, ours first.",
+            fun: merge_keep_both,
+            signature: CommandSignature::none(),
+        },
+    ];
+
+fn remote_open(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() == 1, ":remote-open requires a [user@]host:path");
+    let remote = crate::remote::parse(&args[0])
+        .ok_or_else(|| anyhow!("invalid remote path '{}', expected [user@]host:path", args[0]))?;
+
+    cx.editor
+        .set_status(format!("Downloading {}...", remote));
+
+    let job_name = format!("remote-open {}", remote);
+    let callback = async move {
+        let text = crate::remote::download(&remote).await?;
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |editor: &mut Editor, _compositor: &mut Compositor| {
+                let doc_id = editor.new_file(Action::Replace);
+                let doc = doc_mut!(editor, &doc_id);
+                let view = view_mut!(editor);
+                doc.ensure_view_init(view.id);
+                let transaction =
+                    helix_core::Transaction::insert(doc.text(), doc.selection(view.id), text.into())
+                        .with_selection(Selection::point(0));
+                doc.apply(&transaction, view.id);
+                doc.append_changes_to_history(view);
+                crate::remote::associate(doc_id, remote.clone());
+                editor.set_status(format!("Opened {}. Save with :remote-save", remote));
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.add(Job::with_callback(callback).name(job_name));
+
+    Ok(())
+}
+
+fn remote_save(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(
+        args.len() <= 1,
+        ":remote-save takes at most one [user@]host:path argument"
+    );
+
+    let doc = doc!(cx.editor);
+    let doc_id = doc.id();
+    let remote = match args.first() {
+        Some(spec) => {
+            let remote = crate::remote::parse(spec).ok_or_else(|| {
+                anyhow!("invalid remote path '{}', expected [user@]host:path", spec)
+            })?;
+            crate::remote::associate(doc_id, remote.clone());
+            remote
+        }
+        None => crate::remote::get(doc_id)
+            .ok_or_else(|| anyhow!("buffer is not associated with a remote file"))?,
+    };
+    let text = doc.text().to_string();
+
+    cx.editor.set_status(format!("Uploading {}...", remote));
+
+    let job_name = format!("remote-save {}", remote);
+    let callback = async move {
+        crate::remote::upload(&remote, text).await?;
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |editor: &mut Editor, _compositor: &mut Compositor| {
+                editor.set_status(format!("Saved {}", remote));
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.add(Job::with_callback(callback).name(job_name));
+
+    Ok(())
+}
+
+fn spell_check(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":spell-check takes no arguments");
+    ensure!(
+        crate::spell::dictionary_available(),
+        "no spelling dictionary found (looked in /usr/share/dict and the personal dictionary)"
+    );
+
+    struct SpellMeta {
+        word: String,
+        start: usize,
+        end: usize,
+        row: usize,
+        col: usize,
+        suggestions: Vec<String>,
+    }
+
+    impl ui::menu::Item for SpellMeta {
+        type Data = ();
+
+        fn format(&self, _data: &Self::Data) -> Row {
+            let suggestions = if self.suggestions.is_empty() {
+                "no suggestions".to_string()
+            } else {
+                self.suggestions.join(", ")
+            };
+            Row::new([
+                format!("{}:{}", self.row + 1, self.col + 1),
+                self.word.clone(),
+                suggestions,
+            ])
+        }
+    }
+
+    let doc = doc!(cx.editor);
+    let text = doc.text().slice(..);
+
+    let items: Vec<SpellMeta> = crate::spell::misspellings(text)
+        .into_iter()
+        .map(|misspelling| {
+            let row = text.char_to_line(misspelling.start);
+            let col = misspelling.start - text.line_to_char(row);
+            SpellMeta {
+                suggestions: crate::spell::suggestions(&misspelling.word, 3),
+                word: misspelling.word,
+                start: misspelling.start,
+                end: misspelling.end,
+                row,
+                col,
+            }
+        })
+        .collect();
+
+    if items.is_empty() {
+        cx.editor.set_status("No misspellings found");
+        return Ok(());
+    }
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                let picker = ui::Picker::new(items, (), move |cx, meta, _action| {
+                    let (view, doc) = current!(cx.editor);
+                    let text = doc.text().slice(..);
+                    if meta.end <= text.len_chars() {
+                        doc.set_selection(view.id, Selection::single(meta.start, meta.end));
+                        align_view(doc, view, Align::Center);
+                    }
+                });
+                compositor.push(Box::new(overlaid(picker)))
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn spell_add(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let word = match args {
+        [] => {
+            let (view, doc) = current!(cx.editor);
+            let text = doc.text().slice(..);
+            doc.selection(view.id).primary().fragment(text).to_string()
+        }
+        [word] => word.to_string(),
+        _ => bail!(":spell-add takes at most one word"),
+    };
+    let word = word.trim();
+    ensure!(
+        !word.is_empty() && !word.contains(char::is_whitespace),
+        "selection or argument must be a single word"
+    );
+
+    crate::spell::add_word(word)?;
+    cx.editor
+        .set_status(format!("Added '{}' to the personal dictionary", word));
+    Ok(())
+}
+
+fn session_save(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() <= 1, ":session-save takes at most one argument");
+    let name = args.first().map_or("default", |name| name.as_ref());
+    ensure!(
+        !name.contains(std::path::is_separator),
+        "session name must not contain path separators"
+    );
+
+    crate::session::save(cx.editor, name)?;
+    cx.editor
+        .set_status(format!("Saved session '{}'. Restore with hx --session {}", name, name));
+    Ok(())
+}
+
+fn plugin(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(
+        args.len() >= 2,
+        ":plugin requires a plugin name and a command"
+    );
+    let plugin_args = args[2..].join(" ");
+
+    let doc = doc!(cx.editor);
+    let text = doc.text().to_string();
+
+    let effects = crate::plugin::call(&args[0], &args[1], &plugin_args, text)?;
+    for effect in effects {
+        match effect {
+            crate::plugin::PluginEffect::Status(message) => cx.editor.set_status(message),
+            crate::plugin::PluginEffect::Error(message) => cx.editor.set_error(message),
+            crate::plugin::PluginEffect::ReplaceText(new_text) => {
+                let (view, doc) = current!(cx.editor);
+                let transaction = helix_core::diff::compare_ropes(
+                    doc.text(),
+                    &helix_core::Rope::from(new_text.as_str()),
+                );
+                doc.apply(&transaction, view.id);
+                doc.append_changes_to_history(view);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn job_cancel(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":job-cancel takes no arguments");
+
+    struct JobMeta {
+        id: u64,
+        name: String,
+        started: std::time::Instant,
+    }
+
+    impl ui::menu::Item for JobMeta {
+        type Data = ();
+
+        fn format(&self, _data: &Self::Data) -> Row {
+            Row::new([
+                format!("{}s", self.started.elapsed().as_secs()),
+                self.name.clone(),
+            ])
+        }
+    }
+
+    let mut items: Vec<JobMeta> = cx
+        .jobs
+        .running
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(&id, job)| JobMeta {
+            id,
+            name: job.name.clone(),
+            started: job.started,
+        })
+        .collect();
+    items.sort_by_key(|meta| meta.id);
+
+    if items.is_empty() {
+        cx.editor.set_status("No cancellable jobs running");
+        return Ok(());
+    }
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                let picker = ui::Picker::new(items, (), move |cx, meta, _action| {
+                    if let Some(job) = cx.jobs.running.lock().unwrap().get(&meta.id) {
+                        job.cancel();
+                    }
+                });
+                compositor.push(Box::new(overlaid(picker)))
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+impl ui::menu::Item for crate::tasks::Task {
+    type Data = ();
+
+    fn format(&self, _data: &Self::Data) -> Row {
+        Row::new([
+            self.name.clone(),
+            self.source.to_string(),
+            self.command.clone(),
+        ])
+    }
+}
+
+fn run_task(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() <= 1, ":run-task takes at most one argument");
+
+    let cwd = std::env::current_dir().context("unable to determine the working directory")?;
+    let tasks = crate::tasks::discover(&cwd);
+    if tasks.is_empty() {
+        cx.editor.set_status("No tasks found in the working directory");
+        return Ok(());
+    }
+
+    if let Some(name) = args.get(0) {
+        let task = tasks
+            .into_iter()
+            .find(|task| task.name == name.as_ref())
+            .ok_or_else(|| anyhow!("no task named '{}'", name))?;
+        run_task_impl(cx.editor, task);
+        return Ok(());
+    }
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                let picker = ui::Picker::new(tasks, (), move |cx, task, _action| {
+                    run_task_impl(cx.editor, task.clone());
+                });
+                compositor.push(Box::new(overlaid(picker)))
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+/// Open a scratch buffer for the task's output and run its command through
+/// the configured shell, streaming stdout and stderr into the buffer and
+/// reporting the exit status when it finishes.
+fn run_task_impl(editor: &mut Editor, task: crate::tasks::Task) {
+    use std::process::Stdio;
+    use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+    use tokio::process::Command;
+    use tokio::sync::mpsc::UnboundedSender;
+
+    let shell = editor.config().shell.clone();
+    if shell.is_empty() {
+        editor.set_error("No shell set");
+        return;
+    }
+
+    // Scratch buffer seeded with the command being run.
+    let doc_id = editor.new_file(Action::Replace);
+    let doc = doc_mut!(editor, &doc_id);
+    let view = view_mut!(editor);
+    doc.ensure_view_init(view.id);
+    let header = format!("$ {}\n", task.command);
+    let transaction =
+        helix_core::Transaction::insert(doc.text(), doc.selection(view.id), header.into())
+            .with_selection(Selection::point(0));
+    doc.apply(&transaction, view.id);
+    doc.append_changes_to_history(view);
+
+    // Appends arrive through `job::dispatch` so the task keeps streaming
+    // without blocking the main loop. The transactions never carry a
+    // selection so the fallback view id is only ever used as a dummy.
+    let append = move |text: String| {
+        job::dispatch(Callback::Editor(Box::new(move |editor| {
+            let focus = editor.tree.focus;
+            // the output buffer was closed: drop the output
+            let Some(doc) = editor.document_mut(doc_id) else {
+                return;
+            };
+            let end = doc.text().len_chars();
+            let view_id = doc.selections().keys().next().copied().unwrap_or(focus);
+            let transaction = helix_core::Transaction::change(
+                doc.text(),
+                [(end, end, Some(text.into()))].into_iter(),
+            );
+            doc.apply(&transaction, view_id);
+        })));
+    };
+
+    fn forward(reader: impl AsyncRead + Unpin + Send + 'static, sx: UnboundedSender<String>) {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if sx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    let name = task.name;
+    let command = task.command;
+    tokio::spawn(async move {
+        let mut process = Command::new(&shell[0]);
+        process
+            .args(&shell[1..])
+            .arg(&command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = match process.spawn() {
+            Ok(child) => child,
+            Err(err) => {
+                append(format!("failed to start shell: {}\n", err));
+                return;
+            }
+        };
+
+        let (line_sx, mut line_rx) = tokio::sync::mpsc::unbounded_channel();
+        forward(child.stdout.take().expect("stdout is piped"), line_sx.clone());
+        forward(child.stderr.take().expect("stderr is piped"), line_sx);
+
+        // Batch whatever lines are already pending into a single append so
+        // chatty tasks do not cause a render per line.
+        while let Some(line) = line_rx.recv().await {
+            let mut chunk = line;
+            chunk.push('\n');
+            while let Ok(line) = line_rx.try_recv() {
+                chunk.push_str(&line);
+                chunk.push('\n');
+            }
+            append(chunk);
+        }
+
+        let (message, success) = match child.wait().await {
+            Ok(status) if status.success() => (format!("task '{}' finished", name), true),
+            Ok(status) => match status.code() {
+                Some(code) => (format!("task '{}' failed: exit code {}", name, code), false),
+                None => (format!("task '{}' was terminated by a signal", name), false),
+            },
+            Err(err) => (format!("task '{}' failed: {}", name, err), false),
+        };
+        append(format!("\n[{}]\n", message));
+        job::dispatch(Callback::Editor(Box::new(move |editor| {
+            if success {
+                editor.set_status(message);
+            } else {
+                editor.set_error(message);
+            }
+        })));
+    });
+}
+
+fn make(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let config = cx.editor.config();
+    let shell = config.shell.clone();
+    ensure!(!shell.is_empty(), "No shell set");
+    let patterns = config
+        .quickfix_patterns
+        .iter()
+        .map(|pattern| helix_core::regex::Regex::new(pattern))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| anyhow!("invalid quickfix pattern: {}", err))?;
+
+    let command = if args.is_empty() {
+        "make".to_string()
+    } else {
+        args.join(" ")
+    };
+    let cwd = std::env::current_dir().context("unable to determine the working directory")?;
+    let job_name = format!(":make {}", command);
+
+    let callback = async move {
+        use std::process::Stdio;
+
+        let output = tokio::process::Command::new(&shell[0])
+            .args(&shell[1..])
+            .arg(&command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await?;
+
+        // Compilers report errors on either stream; scan both.
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        let entries = parse_quickfix(&text, &patterns, &cwd);
+        let status = output.status;
+
+        let call: job::Callback = Callback::Editor(Box::new(move |editor| {
+            let count = entries.len();
+            editor.quickfix = entries;
+            editor.quickfix_index = None;
+
+            if count > 0 {
+                editor.set_status(format!(
+                    "'{}': {} quickfix entries, jump with ]q or :quickfix",
+                    command, count
+                ));
+            } else if status.success() {
+                editor.set_status(format!("'{}' finished, quickfix list is empty", command));
+            } else {
+                editor.set_error(match status.code() {
+                    Some(code) => format!("'{}' failed with exit code {}", command, code),
+                    None => format!("'{}' was terminated by a signal", command),
+                });
+            }
+        }));
+        Ok(call)
+    };
+    cx.jobs.add(Job::with_callback(callback).name(job_name));
+
+    Ok(())
+}
+
+/// Match every line of `output` against `patterns` (first match wins) and
+/// collect the extracted locations. Relative paths resolve against `cwd`.
+fn parse_quickfix(
+    output: &str,
+    patterns: &[helix_core::regex::Regex],
+    cwd: &Path,
+) -> Vec<helix_view::editor::QuickfixEntry> {
+    let mut entries = Vec::new();
+    for line in output.lines() {
+        let Some(captures) = patterns.iter().find_map(|pattern| pattern.captures(line)) else {
+            continue;
+        };
+        let Some(file) = captures.name("file") else {
+            continue;
+        };
+        let Some(line_num) = captures
+            .name("line")
+            .and_then(|m| m.as_str().parse::<usize>().ok())
+            .filter(|line| *line > 0)
+        else {
+            continue;
+        };
+        let column = captures
+            .name("col")
+            .and_then(|m| m.as_str().parse::<usize>().ok())
+            .and_then(|col| col.checked_sub(1));
+        let message = captures
+            .name("message")
+            .map(|m| m.as_str().trim().to_string())
+            .unwrap_or_default();
+
+        let mut path = PathBuf::from(file.as_str());
+        if path.is_relative() {
+            path = cwd.join(path);
+        }
+        entries.push(helix_view::editor::QuickfixEntry {
+            path,
+            line: line_num - 1,
+            column,
+            message,
+        });
+    }
+    entries
+}
+
+fn quickfix_picker(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":quickfix takes no arguments");
+
+    struct QuickfixItem {
+        index: usize,
+        entry: helix_view::editor::QuickfixEntry,
+    }
+
+    impl ui::menu::Item for QuickfixItem {
+        type Data = ();
+
+        fn format(&self, _data: &Self::Data) -> Row {
+            let relative_path = helix_core::path::get_relative_path(&self.entry.path)
+                .to_string_lossy()
+                .into_owned();
+            format!(
+                "{}:{}: {}",
+                relative_path,
+                self.entry.line + 1,
+                self.entry.message
+            )
+            .into()
+        }
+    }
+
+    if cx.editor.quickfix.is_empty() {
+        cx.editor.set_status("quickfix list is empty");
+        return Ok(());
+    }
+
+    let items: Vec<QuickfixItem> = cx
+        .editor
+        .quickfix
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| QuickfixItem {
+            index,
+            entry: entry.clone(),
+        })
+        .collect();
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                let picker = ui::Picker::new(items, (), move |cx, item, action| {
+                    super::jump_to_quickfix_entry(cx.editor, item.index, action);
+                })
+                .with_preview(|_editor, item| {
+                    Some((
+                        item.entry.path.clone().into(),
+                        Some((item.entry.line, item.entry.line)),
+                    ))
+                });
+                compositor.push(Box::new(overlaid(picker)))
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn messages(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":messages takes no arguments");
+
+    use helix_view::editor::Severity;
+
+    let mut contents = String::new();
+    for msg in &cx.editor.status_history {
+        let severity = match msg.severity {
+            Severity::Hint => "HINT",
+            Severity::Info => "INFO",
+            Severity::Warning => "WARN",
+            Severity::Error => "ERROR",
+        };
+        contents.push_str(&format!("{:5} {}\n", severity, msg.message));
+    }
+
+    if contents.is_empty() {
+        cx.editor.set_status("No messages this session");
+        return Ok(());
+    }
+
+    let doc_id = cx.editor.new_file(Action::Replace);
+    let doc = doc_mut!(cx.editor, &doc_id);
+    let view = view_mut!(cx.editor);
+    doc.ensure_view_init(view.id);
+    let transaction =
+        helix_core::Transaction::insert(doc.text(), doc.selection(view.id), contents.into())
+            .with_selection(Selection::point(0));
+    doc.apply(&transaction, view.id);
+    doc.append_changes_to_history(view);
+
+    Ok(())
+}
+
+fn clear_search_highlight(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":clear-search-highlight takes no arguments");
+
+    cx.editor.search_matches = None;
+    cx.editor.search_wrapped_match = None;
+
+    Ok(())
+}
+
+fn diagnostics_toggle(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":diagnostics-toggle takes no arguments");
+
+    let doc = doc_mut!(cx.editor);
+    doc.diagnostics_enabled = !doc.diagnostics_enabled;
+    let status = if doc.diagnostics_enabled {
+        "diagnostics shown for this buffer"
+    } else {
+        "diagnostics hidden for this buffer"
+    };
+    cx.editor.set_status(status);
+
+    Ok(())
+}
+
+fn diagnostics_panel(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":diagnostics-panel takes no arguments");
+
+    let callback = async move {
+        let call: job::Callback = job::Callback::EditorCompositor(Box::new(
+            |_editor: &mut Editor, compositor: &mut Compositor| {
+                let editor_view = compositor.find::<ui::EditorView>().unwrap();
+                editor_view.diagnostics_panel = match editor_view.diagnostics_panel.take() {
+                    Some(_) => None,
+                    None => Some(ui::DiagnosticsPanel::new()),
+                };
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn perf(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":perf takes no arguments");
+
+    let callback = async move {
+        let call: job::Callback = job::Callback::EditorCompositor(Box::new(
+            |_editor: &mut Editor, compositor: &mut Compositor| {
+                let editor_view = compositor.find::<ui::EditorView>().unwrap();
+                editor_view.perf_overlay = match editor_view.perf_overlay.take() {
+                    Some(_) => None,
+                    None => Some(ui::PerfOverlay),
+                };
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn key_display(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":key-display takes no arguments");
+
+    let callback = async move {
+        let call: job::Callback = job::Callback::EditorCompositor(Box::new(
+            |_editor: &mut Editor, compositor: &mut Compositor| {
+                let editor_view = compositor.find::<ui::EditorView>().unwrap();
+                editor_view.key_display = match editor_view.key_display.take() {
+                    Some(_) => None,
+                    None => Some(ui::KeyDisplay::new()),
+                };
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn scroll_bind(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":scroll-bind takes no arguments");
+
+    let view = view_mut!(cx.editor);
+    view.scroll_bind = !view.scroll_bind;
+    let status = if view.scroll_bind {
+        "scroll binding enabled"
+    } else {
+        "scroll binding disabled"
+    };
+    cx.editor.set_status(status);
+
+    Ok(())
+}
+
+fn merge_keep_ours(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    merge_keep_impl(cx, args, event, true, false)
+}
+
+fn merge_keep_theirs(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    merge_keep_impl(cx, args, event, false, true)
+}
+
+fn merge_keep_both(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    merge_keep_impl(cx, args, event, true, true)
+}
+
+/// Resolve the merge conflict under the primary cursor by replacing it with
+/// one or both of its sides, markers removed.
+fn merge_keep_impl(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+    keep_ours: bool,
+    keep_theirs: bool,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), "this command takes no arguments");
+
+    let (view, doc) = current!(cx.editor);
+    let text = doc.text().slice(..);
+
+    let cursor_line = doc.selection(view.id).primary().cursor_line(text);
+    let conflict = helix_core::conflict::find_conflicts(text)
+        .into_iter()
+        .find(|conflict| conflict.contains_line(cursor_line))
+        .ok_or_else(|| anyhow!("no merge conflict under the primary cursor"))?;
+
+    let mut replacement = String::new();
+    if keep_ours {
+        replacement.push_str(&Cow::from(text.slice(conflict.ours(text))));
+    }
+    if keep_theirs {
+        replacement.push_str(&Cow::from(text.slice(conflict.theirs(text))));
+    }
+
+    let range = conflict.range(text);
+    let transaction = Transaction::change(
+        doc.text(),
+        std::iter::once((range.start, range.end, Some(replacement.into()))),
+    );
+    doc.apply(&transaction, view.id);
+    doc.append_changes_to_history(view);
+
+    Ok(())
+}
+
+fn keymap(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.is_empty(), ":keymap takes no arguments");
+
+    struct KeymapEntry {
+        mode: &'static str,
+        keys: String,
+        name: String,
+        doc: String,
+    }
+
+    impl ui::menu::Item for KeymapEntry {
+        type Data = ();
+
+        fn format(&self, _data: &Self::Data) -> Row {
+            Row::new([
+                self.mode.to_string(),
+                self.keys.clone(),
+                self.name.clone(),
+                self.doc.clone(),
+            ])
+        }
+    }
+
+    fn walk(
+        entries: &mut Vec<KeymapEntry>,
+        mode: &'static str,
+        keys: &mut Vec<helix_view::input::KeyEvent>,
+        trie: &crate::keymap::KeyTrie,
+    ) {
+        use crate::keymap::KeyTrie;
+
+        let format_keys = |keys: &[helix_view::input::KeyEvent]| {
+            keys.iter()
+                .map(|key| key.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        match trie {
+            KeyTrie::MappableCommand(command) => {
+                if command.name() != "no_op" {
+                    entries.push(KeymapEntry {
+                        mode,
+                        keys: format_keys(keys),
+                        name: command.name().to_string(),
+                        doc: command.doc().to_string(),
+                    });
+                }
+            }
+            KeyTrie::Sequence(commands) => {
+                entries.push(KeymapEntry {
+                    mode,
+                    keys: format_keys(keys),
+                    name: commands
+                        .iter()
+                        .map(|command| command.name())
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                    doc: "command sequence".to_string(),
+                });
+            }
+            KeyTrie::Node(node) => {
+                for (key, sub) in node.iter() {
+                    keys.push(*key);
+                    walk(entries, mode, keys, sub);
+                    keys.pop();
+                }
+            }
+        }
+    }
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                use helix_view::document::Mode;
+
+                let mut entries = Vec::new();
+                {
+                    let editor_view = compositor.find::<ui::EditorView>().unwrap();
+                    // the loaded map includes user overrides, unlike the
+                    // static defaults the book documents
+                    let keymaps = editor_view.keymaps.map();
+                    for (mode, trie) in keymaps.iter() {
+                        let mode = match mode {
+                            Mode::Normal => "normal",
+                            Mode::Insert => "insert",
+                            Mode::Select => "select",
+                        };
+                        walk(&mut entries, mode, &mut Vec::new(), trie);
+                    }
+                }
+                entries.sort_by(|a, b| (a.mode, &a.keys).cmp(&(b.mode, &b.keys)));
+
+                let picker = ui::Picker::new(entries, (), |cx, entry, _action| {
+                    cx.editor.set_status(format!(
+                        "{} mode: {} is bound to {}",
+                        entry.mode, entry.keys, entry.name
+                    ));
+                });
+                compositor.push(Box::new(overlaid(picker)))
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn register_edit(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() == 1, ":register-edit takes a register name");
+    let arg = args[0].as_ref();
+    ensure!(
+        arg.chars().count() == 1,
+        "register names are single characters"
+    );
+    let name = arg.chars().next().unwrap();
+
+    let contents = cx
+        .editor
+        .registers
+        .read(name)
+        .map(|values| values.join("\n"))
+        .unwrap_or_default();
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |editor: &mut Editor, compositor: &mut Compositor| {
+                let prompt = Prompt::new(
+                    format!("edit register {}:", name).into(),
+                    None,
+                    ui::completers::none,
+                    move |cx: &mut compositor::Context, input: &str, event: PromptEvent| {
+                        if event != PromptEvent::Validate {
+                            return;
+                        }
+                        let values: Vec<String> = input.split('\n').map(str::to_string).collect();
+                        cx.editor.registers.write(name, values);
+                        cx.editor.set_status(format!("register {} updated", name));
+                    },
+                )
+                .with_line(contents, editor);
+                compositor.push(Box::new(prompt));
+            },
+        ));
+        Ok(call)
+    };
+    cx.jobs.callback(callback);
+
+    Ok(())
+}
+
+fn diff_open(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    ensure!(args.len() == 1, ":diff-open takes one file to diff against");
+
+    let path = helix_core::path::expand_tilde(std::path::Path::new(args[0].as_ref()));
+    let contents = std::fs::read(&path)
+        .map_err(|err| anyhow!("unable to read {}: {}", path.display(), err))?;
+
+    let doc_contents = doc!(cx.editor).text().to_string().into_bytes();
+
+    // Diff each buffer against the other, like `hx --diff`.
+    let redraw_handle = cx.editor.redraw_handle.clone();
+    doc_mut!(cx.editor).set_diff_base(contents, redraw_handle.clone());
+    let other_id = cx.editor.open(&path, Action::VerticalSplit)?;
+    doc_mut!(cx.editor, &other_id).set_diff_base(doc_contents, redraw_handle);
+
+    Ok(())
+}
 
 pub static TYPABLE_COMMAND_MAP: Lazy<HashMap<&'static str, &'static TypableCommand>> =
     Lazy::new(|| {
@@ -2864,6 +5234,92 @@ pub static TYPABLE_COMMAND_MAP: Lazy<HashMap<&'static str, &'static TypableComma
             .collect()
     });
 
+/// User-defined commands from the `[commands]` section of config.toml: each
+/// name expands to a sequence of typable command lines. Kept up to date by
+/// the application on config (re)load.
+pub static USER_COMMANDS: Lazy<arc_swap::ArcSwap<HashMap<String, Vec<String>>>> =
+    Lazy::new(Default::default);
+
+/// Maximum expansion depth for user-defined commands, guarding against
+/// mutually recursive definitions.
+const MAX_USER_COMMAND_DEPTH: usize = 16;
+
+/// Run the user-defined command `name`, or `None` when no such command is
+/// defined. Entries are full command lines and may reference other
+/// user-defined commands up to a bounded depth.
+pub fn execute_user_command(
+    cx: &mut compositor::Context,
+    name: &str,
+    depth: usize,
+) -> Option<anyhow::Result<()>> {
+    let lines = USER_COMMANDS.load().get(name).cloned()?;
+    Some(execute_user_command_impl(cx, name, &lines, depth))
+}
+
+fn execute_user_command_impl(
+    cx: &mut compositor::Context,
+    name: &str,
+    lines: &[String],
+    depth: usize,
+) -> anyhow::Result<()> {
+    ensure!(
+        depth < MAX_USER_COMMAND_DEPTH,
+        "user-defined command '{}' expands too deeply (recursive definition?)",
+        name
+    );
+
+    for line in lines {
+        let line = expand_variables(cx.editor, line)
+            .map_err(|err| anyhow!("'{}': {}", line, err))?;
+        let shellwords = Shellwords::from(line.as_ref());
+        let words = shellwords.words();
+        let Some(command) = words.first() else { continue };
+
+        if let Some(cmd) = TYPABLE_COMMAND_MAP.get(command.as_ref()) {
+            (cmd.fun)(cx, &words[1..], PromptEvent::Validate)
+                .map_err(|err| anyhow!("'{}': {}", line, err))?;
+        } else if let Some(nested) = USER_COMMANDS.load().get(command.as_ref()).cloned() {
+            execute_user_command_impl(cx, command.as_ref(), &nested, depth + 1)?;
+        } else {
+            return Err(anyhow!("no such command: '{}' (in '{}')", command, name));
+        }
+    }
+    Ok(())
+}
+
+/// Run `input` as if it had been entered at the `:` prompt: a lone number
+/// goes to that line, otherwise the first word is resolved as a typable or
+/// user-defined command. Errors are reported via the status line.
+pub fn execute_command_line(cx: &mut compositor::Context, input: &str) {
+    let parts = input.split_whitespace().collect::<Vec<&str>>();
+    let Some(&command) = parts.first() else { return };
+
+    // If command is numeric, interpret as line number and go there.
+    if parts.len() == 1 && command.parse::<usize>().is_ok() {
+        if let Err(e) = goto_line_number(cx, &[Cow::from(command)], PromptEvent::Validate) {
+            cx.editor.set_error(format!("{}", e));
+        }
+        return;
+    }
+
+    if let Some(cmd) = TYPABLE_COMMAND_MAP.get(command) {
+        let shellwords = Shellwords::from(input);
+        let args = shellwords.words();
+
+        if let Err(e) = (cmd.fun)(cx, &args[1..], PromptEvent::Validate) {
+            cx.editor.set_error(format!("{}", e));
+        }
+    } else {
+        match execute_user_command(cx, command, 0) {
+            Some(Err(e)) => cx.editor.set_error(format!("{}", e)),
+            Some(Ok(())) => (),
+            None => cx
+                .editor
+                .set_error(format!("no such command: '{}'", command)),
+        }
+    }
+}
+
 #[allow(clippy::unnecessary_unwrap)]
 pub(super) fn command_mode(cx: &mut Context) {
     let mut prompt = Prompt::new(
@@ -2883,15 +5339,17 @@ pub(super) fn command_mode(cx: &mut Context) {
                     .filter_map(|command| {
                         FUZZY_MATCHER
                             .fuzzy_match(command.name, input)
-                            .map(|score| (command.name, score))
+                            .map(|score| (Cow::from(command.name), score))
                     })
                     .collect();
+                matches.extend(typed::USER_COMMANDS.load().keys().filter_map(|name| {
+                    FUZZY_MATCHER
+                        .fuzzy_match(name, input)
+                        .map(|score| (Cow::from(name.clone()), score))
+                }));
 
                 matches.sort_unstable_by_key(|(_file, score)| std::cmp::Reverse(*score));
-                matches
-                    .into_iter()
-                    .map(|(name, _)| (0.., name.into()))
-                    .collect()
+                matches.into_iter().map(|(name, _)| (0.., name)).collect()
             } else {
                 // Otherwise, use the command's completer and the last shellword
                 // as completion input.
@@ -2924,6 +5382,11 @@ pub(super) fn command_mode(cx: &mut Context) {
             }
         }, // completion
         move |cx: &mut compositor::Context, input: &str, event: PromptEvent| {
+            if event == PromptEvent::Validate {
+                typed::execute_command_line(cx, input);
+                return;
+            }
+
             let parts = input.split_whitespace().collect::<Vec<&str>>();
             if parts.is_empty() {
                 return;
@@ -2937,7 +5400,7 @@ pub(super) fn command_mode(cx: &mut Context) {
                 return;
             }
 
-            // Handle typable commands
+            // Preview typable commands on update/abort.
             if let Some(cmd) = typed::TYPABLE_COMMAND_MAP.get(parts[0]) {
                 let shellwords = Shellwords::from(input);
                 let args = shellwords.words();
@@ -2945,9 +5408,6 @@ pub(super) fn command_mode(cx: &mut Context) {
                 if let Err(e) = (cmd.fun)(cx, &args[1..], event) {
                     cx.editor.set_error(format!("{}", e));
                 }
-            } else if event == PromptEvent::Validate {
-                cx.editor
-                    .set_error(format!("no such command: '{}'", parts[0]));
             }
         },
     );
@@ -2963,6 +5423,10 @@ pub(super) fn command_mode(cx: &mut Context) {
             return Some(format!("{}\nAliases: {}", doc, aliases.join(", ")).into());
         }
 
+        if let Some(lines) = typed::USER_COMMANDS.load().get(part) {
+            return Some(format!("user-defined: {}", lines.join("; ")).into());
+        }
+
         None
     });
 
@@ -0,0 +1,161 @@
+//! Shell completion scripts for the `hx` binary, printed by
+//! `hx --completions <shell>`.
+//!
+//! The argument parser in [`crate::args`] is hand-rolled, so the scripts are
+//! generated from templates here rather than derived from a clap definition.
+//! Each script completes every flag `hx` accepts and defines two dynamic
+//! helpers: one listing language names (via `hx --health languages`) used for
+//! `--health`, and one listing installed theme names from the runtime and
+//! user theme directories.
+
+use std::io::Write;
+
+const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+const BASH: &str = r#"# Bash completion script for the Helix editor.
+# Generated by `hx --completions bash`.
+
+__hx_languages() {
+    hx --health languages 2>/dev/null | tail -n +2 | awk '{print $1}' | sed 's/\x1b\[[0-9;]*m//g'
+}
+
+__hx_themes() {
+    local dir
+    for dir in "$HELIX_RUNTIME/themes" "${XDG_CONFIG_HOME:-$HOME/.config}/helix/themes"; do
+        [ -d "$dir" ] && command ls "$dir" 2>/dev/null | sed 's/\.toml$//'
+    done
+    printf 'default\nbase16_default\n'
+}
+
+_hx() {
+    local cur prev
+    cur="${COMP_WORDS[COMP_CWORD]}"
+    prev="${COMP_WORDS[COMP_CWORD-1]}"
+
+    case "$prev" in
+        --health)
+            COMPREPLY=($(compgen -W "all clipboard languages $(__hx_languages)" -- "$cur"))
+            return 0
+            ;;
+        -g|--grammar)
+            COMPREPLY=($(compgen -W "fetch build" -- "$cur"))
+            return 0
+            ;;
+        --completions)
+            COMPREPLY=($(compgen -W "bash zsh fish" -- "$cur"))
+            return 0
+            ;;
+        -c|--config|--log)
+            COMPREPLY=($(compgen -f -- "$cur"))
+            return 0
+            ;;
+    esac
+
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=($(compgen -W "-h --help --tutor --health -g --grammar -c --config --command -v -V --version --log --remote --session --diff --vsplit --hsplit --completions" -- "$cur"))
+    else
+        COMPREPLY=($(compgen -fd -- "$cur"))
+    fi
+}
+
+complete -o filenames -F _hx hx
+"#;
+
+const ZSH: &str = r#"#compdef hx
+# Zsh completion script for the Helix editor.
+# Generated by `hx --completions zsh`.
+
+__hx_languages() {
+    hx --health languages 2>/dev/null | tail -n +2 | awk '{print $1}' | sed 's/\x1b\[[0-9;]*m//g'
+}
+
+__hx_themes() {
+    local dir
+    for dir in "$HELIX_RUNTIME/themes" "${XDG_CONFIG_HOME:-$HOME/.config}/helix/themes"; do
+        [ -d "$dir" ] && command ls "$dir" 2>/dev/null | sed 's/\.toml$//'
+    done
+    printf 'default\nbase16_default\n'
+}
+
+_hx() {
+    _arguments -s \
+        "(-h --help)"{-h,--help}"[Prints help information]" \
+        "--tutor[Loads the tutorial]" \
+        "--health[Checks for errors in editor setup]:language:->health" \
+        "(-g --grammar)"{-g,--grammar}"[Fetches or builds tree-sitter grammars]:action:(fetch build)" \
+        "(-c --config)"{-c,--config}"[Specifies a file to use for configuration]:file:_files" \
+        "--command[Runs the given typable command after the UI initializes]:command:" \
+        "-v[Increases logging verbosity]" \
+        "(-V --version)"{-V,--version}"[Prints version information]" \
+        "--log[Specifies a file to use for logging]:file:_files" \
+        "--remote[Opens the given files in an already running helix instance]" \
+        "--session[Restores a session saved with :session-save]:session:" \
+        "--diff[Opens two files side by side and diffs them]:file:_files" \
+        "--vsplit[Splits all given files vertically]" \
+        "--hsplit[Splits all given files horizontally]" \
+        "--completions[Prints a shell completion script]:shell:(bash zsh fish)" \
+        "*:file:_files"
+
+    case "$state" in
+        health)
+            local -a languages
+            languages=(all clipboard languages $(__hx_languages))
+            _values "language" $languages
+            ;;
+    esac
+}
+
+_hx "$@"
+"#;
+
+const FISH: &str = r#"# Fish completion script for the Helix editor.
+# Generated by `hx --completions fish`.
+
+function __hx_languages
+    hx --health languages 2>/dev/null | tail -n +2 | awk '{print $1}' | sed 's/\x1b\[[0-9;]*m//g'
+end
+
+function __hx_themes
+    for dir in "$HELIX_RUNTIME/themes" "$HOME/.config/helix/themes"
+        test -d "$dir"; and command ls "$dir" 2>/dev/null | sed 's/\.toml$//'
+    end
+    printf 'default\nbase16_default\n'
+end
+
+complete -c hx -s h -l help -d "Prints help information"
+complete -c hx -l tutor -d "Loads the tutorial"
+complete -c hx -l health -x -a "all clipboard languages (__hx_languages)" -d "Checks for errors in editor setup"
+complete -c hx -s g -l grammar -x -a "fetch build" -d "Fetches or builds tree-sitter grammars"
+complete -c hx -s c -l config -r -d "Specifies a file to use for configuration"
+complete -c hx -l command -x -d "Runs the given typable command after the UI initializes"
+complete -c hx -s v -o vv -o vvv -d "Increases logging verbosity"
+complete -c hx -s V -l version -d "Prints version information"
+complete -c hx -l log -r -d "Specifies a file to use for logging"
+complete -c hx -l remote -d "Opens the given files in an already running helix instance"
+complete -c hx -l session -x -d "Restores a session saved with :session-save"
+complete -c hx -l diff -r -d "Opens two files side by side and diffs them"
+complete -c hx -l vsplit -d "Splits all given files vertically into different windows"
+complete -c hx -l hsplit -d "Splits all given files horizontally into different windows"
+complete -c hx -l completions -x -a "bash zsh fish" -d "Prints a shell completion script"
+"#;
+
+/// Print the completion script for `shell` to stdout. Errors on unknown
+/// shells so a typo doesn't silently install an empty script.
+pub fn print_completions(shell: &str) -> anyhow::Result<()> {
+    let script = match shell {
+        "bash" => BASH,
+        "zsh" => ZSH,
+        "fish" => FISH,
+        _ => anyhow::bail!(
+            "unknown shell '{}', expected one of {}",
+            shell,
+            SHELLS.join(", ")
+        ),
+    };
+
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+    stdout.write_all(script.as_bytes())?;
+
+    Ok(())
+}
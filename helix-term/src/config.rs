@@ -12,7 +12,14 @@ use toml::de::Error as TomlError;
 #[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     pub theme: Option<String>,
+    /// Themes picked over `theme` when the terminal background is detected
+    /// as light or dark respectively.
+    pub light_theme: Option<String>,
+    pub dark_theme: Option<String>,
     pub keys: HashMap<Mode, KeyTrie>,
+    /// User-defined commands from `[commands]`: each name expands to a
+    /// sequence of typable command lines.
+    pub commands: HashMap<String, Vec<String>>,
     pub editor: helix_view::editor::Config,
 }
 
@@ -20,7 +27,12 @@ pub struct Config {
 #[serde(deny_unknown_fields)]
 pub struct ConfigRaw {
     pub theme: Option<String>,
+    #[serde(rename = "light-theme")]
+    pub light_theme: Option<String>,
+    #[serde(rename = "dark-theme")]
+    pub dark_theme: Option<String>,
     pub keys: Option<HashMap<Mode, KeyTrie>>,
+    pub commands: Option<HashMap<String, Vec<String>>>,
     pub editor: Option<toml::Value>,
 }
 
@@ -28,7 +40,10 @@ impl Default for Config {
     fn default() -> Config {
         Config {
             theme: None,
+            light_theme: None,
+            dark_theme: None,
             keys: keymap::default(),
+            commands: HashMap::new(),
             editor: helix_view::editor::Config::default(),
         }
     }
@@ -84,9 +99,16 @@ impl Config {
                         .map_err(ConfigLoadError::BadConfig)?,
                 };
 
+                // local definitions shadow global ones of the same name
+                let mut commands = global.commands.unwrap_or_default();
+                commands.extend(local.commands.unwrap_or_default());
+
                 Config {
                     theme: local.theme.or(global.theme),
+                    light_theme: local.light_theme.or(global.light_theme),
+                    dark_theme: local.dark_theme.or(global.dark_theme),
                     keys,
+                    commands,
                     editor,
                 }
             }
@@ -102,7 +124,10 @@ impl Config {
                 }
                 Config {
                     theme: config.theme,
+                    light_theme: config.light_theme,
+                    dark_theme: config.dark_theme,
                     keys,
+                    commands: config.commands.unwrap_or_default(),
                     editor: config.editor.map_or_else(
                         || Ok(helix_view::editor::Config::default()),
                         |val| val.try_into().map_err(ConfigLoadError::BadConfig),
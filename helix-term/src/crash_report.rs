@@ -0,0 +1,81 @@
+//! Crash reporting: when a panic occurs, a report with the backtrace, a
+//! summary of the editor state (open files, recently pressed keys) and
+//! version info is written to the cache directory so bug reports contain
+//! something actionable.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use helix_view::input::KeyEvent;
+use helix_view::Editor;
+
+/// How many of the most recently pressed keys are included in a report.
+const MAX_RECENT_KEYS: usize = 32;
+
+struct Context {
+    open_files: Vec<String>,
+    recent_keys: VecDeque<KeyEvent>,
+}
+
+static CONTEXT: Mutex<Context> = Mutex::new(Context {
+    open_files: Vec::new(),
+    recent_keys: VecDeque::new(),
+});
+
+/// Records a pressed key and a snapshot of the open documents, so the panic
+/// hook has something to report without access to the editor.
+pub fn record_event(editor: &Editor, key: KeyEvent) {
+    let Ok(mut context) = CONTEXT.lock() else { return };
+    context.recent_keys.push_back(key);
+    while context.recent_keys.len() > MAX_RECENT_KEYS {
+        context.recent_keys.pop_front();
+    }
+    context.open_files = editor
+        .documents()
+        .map(|doc| doc.display_name().into_owned())
+        .collect();
+}
+
+fn crash_file() -> PathBuf {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    helix_loader::cache_dir().join(format!("helix-crash-{}.txt", timestamp))
+}
+
+/// Writes a crash report for `info`, returning its path. Called from the
+/// panic hook, so errors are swallowed rather than propagated.
+pub fn write_report(info: &PanicHookInfo) -> Option<PathBuf> {
+    // force_capture ignores RUST_BACKTRACE: if we crashed we want the
+    // backtrace in the report regardless of the environment.
+    let backtrace = std::backtrace::Backtrace::force_capture();
+
+    let mut report = String::new();
+    let _ = writeln!(report, "helix {}", helix_loader::VERSION_AND_GIT_HASH);
+    let _ = writeln!(report, "{}", info);
+
+    // try_lock: the panicking thread may already hold the lock, and a
+    // deadlock here would swallow the panic message entirely.
+    if let Ok(context) = CONTEXT.try_lock() {
+        let _ = writeln!(report, "\nopen files:");
+        for file in &context.open_files {
+            let _ = writeln!(report, "  {}", file);
+        }
+        let _ = write!(report, "\nrecent keys:");
+        for key in &context.recent_keys {
+            let _ = write!(report, " {}", key);
+        }
+        let _ = writeln!(report);
+    }
+
+    let _ = writeln!(report, "\nbacktrace:\n{}", backtrace);
+
+    let path = crash_file();
+    std::fs::create_dir_all(helix_loader::cache_dir()).ok()?;
+    std::fs::write(&path, report).ok()?;
+    Some(path)
+}
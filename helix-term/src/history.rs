@@ -0,0 +1,88 @@
+//! Persistence for prompt history: the search (`/`) and command (`:`)
+//! registers are written to the state directory on exit and loaded back
+//! on startup.
+
+use std::fs;
+use std::path::PathBuf;
+
+use helix_core::register::Registers;
+
+/// The registers whose contents survive across sessions.
+const HISTORY_REGISTERS: &[char] = &['/', ':'];
+
+/// At most this many entries are persisted per register, oldest dropped first.
+const MAX_PERSISTED_ENTRIES: usize = 100;
+
+fn history_file() -> PathBuf {
+    helix_loader::state_dir().join("history")
+}
+
+/// Entries are stored one per line as `<register>\t<entry>` with newlines
+/// and backslashes escaped.
+fn escape(entry: &str) -> String {
+    entry.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(entry: &str) -> String {
+    let mut unescaped = String::with_capacity(entry.len());
+    let mut chars = entry.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some(c) => unescaped.push(c),
+                None => break,
+            },
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// Load the persisted history into `registers`, oldest entries first so that
+/// the most recent entry ends up last, as `Registers::push` expects.
+pub fn load(registers: &mut Registers) {
+    let contents = match fs::read_to_string(history_file()) {
+        Ok(contents) => contents,
+        Err(_) => return,
+    };
+
+    for line in contents.lines() {
+        let mut chars = line.chars();
+        let register = match (chars.next(), chars.next()) {
+            (Some(register), Some('\t')) => register,
+            _ => continue,
+        };
+        if HISTORY_REGISTERS.contains(&register) {
+            registers.push(register, unescape(chars.as_str()));
+        }
+    }
+}
+
+/// Persist the history registers, keeping at most the newest
+/// [`MAX_PERSISTED_ENTRIES`] entries per register.
+pub fn save(registers: &Registers) {
+    let path = history_file();
+    if let Some(parent) = path.parent() {
+        if !parent.exists() && fs::create_dir_all(parent).is_err() {
+            return;
+        }
+    }
+
+    let mut contents = String::new();
+    for &register in HISTORY_REGISTERS {
+        if let Some(values) = registers.read(register) {
+            let skip = values.len().saturating_sub(MAX_PERSISTED_ENTRIES);
+            for entry in &values[skip..] {
+                contents.push(register);
+                contents.push('\t');
+                contents.push_str(&escape(entry));
+                contents.push('\n');
+            }
+        }
+    }
+
+    if let Err(err) = fs::write(&path, contents) {
+        log::error!("Failed to write history file: {}", err);
+    }
+}
@@ -0,0 +1,118 @@
+//! Client/server mode: `hx --remote file[:row[:col]]` asks an already
+//! running instance to open the given files over a per-user Unix socket
+//! instead of nesting editor sessions, so `$EDITOR` invocations from git
+//! or a terminal land in the editor that is already open.
+//!
+//! The first instance to start owns the socket; later instances neither
+//! serve nor fail. The protocol is one file per line as
+//! `<path>\t<row>\t<col>` (zero-based row and column), with paths made
+//! absolute by the client since the working directories may differ.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use helix_core::Position;
+
+/// Files a client asked us to open, with cursor positions.
+pub type Request = Vec<(PathBuf, Position)>;
+
+fn socket_path() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(helix_loader::state_dir)
+        .join("helix.sock")
+}
+
+/// Binds the socket and spawns a thread accepting open requests, returning
+/// the channel they arrive on. Returns `None` when another instance is
+/// already serving (or the socket cannot be bound); this instance then
+/// simply runs standalone.
+#[cfg(unix)]
+pub fn spawn_listener() -> Option<tokio::sync::mpsc::UnboundedReceiver<Request>> {
+    use std::io::Read;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let path = socket_path();
+    if UnixStream::connect(&path).is_ok() {
+        return None;
+    }
+    // a leftover socket from a crashed instance refuses connections but
+    // still blocks bind, so clear it out first
+    let _ = std::fs::remove_file(&path);
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("unable to bind {}: {}", path.display(), err);
+            return None;
+        }
+    };
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut message = String::new();
+            if stream.read_to_string(&mut message).is_err() {
+                continue;
+            }
+            let request: Request = message.lines().filter_map(parse_line).collect();
+            if !request.is_empty() && tx.send(request).is_err() {
+                // the application is gone, stop serving
+                break;
+            }
+        }
+    });
+    Some(rx)
+}
+
+#[cfg(not(unix))]
+pub fn spawn_listener() -> Option<tokio::sync::mpsc::UnboundedReceiver<Request>> {
+    None
+}
+
+fn parse_line(line: &str) -> Option<(PathBuf, Position)> {
+    let mut fields = line.split('\t');
+    let path = PathBuf::from(fields.next()?);
+    let row = fields.next()?.parse().ok()?;
+    let col = fields.next()?.parse().ok()?;
+    Some((path, Position::new(row, col)))
+}
+
+/// Removes the socket on shutdown. Only called by the serving instance.
+pub fn cleanup() {
+    let _ = std::fs::remove_file(socket_path());
+}
+
+/// Asks the running instance to open `files`. Fails when no instance is
+/// listening so the caller can report it instead of silently editing
+/// nothing.
+#[cfg(unix)]
+pub fn send_request(files: &[(PathBuf, Position)]) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::net::UnixStream;
+
+    use anyhow::Context;
+
+    anyhow::ensure!(!files.is_empty(), "--remote requires at least one file");
+
+    let mut stream = UnixStream::connect(socket_path())
+        .context("no running helix instance found to connect to")?;
+    let cwd = std::env::current_dir().context("unable to determine current directory")?;
+    let mut message = String::new();
+    for (path, pos) in files {
+        let path = if path.is_absolute() {
+            path.clone()
+        } else {
+            cwd.join(path)
+        };
+        use std::fmt::Write as _;
+        let _ = writeln!(message, "{}\t{}\t{}", path.display(), pos.row, pos.col);
+    }
+    stream.write_all(message.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn send_request(_files: &[(PathBuf, Position)]) -> Result<()> {
+    anyhow::bail!("--remote is only supported on Unix")
+}
@@ -2,7 +2,14 @@ use helix_view::Editor;
 
 use crate::compositor::Compositor;
 
-use futures_util::future::{BoxFuture, Future, FutureExt};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use futures_util::future::{AbortHandle, Abortable, BoxFuture, Future, FutureExt};
 use futures_util::stream::{FuturesUnordered, StreamExt};
 
 pub type EditorCompositorCallback = Box<dyn FnOnce(&mut Editor, &mut Compositor) + Send>;
@@ -19,13 +26,125 @@ pub struct Job {
     pub future: BoxFuture<'static, anyhow::Result<Option<Callback>>>,
     /// Do we need to wait for this job to finish before exiting?
     pub wait: bool,
+    /// Jobs with a name show up in the `:job-cancel` picker and can be
+    /// canceled there.
+    pub name: Option<String>,
+    /// Fail the job if it has not finished after this long.
+    pub timeout: Option<Duration>,
+    /// A progress handle the job reports completion percentage through,
+    /// shown by the `background-jobs` statusline element.
+    pub progress: Option<JobProgress>,
+}
+
+/// A handle a job can clone into its future to report how far along it is.
+/// The percentage is rendered next to the job's spinner in the statusline.
+#[derive(Clone)]
+pub struct JobProgress(Arc<AtomicU8>);
+
+// sentinel for "no percentage reported (yet)"
+const PROGRESS_UNKNOWN: u8 = u8::MAX;
+
+impl JobProgress {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicU8::new(PROGRESS_UNKNOWN)))
+    }
+
+    pub fn set(&self, percent: u8) {
+        self.0.store(percent.min(100), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> Option<u8> {
+        match self.0.load(Ordering::Relaxed) {
+            PROGRESS_UNKNOWN => None,
+            percent => Some(percent),
+        }
+    }
+}
+
+impl Default for JobProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A currently running named job, cancellable through its abort handle.
+pub struct RunningJob {
+    pub name: String,
+    pub started: Instant,
+    pub progress: Option<JobProgress>,
+    abort: AbortHandle,
+}
+
+impl RunningJob {
+    pub fn cancel(&self) {
+        self.abort.abort();
+    }
+}
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(0);
+
+type DispatchChannel = (
+    tokio::sync::mpsc::UnboundedSender<Callback>,
+    Mutex<Option<tokio::sync::mpsc::UnboundedReceiver<Callback>>>,
+);
+
+// Lets long-running background tasks (which outlive a single job future)
+// queue callbacks onto the main loop; the receiver is polled by
+// `Application::event_loop_until_idle`.
+static DISPATCH: Lazy<DispatchChannel> = Lazy::new(|| {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    (tx, Mutex::new(Some(rx)))
+});
+
+/// Queue a callback from any thread or task. It runs on the main loop with
+/// full editor access as soon as it is received.
+pub fn dispatch(callback: Callback) {
+    // The send only fails when the receiver is gone, i.e. during shutdown.
+    let _ = DISPATCH.0.send(callback);
+}
+
+/// Take the receiving end of the [`dispatch`] channel. May only be called
+/// once, by the application event loop.
+pub fn take_dispatch_receiver() -> tokio::sync::mpsc::UnboundedReceiver<Callback> {
+    DISPATCH
+        .1
+        .lock()
+        .unwrap()
+        .take()
+        .expect("dispatch receiver already taken")
+}
+
+// Shared between every `Jobs` instance (there is only one in practice) so
+// that the statusline can read it without access to `Jobs` itself.
+static RUNNING: Lazy<Arc<Mutex<HashMap<u64, RunningJob>>>> = Lazy::new(Default::default);
+
+/// A snapshot of the oldest running named job for the statusline: its name,
+/// reported progress and how many named jobs are running in total.
+pub fn oldest_running() -> Option<(String, Option<u8>, usize)> {
+    let running = RUNNING.lock().unwrap();
+    let count = running.len();
+    running
+        .iter()
+        .min_by_key(|(&id, _)| id)
+        .map(|(_, job)| (job.name.clone(), job.progress.as_ref().and_then(JobProgress::get), count))
 }
 
-#[derive(Default)]
 pub struct Jobs {
     pub futures: FuturesUnordered<JobFuture>,
     /// These are the ones that need to complete before we exit.
     pub wait_futures: FuturesUnordered<JobFuture>,
+    /// Named jobs that are currently running, keyed by job id.
+    pub running: Arc<Mutex<HashMap<u64, RunningJob>>>,
+}
+
+impl Default for Jobs {
+    fn default() -> Self {
+        Self {
+            futures: FuturesUnordered::default(),
+            wait_futures: FuturesUnordered::default(),
+            running: RUNNING.clone(),
+        }
+    }
 }
 
 impl Job {
@@ -33,6 +152,9 @@ impl Job {
         Self {
             future: f.map(|r| r.map(|()| None)).boxed(),
             wait: false,
+            name: None,
+            timeout: None,
+            progress: None,
         }
     }
 
@@ -42,6 +164,9 @@ impl Job {
         Self {
             future: f.map(|r| r.map(Some)).boxed(),
             wait: false,
+            name: None,
+            timeout: None,
+            progress: None,
         }
     }
 
@@ -49,6 +174,21 @@ impl Job {
         self.wait = true;
         self
     }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn progress(mut self, progress: JobProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
 }
 
 impl Jobs {
@@ -93,10 +233,58 @@ impl Jobs {
     }
 
     pub fn add(&self, j: Job) {
-        if j.wait {
-            self.wait_futures.push(j.future);
+        let Job {
+            mut future,
+            wait,
+            name,
+            timeout,
+            progress,
+        } = j;
+
+        if let Some(timeout) = timeout {
+            let name = name.clone().unwrap_or_else(|| "job".to_string());
+            future = async move {
+                match tokio::time::timeout(timeout, future).await {
+                    Ok(result) => result,
+                    Err(_) => Err(anyhow::anyhow!("'{}' timed out", name)),
+                }
+            }
+            .boxed();
+        }
+
+        if let Some(name) = name {
+            let id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+            let (abort, abort_registration) = AbortHandle::new_pair();
+            self.running.lock().unwrap().insert(
+                id,
+                RunningJob {
+                    name: name.clone(),
+                    started: Instant::now(),
+                    progress,
+                    abort,
+                },
+            );
+            let running = self.running.clone();
+            let abortable = Abortable::new(future, abort_registration);
+            future = async move {
+                let result = abortable.await;
+                running.lock().unwrap().remove(&id);
+                match result {
+                    Ok(result) => result,
+                    // the cancellation was requested by the user, so report
+                    // it as a status rather than an error
+                    Err(_aborted) => Ok(Some(Callback::Editor(Box::new(move |editor| {
+                        editor.set_status(format!("Canceled job '{}'", name));
+                    })))),
+                }
+            }
+            .boxed();
+        }
+
+        if wait {
+            self.wait_futures.push(future);
         } else {
-            self.futures.push(j.future);
+            self.futures.push(future);
         }
     }
 
@@ -58,6 +58,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "k" => move_line_up,
             "j" => move_line_down,
             "." => goto_last_modification,
+            "|" => goto_column,
         },
         ":" => command_mode,
 
@@ -79,6 +80,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
 
         "s" => select_regex,
         "A-s" => split_selection_on_newline,
+        "A-S" => split_selection_on_column,
         "A-minus" => merge_selections,
         "A-_" => merge_consecutive_selections,
         "S" => split_selection,
@@ -89,7 +91,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
         "A-p" | "A-left" => select_prev_sibling,
         "A-n" | "A-right" => select_next_sibling,
 
-        "%" => select_all,
+        "%" => goto_line_percentage,
         "x" => extend_line_below,
         "X" => extend_to_line_bounds,
         "A-x" => shrink_to_line_bounds,
@@ -107,6 +109,8 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "D" => goto_first_diag,
             "g" => goto_prev_change,
             "G" => goto_first_change,
+            "n" => goto_prev_conflict,
+            "q" => goto_prev_quickfix,
             "f" => goto_prev_function,
             "t" => goto_prev_class,
             "a" => goto_prev_parameter,
@@ -120,6 +124,8 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "D" => goto_last_diag,
             "g" => goto_next_change,
             "G" => goto_last_change,
+            "n" => goto_next_conflict,
+            "q" => goto_next_quickfix,
             "f" => goto_next_function,
             "t" => goto_next_class,
             "a" => goto_next_parameter,
@@ -131,9 +137,11 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
 
         "/" => search,
         "?" => rsearch,
+        "A-/" => search_in_selection,
         "n" => search_next,
         "N" => search_prev,
-        "*" => search_selection,
+        "*" => search_selection_next,
+        "#" => search_selection_prev,
 
         "u" => undo,
         "U" => redo,
@@ -164,6 +172,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
         // "Q" => replay_macro,
 
         "&" => align_selections,
+        "A-&" => align_cursors,
         "_" => trim_selections,
 
         "(" => rotate_selections_backward,
@@ -186,6 +195,8 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "C-t" | "t" => transpose_view,
             "f" => goto_file_hsplit,
             "F" => goto_file_vsplit,
+            "d" => goto_definition_hsplit,
+            "D" => goto_definition_vsplit,
             "C-q" | "q" => wclose,
             "C-o" | "o" => wonly,
             "C-h" | "h" | "left" => jump_view_left,
@@ -216,6 +227,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
             "F" => file_picker_in_current_directory,
             "b" => buffer_picker,
             "j" => jumplist_picker,
+            "u" => undo_history_picker,
             "s" => symbol_picker,
             "S" => workspace_symbol_picker,
             "d" => diagnostics_picker,
@@ -232,6 +244,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
                 "o" => dap_step_out,
                 "n" => dap_next,
                 "v" => dap_variables,
+                "x" => dap_evaluate,
                 "t" => dap_terminate,
                 "C-c" => dap_edit_condition,
                 "C-l" => dap_edit_log,
@@ -250,6 +263,8 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
                 "C-t" | "t" => transpose_view,
                 "f" => goto_file_hsplit,
                 "F" => goto_file_vsplit,
+                "d" => goto_definition_hsplit,
+                "D" => goto_definition_vsplit,
                 "C-q" | "q" => wclose,
                 "C-o" | "o" => wonly,
                 "C-h" | "h" | "left" => jump_view_left,
@@ -360,6 +375,7 @@ pub fn default() -> HashMap<Mode, KeyTrie> {
         "C-s" => commit_undo_checkpoint,
         "C-x" => completion,
         "C-r" => insert_register,
+        "C-v" => insert_unicode,
 
         "C-w" | "A-backspace" => delete_word_backward,
         "A-d" | "A-del" => delete_word_forward,
@@ -4,11 +4,21 @@ extern crate helix_view;
 pub mod application;
 pub mod args;
 pub mod commands;
+pub mod completions;
 pub mod compositor;
 pub mod config;
+pub mod crash_report;
 pub mod health;
+pub mod history;
+pub mod ipc;
 pub mod job;
 pub mod keymap;
+pub mod plugin;
+pub mod remote;
+pub mod session;
+pub mod snippets;
+pub mod spell;
+pub mod tasks;
 pub mod ui;
 use std::path::Path;
 
@@ -26,6 +36,114 @@ fn true_color() -> bool {
     true
 }
 
+/// Best-effort guess whether the terminal background is dark, used to pick
+/// between the configured `light-theme` and `dark-theme`. Tries an OSC 11
+/// background color query first and falls back to the `COLORFGBG` hint some
+/// terminals export. `None` when neither gives an answer.
+fn terminal_background_dark() -> Option<bool> {
+    if let Some((r, g, b)) = query_background_color() {
+        // ITU-R BT.601 luma
+        let luma = (299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000;
+        return Some(luma < 128);
+    }
+
+    // `COLORFGBG` is `<fg>;<bg>` (sometimes `<fg>;default;<bg>`) with ANSI
+    // palette indices; 7 and 15 are the light background colors.
+    let hint = std::env::var("COLORFGBG").ok()?;
+    let bg: u8 = hint.rsplit(';').next()?.trim().parse().ok()?;
+    Some(bg != 7 && bg != 15)
+}
+
+/// Query the terminal background color with OSC 11, returning 8-bit RGB.
+/// Talks to the controlling tty directly so it works both before the UI
+/// starts and while the alternate screen is active, and gives up quietly on
+/// terminals that don't answer within the timeout.
+#[cfg(unix)]
+fn query_background_color() -> Option<(u8, u8, u8)> {
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let fd = tty.as_raw_fd();
+
+    // Raw mode just long enough for the reply, so it isn't echoed or line
+    // buffered.
+    let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+    if unsafe { libc::tcgetattr(fd, &mut termios) } != 0 {
+        return None;
+    }
+    let saved = termios;
+    unsafe { libc::cfmakeraw(&mut termios) };
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &termios) } != 0 {
+        return None;
+    }
+
+    let result = (|| {
+        tty.write_all(b"\x1b]11;?\x1b\\").ok()?;
+        tty.flush().ok()?;
+
+        let mut reply = Vec::new();
+        let mut buf = [0u8; 64];
+        loop {
+            let mut pollfd = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            if unsafe { libc::poll(&mut pollfd, 1, 100) } <= 0 {
+                return None;
+            }
+            let n = tty.read(&mut buf).ok()?;
+            if n == 0 {
+                return None;
+            }
+            reply.extend_from_slice(&buf[..n]);
+            // The reply is terminated by BEL or ST (ESC \)
+            if reply.contains(&0x07) || reply.windows(2).any(|pair| pair == b"\x1b\\") {
+                break;
+            }
+            if reply.len() > 256 {
+                return None;
+            }
+        }
+        parse_osc_color(&String::from_utf8_lossy(&reply))
+    })();
+
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &saved) };
+    result
+}
+
+#[cfg(not(unix))]
+fn query_background_color() -> Option<(u8, u8, u8)> {
+    None
+}
+
+/// Parse the `rgb:RRRR/GGGG/BBBB` payload of an OSC 11 reply; each channel
+/// is 1 to 4 hex digits wide.
+fn parse_osc_color(reply: &str) -> Option<(u8, u8, u8)> {
+    fn channel(value: &str) -> Option<u8> {
+        let value = value.trim_matches(|c: char| !c.is_ascii_hexdigit());
+        if value.is_empty() || value.len() > 4 {
+            return None;
+        }
+        let max = (1u32 << (4 * value.len() as u32)) - 1;
+        let value = u32::from_str_radix(value, 16).ok()?;
+        Some((value * 255 / max) as u8)
+    }
+
+    let start = reply.find("rgb:")? + 4;
+    let mut channels = reply[start..].splitn(3, '/');
+    let r = channel(channels.next()?)?;
+    let g = channel(channels.next()?)?;
+    let b = channel(channels.next()?)?;
+    Some((r, g, b))
+}
+
 /// Function used for filtering dir entries in the various file pickers.
 fn filter_picker_entry(entry: &DirEntry, root: &Path, dedup_symlinks: bool) -> bool {
     // We always want to ignore the .git directory, otherwise if
@@ -67,12 +67,19 @@ FLAGS:
                                    or 'all'. 'all' is the default if not specified.
     -g, --grammar {{fetch|build}}    Fetches or builds tree-sitter grammars listed in languages.toml
     -c, --config <file>            Specifies a file to use for configuration
+    -c, --command <:command>       Runs the given typable command after the UI initializes
+                                   (may be given multiple times; `-c` treats arguments starting
+                                   with ':' as commands)
     -v                             Increases logging verbosity each use for up to 3 times
     --log <file>                   Specifies a file to use for logging
                                    (default file: {})
     -V, --version                  Prints version information
+    --remote                       Opens the given files in an already running helix instance
+    --session <name>               Restores a session saved with :session-save
+    --diff <left> <right>          Opens the two files side by side and diffs them against each other
     --vsplit                       Splits all given files vertically into different windows
     --hsplit                       Splits all given files horizontally into different windows
+    --completions {{bash|zsh|fish}}  Prints a completion script for the given shell to stdout
 ",
         env!("CARGO_PKG_NAME"),
         VERSION_AND_GIT_HASH,
@@ -106,6 +113,16 @@ FLAGS:
         std::process::exit(0);
     }
 
+    if let Some(shell) = args.completions.as_deref() {
+        helix_term::completions::print_completions(shell)?;
+        std::process::exit(0);
+    }
+
+    if args.remote {
+        helix_term::ipc::send_request(&args.files)?;
+        return Ok(0);
+    }
+
     if args.fetch_grammars {
         helix_loader::grammar::fetch_grammars()?;
         return Ok(0);
@@ -0,0 +1,270 @@
+//! A minimal WASM plugin runtime.
+//!
+//! Plugins are WebAssembly modules placed in the `plugins` directory inside
+//! the config directory (typically `~/.config/helix/plugins/*.wasm`). They
+//! are discovered and instantiated once at startup and invoked through the
+//! `:plugin` typable command, which can in turn be bound to keys like any
+//! other typable command.
+//!
+//! The host API is deliberately small (version 0):
+//!
+//! * Plugins may export `hx_init()`, called once after instantiation.
+//! * Every export named `hx_cmd_<name>(ptr, len)` becomes a plugin command
+//!   invokable as `:plugin <plugin> <name> [args…]`. The joined argument
+//!   string is copied into plugin memory via the plugin's `hx_alloc(len)`
+//!   export; plugins without arguments can omit `hx_alloc`.
+//! * Host functions are importable from the `"helix"` module:
+//!   - `status(ptr, len)` / `error(ptr, len)`: report a message.
+//!   - `text_len() -> len` / `text_read(ptr)`: read a snapshot of the
+//!     focused document taken just before the call.
+//!   - `text_replace(ptr, len)`: replace the document contents. The
+//!     replacement is diffed against the snapshot and applied as a regular
+//!     transaction, so it composes with undo and multiple cursors elsewhere.
+//!
+//! Plugins never touch editor state directly: host calls record effects
+//! which the invoking command drains and applies through the usual editor
+//! paths once the WASM call returns.
+
+use std::sync::Mutex;
+
+use anyhow::{anyhow, bail, Result};
+use once_cell::sync::Lazy;
+use wasmi::{core::Trap, Caller, Engine, Extern, Instance, Linker, Memory, Module, Store};
+
+/// Name prefix for exported plugin commands.
+const COMMAND_PREFIX: &str = "hx_cmd_";
+
+/// An editor-side action requested by a plugin during a call.
+pub enum PluginEffect {
+    Status(String),
+    Error(String),
+    /// Replace the contents of the focused document.
+    ReplaceText(String),
+}
+
+/// Per-instance state accessible to host functions.
+#[derive(Default)]
+struct PluginData {
+    /// Snapshot of the focused document, taken before each call.
+    text: String,
+    effects: Vec<PluginEffect>,
+}
+
+pub struct Plugin {
+    name: String,
+    store: Store<PluginData>,
+    instance: Instance,
+}
+
+impl Plugin {
+    fn load(name: String, wasm: &[u8]) -> Result<Self> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, wasm)?;
+        let mut store = Store::new(&engine, PluginData::default());
+
+        let mut linker = <Linker<PluginData>>::new(&engine);
+        linker.func_wrap(
+            "helix",
+            "status",
+            |mut caller: Caller<'_, PluginData>, ptr: i32, len: i32| {
+                let message = read_string(&mut caller, ptr, len)?;
+                caller.data_mut().effects.push(PluginEffect::Status(message));
+                Ok(())
+            },
+        )?;
+        linker.func_wrap(
+            "helix",
+            "error",
+            |mut caller: Caller<'_, PluginData>, ptr: i32, len: i32| {
+                let message = read_string(&mut caller, ptr, len)?;
+                caller.data_mut().effects.push(PluginEffect::Error(message));
+                Ok(())
+            },
+        )?;
+        linker.func_wrap("helix", "text_len", |caller: Caller<'_, PluginData>| {
+            caller.data().text.len() as i32
+        })?;
+        linker.func_wrap(
+            "helix",
+            "text_read",
+            |mut caller: Caller<'_, PluginData>, ptr: i32| {
+                let text = std::mem::take(&mut caller.data_mut().text);
+                let memory = plugin_memory(&mut caller)?;
+                memory
+                    .write(&mut caller, ptr as usize, text.as_bytes())
+                    .map_err(|err| Trap::new(err.to_string()))?;
+                caller.data_mut().text = text;
+                Ok(())
+            },
+        )?;
+        linker.func_wrap(
+            "helix",
+            "text_replace",
+            |mut caller: Caller<'_, PluginData>, ptr: i32, len: i32| {
+                let text = read_string(&mut caller, ptr, len)?;
+                caller
+                    .data_mut()
+                    .effects
+                    .push(PluginEffect::ReplaceText(text));
+                Ok(())
+            },
+        )?;
+
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+
+        if let Ok(init) = instance.get_typed_func::<(), ()>(&store, "hx_init") {
+            init.call(&mut store, ())?;
+        }
+
+        Ok(Self {
+            name,
+            store,
+            instance,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The commands this plugin exports, without the `hx_cmd_` prefix.
+    pub fn commands(&self) -> Vec<String> {
+        self.instance
+            .exports(&self.store)
+            .filter_map(|export| export.name().strip_prefix(COMMAND_PREFIX).map(String::from))
+            .collect()
+    }
+
+    /// Invoke an exported command with `text` as the current document
+    /// snapshot, returning the effects the plugin requested.
+    pub fn call(&mut self, command: &str, args: &str, text: String) -> Result<Vec<PluginEffect>> {
+        let func = self
+            .instance
+            .get_typed_func::<(i32, i32), ()>(&self.store, &format!("{COMMAND_PREFIX}{command}"))
+            .map_err(|_| anyhow!("plugin '{}' has no command '{}'", self.name, command))?;
+
+        let (ptr, len) = if args.is_empty() {
+            (0, 0)
+        } else {
+            let alloc = self
+                .instance
+                .get_typed_func::<i32, i32>(&self.store, "hx_alloc")
+                .map_err(|_| {
+                    anyhow!(
+                        "plugin '{}' takes no arguments (missing hx_alloc export)",
+                        self.name
+                    )
+                })?;
+            let ptr = alloc.call(&mut self.store, args.len() as i32)?;
+            let memory = self
+                .instance
+                .get_memory(&self.store, "memory")
+                .ok_or_else(|| anyhow!("plugin '{}' exports no memory", self.name))?;
+            memory
+                .write(&mut self.store, ptr as usize, args.as_bytes())
+                .map_err(|err| anyhow!("plugin '{}': {}", self.name, err))?;
+            (ptr, args.len() as i32)
+        };
+
+        self.store.data_mut().text = text;
+        self.store.data_mut().effects.clear();
+        let result = func.call(&mut self.store, (ptr, len));
+        self.store.data_mut().text = String::new();
+        result?;
+        Ok(std::mem::take(&mut self.store.data_mut().effects))
+    }
+}
+
+fn plugin_memory(caller: &mut Caller<'_, PluginData>) -> Result<Memory, Trap> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| Trap::new("plugin exports no memory"))
+}
+
+fn read_string(
+    caller: &mut Caller<'_, PluginData>,
+    ptr: i32,
+    len: i32,
+) -> Result<String, Trap> {
+    let memory = plugin_memory(caller)?;
+    let mut buf = vec![0; len as usize];
+    memory
+        .read(&*caller, ptr as usize, &mut buf)
+        .map_err(|err| Trap::new(err.to_string()))?;
+    String::from_utf8(buf).map_err(|_| Trap::new("plugin passed invalid utf-8"))
+}
+
+#[derive(Default)]
+pub struct PluginManager {
+    plugins: Vec<Plugin>,
+}
+
+impl PluginManager {
+    /// Load every `*.wasm` file from the `plugins` config directory.
+    /// Failures are logged and skipped so one broken plugin cannot prevent
+    /// the editor from starting.
+    fn load() -> Self {
+        let mut plugins = Vec::new();
+        let dir = helix_loader::config_dir().join("plugins");
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => return Self { plugins },
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map_or(true, |ext| ext != "wasm") {
+                continue;
+            }
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            match std::fs::read(&path).map_err(anyhow::Error::from) {
+                Ok(wasm) => match Plugin::load(name.clone(), &wasm) {
+                    Ok(plugin) => {
+                        log::info!("loaded plugin '{}' from {}", name, path.display());
+                        plugins.push(plugin);
+                    }
+                    Err(err) => log::error!("failed to load plugin {}: {}", path.display(), err),
+                },
+                Err(err) => log::error!("failed to read plugin {}: {}", path.display(), err),
+            }
+        }
+        Self { plugins }
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.plugins.iter().map(|plugin| plugin.name())
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Result<&mut Plugin> {
+        self.plugins
+            .iter_mut()
+            .find(|plugin| plugin.name() == name)
+            .ok_or_else(|| anyhow!("no plugin named '{}'", name))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+}
+
+static PLUGINS: Lazy<Mutex<PluginManager>> = Lazy::new(|| Mutex::new(PluginManager::load()));
+
+/// Force plugin discovery; called once at startup.
+pub fn initialize() {
+    let manager = PLUGINS.lock().unwrap();
+    if !manager.is_empty() {
+        log::info!("{} plugin(s) loaded", manager.names().count());
+    }
+}
+
+/// Run a plugin command against the given document snapshot.
+pub fn call(plugin: &str, command: &str, args: &str, text: String) -> Result<Vec<PluginEffect>> {
+    let mut manager = PLUGINS.lock().unwrap();
+    if manager.is_empty() {
+        bail!("no plugins are loaded");
+    }
+    manager.get_mut(plugin)?.call(command, args, text)
+}
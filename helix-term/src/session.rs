@@ -0,0 +1,216 @@
+//! Saving and restoring editor sessions.
+//!
+//! A session records the working directory, the open buffers, the split
+//! layout and the cursor position of every view. Sessions are saved with
+//! the `:session-save [name]` typable command and restored by starting the
+//! editor with `hx --session <name>`. They are stored as TOML files in the
+//! `sessions` directory inside the cache directory.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use helix_core::{coords_at_pos, pos_at_coords, Selection};
+use helix_view::{editor::Action, tree::Layout, tree::ViewLayout, Editor};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Session {
+    pub working_directory: PathBuf,
+    pub layout: Node,
+}
+
+/// One node of the persisted split tree. Mirrors [`ViewLayout`], with views
+/// replaced by the path and cursor position of the document they showed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Node {
+    Split {
+        layout: SplitLayout,
+        children: Vec<Node>,
+    },
+    View {
+        path: PathBuf,
+        row: usize,
+        col: usize,
+        #[serde(default)]
+        focused: bool,
+    },
+}
+
+/// Serializable mirror of [`Layout`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SplitLayout {
+    Horizontal,
+    Vertical,
+}
+
+impl From<Layout> for SplitLayout {
+    fn from(layout: Layout) -> Self {
+        match layout {
+            Layout::Horizontal => Self::Horizontal,
+            Layout::Vertical => Self::Vertical,
+        }
+    }
+}
+
+fn session_file(name: &str) -> PathBuf {
+    helix_loader::cache_dir()
+        .join("sessions")
+        .join(format!("{}.toml", name))
+}
+
+/// Names of all saved sessions, most recently written first.
+pub fn list() -> Vec<String> {
+    let dir = helix_loader::cache_dir().join("sessions");
+    let mut sessions: Vec<(std::time::SystemTime, String)> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            let modified = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            Some((modified, name))
+        })
+        .collect();
+    sessions.sort_by_key(|&(modified, _)| std::cmp::Reverse(modified));
+    sessions.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Capture the current editor state and write it to the session file for
+/// `name`.
+pub fn save(editor: &Editor, name: &str) -> Result<()> {
+    let layout = node_from_layout(editor, editor.tree.layout())
+        .ok_or_else(|| anyhow!("no file-backed buffers to save in this session"))?;
+    let session = Session {
+        working_directory: std::env::current_dir()?,
+        layout,
+    };
+
+    let path = session_file(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, toml::to_string(&session)?)
+        .with_context(|| format!("failed to write session file {}", path.display()))?;
+    Ok(())
+}
+
+fn node_from_layout(editor: &Editor, layout: ViewLayout) -> Option<Node> {
+    match layout {
+        ViewLayout::Split { layout, children } => {
+            let mut children: Vec<_> = children
+                .into_iter()
+                .filter_map(|child| node_from_layout(editor, child))
+                .collect();
+            match children.len() {
+                // scratch-only containers collapse away
+                0 => None,
+                1 => Some(children.remove(0)),
+                _ => Some(Node::Split {
+                    layout: layout.into(),
+                    children,
+                }),
+            }
+        }
+        ViewLayout::View(view_id) => {
+            let view = editor.tree.get(view_id);
+            let doc = editor.documents.get(&view.doc)?;
+            // scratch buffers have nothing to restore from
+            let path = doc.path()?.clone();
+            let text = doc.text().slice(..);
+            let cursor = doc
+                .selection(view_id)
+                .primary()
+                .cursor(text);
+            let position = coords_at_pos(text, cursor);
+            Some(Node::View {
+                path,
+                row: position.row,
+                col: position.col,
+                focused: editor.tree.focus == view_id,
+            })
+        }
+    }
+}
+
+/// Read the session file for `name`.
+pub fn load(name: &str) -> Result<Session> {
+    let path = session_file(name);
+    let bytes = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read session file {}", path.display()))?;
+    toml::from_str(&bytes).with_context(|| format!("malformed session file {}", path.display()))
+}
+
+/// Restore a session into a freshly started editor: change to the saved
+/// working directory, then recreate the split tree, buffers and cursors.
+pub fn restore(editor: &mut Editor, session: Session) -> Result<()> {
+    if session.working_directory.is_dir() {
+        std::env::set_current_dir(&session.working_directory)?;
+    }
+
+    let mut focus = None;
+    restore_node(editor, &session.layout, Action::VerticalSplit, &mut focus)?;
+    if let Some(view_id) = focus {
+        editor.focus(view_id);
+    }
+    Ok(())
+}
+
+fn restore_node(
+    editor: &mut Editor,
+    node: &Node,
+    action: Action,
+    focus: &mut Option<helix_view::ViewId>,
+) -> Result<()> {
+    match node {
+        Node::View {
+            path,
+            row,
+            col,
+            focused,
+        } => {
+            if !path.exists() {
+                bail!("session file refers to missing file {}", path.display());
+            }
+            let doc_id = editor
+                .open(path, action)
+                .with_context(|| format!("open '{}'", path.display()))?;
+            let view_id = editor.tree.focus;
+            let doc = doc_mut!(editor, &doc_id);
+            let pos = pos_at_coords(
+                doc.text().slice(..),
+                helix_core::Position::new(*row, *col),
+                true,
+            );
+            doc.set_selection(view_id, Selection::point(pos));
+            if *focused {
+                *focus = Some(view_id);
+            }
+        }
+        Node::Split { layout, children } => {
+            let split_action = match layout {
+                SplitLayout::Horizontal => Action::HorizontalSplit,
+                SplitLayout::Vertical => Action::VerticalSplit,
+            };
+            for (i, child) in children.iter().enumerate() {
+                // the first child reuses the view the parent assigned to
+                // this subtree, subsequent children split off from it
+                let action = if i == 0 { action } else { split_action };
+                if let Err(err) = restore_node(editor, child, action, focus) {
+                    log::error!("session restore: {}", err);
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,155 @@
+//! Loading of user-defined snippets.
+//!
+//! Snippets are read from `snippets/<language>.json` in the config directory
+//! (plus `snippets/global.json` for all languages), in the VSCode format:
+//!
+//! ```json
+//! {
+//!     "Print to console": {
+//!         "prefix": "log",
+//!         "body": ["println!(\"{}\", ${1:value});", "$0"],
+//!         "description": "println! with a placeholder"
+//!     }
+//! }
+//! ```
+//!
+//! `prefix` and `body` may each be a single string or a list of strings. The
+//! bodies use the LSP snippet syntax and are expanded through the same
+//! tabstop engine as language server snippets.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+/// Snippet definitions that apply to every language live in this pseudo
+/// language file.
+const GLOBAL: &str = "global";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+struct SnippetDef {
+    prefix: OneOrMany,
+    body: OneOrMany,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    pub prefix: String,
+    pub body: String,
+    pub description: Option<String>,
+}
+
+/// A snippet file's parsed contents plus the mtime it was loaded at.
+type CachedFile = (Option<SystemTime>, Vec<Snippet>);
+
+/// Loaded snippet files, keyed by language and invalidated when the file's
+/// mtime changes so edits are picked up without restarting.
+static CACHE: Lazy<Mutex<HashMap<String, CachedFile>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn snippet_file(language: &str) -> PathBuf {
+    helix_loader::config_dir()
+        .join("snippets")
+        .join(format!("{}.json", language))
+}
+
+/// All snippets that apply to `language`: its own file first, then the
+/// global ones.
+pub fn language_snippets(language: &str) -> Vec<Snippet> {
+    let mut snippets = load(language);
+    snippets.extend(load(GLOBAL));
+    snippets
+}
+
+fn load(language: &str) -> Vec<Snippet> {
+    let path = snippet_file(language);
+    let mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+    let mut cache = CACHE.lock().unwrap();
+    if let Some((cached_mtime, snippets)) = cache.get(language) {
+        if *cached_mtime == mtime {
+            return snippets.clone();
+        }
+    }
+
+    let snippets = if mtime.is_some() {
+        parse_file(&path).unwrap_or_else(|err| {
+            log::error!("Failed to load snippets from {}: {}", path.display(), err);
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+    cache.insert(language.to_string(), (mtime, snippets.clone()));
+    snippets
+}
+
+fn parse_file(path: &std::path::Path) -> anyhow::Result<Vec<Snippet>> {
+    let text = std::fs::read_to_string(path)?;
+    let defs: HashMap<String, SnippetDef> = serde_json::from_str(&text)?;
+
+    let mut snippets = Vec::with_capacity(defs.len());
+    for (name, def) in defs {
+        let body = match def.body {
+            OneOrMany::One(line) => line,
+            OneOrMany::Many(lines) => lines.join("\n"),
+        };
+        let description = def.description.or(Some(name));
+        let prefixes = match def.prefix {
+            OneOrMany::One(prefix) => vec![prefix],
+            OneOrMany::Many(prefixes) => prefixes,
+        };
+        for prefix in prefixes {
+            snippets.push(Snippet {
+                prefix,
+                body: body.clone(),
+                description: description.clone(),
+            });
+        }
+    }
+    snippets.sort_by(|a, b| a.prefix.cmp(&b.prefix));
+    Ok(snippets)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_vscode_format() {
+        let text = r#"{
+            "Print": {
+                "prefix": ["log", "print"],
+                "body": ["println!(\"{}\", ${1:value});", "$0"],
+                "description": "println! with a placeholder"
+            },
+            "Main": { "prefix": "main", "body": "fn main() {\n    $0\n}" }
+        }"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rust.json");
+        std::fs::write(&path, text).unwrap();
+
+        let snippets = parse_file(&path).unwrap();
+        assert_eq!(snippets.len(), 3);
+        assert_eq!(snippets[0].prefix, "log");
+        assert_eq!(snippets[0].body, "println!(\"{}\", ${1:value});\n$0");
+        assert_eq!(
+            snippets[0].description.as_deref(),
+            Some("println! with a placeholder")
+        );
+        assert_eq!(snippets[1].prefix, "main");
+        assert_eq!(snippets[1].description.as_deref(), Some("Main"));
+        assert_eq!(snippets[2].prefix, "print");
+    }
+}
@@ -0,0 +1,177 @@
+//! Naive dictionary based spell checking.
+//!
+//! Words are checked against a word list loaded from the system dictionary
+//! (`/usr/share/dict/words` and friends) merged with a personal dictionary
+//! at `<config-dir>/spell/personal.txt`. `:spell-check` lists misspellings
+//! in a picker together with suggestions; `:spell-add` appends words to
+//! the personal dictionary.
+//!
+//! This intentionally checks the whole buffer: restricting checks to
+//! comment/string scopes and rendering inline underlines need decoration
+//! support in the renderer and are left for a follow-up.
+
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::RwLock;
+
+use anyhow::{bail, Context, Result};
+use helix_core::RopeSlice;
+use once_cell::sync::Lazy;
+
+/// Candidate system word lists, first match wins.
+const SYSTEM_DICTIONARIES: &[&str] = &[
+    "/usr/share/dict/words",
+    "/usr/share/dict/american-english",
+    "/usr/share/dict/british-english",
+];
+
+static DICTIONARY: Lazy<RwLock<HashSet<String>>> = Lazy::new(|| RwLock::new(load_dictionary()));
+
+fn personal_dictionary_path() -> std::path::PathBuf {
+    helix_loader::config_dir().join("spell").join("personal.txt")
+}
+
+fn load_dictionary() -> HashSet<String> {
+    let mut words = HashSet::new();
+    for path in SYSTEM_DICTIONARIES {
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            words.extend(contents.lines().map(|word| word.trim().to_lowercase()));
+            break;
+        }
+    }
+    if let Ok(contents) = std::fs::read_to_string(personal_dictionary_path()) {
+        words.extend(contents.lines().map(|word| word.trim().to_lowercase()));
+    }
+    words
+}
+
+/// Whether any dictionary could be loaded at all.
+pub fn dictionary_available() -> bool {
+    !DICTIONARY.read().unwrap().is_empty()
+}
+
+pub fn is_correct(word: &str) -> bool {
+    let dictionary = DICTIONARY.read().unwrap();
+    dictionary.contains(&word.to_lowercase())
+}
+
+/// Add a word to the personal dictionary, both in memory and on disk.
+pub fn add_word(word: &str) -> Result<()> {
+    let word = word.trim();
+    if word.is_empty() {
+        bail!("cannot add an empty word to the dictionary");
+    }
+    let path = personal_dictionary_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    writeln!(file, "{}", word)?;
+    DICTIONARY.write().unwrap().insert(word.to_lowercase());
+    Ok(())
+}
+
+/// A misspelled word and its location as a char range in the document.
+pub struct Misspelling {
+    pub word: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Scan `text` for words not present in the dictionary.
+///
+/// Identifier-like tokens (mixed case, containing digits or underscores)
+/// are skipped to cut down on noise in source code.
+pub fn misspellings(text: RopeSlice) -> Vec<Misspelling> {
+    let mut result = Vec::new();
+    let mut word = String::new();
+    let mut start = 0;
+    let mut identifier = false;
+
+    let mut flush = |word: &mut String, start: usize, end: usize, identifier: bool| {
+        if word.len() >= 2 && !identifier && !is_correct(word) {
+            result.push(Misspelling {
+                word: std::mem::take(word),
+                start,
+                end,
+            });
+        }
+        word.clear();
+    };
+
+    for (i, ch) in text.chars().enumerate() {
+        if ch.is_alphabetic() || (ch == '\'' && !word.is_empty()) {
+            if word.is_empty() {
+                start = i;
+                identifier = false;
+            }
+            // uppercase after the first letter: camelCase identifier
+            if ch.is_uppercase() && !word.is_empty() {
+                identifier = true;
+            }
+            word.push(ch);
+        } else {
+            if ch.is_ascii_digit() || ch == '_' {
+                identifier = true;
+            }
+            flush(&mut word, start, i, identifier);
+        }
+    }
+    let len = text.len_chars();
+    flush(&mut word, start, len, identifier);
+
+    result
+}
+
+/// Suggest up to `limit` corrections for `word` by testing all single-edit
+/// variants against the dictionary.
+pub fn suggestions(word: &str, limit: usize) -> Vec<String> {
+    let word = word.to_lowercase();
+    let dictionary = DICTIONARY.read().unwrap();
+    let chars: Vec<char> = word.chars().collect();
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+
+    let push = |candidate: String, result: &mut Vec<String>, seen: &mut HashSet<String>| {
+        if candidate != word && dictionary.contains(&candidate) && seen.insert(candidate.clone()) {
+            result.push(candidate);
+        }
+    };
+
+    // deletions
+    for i in 0..chars.len() {
+        let mut candidate = chars.clone();
+        candidate.remove(i);
+        push(candidate.iter().collect(), &mut result, &mut seen);
+    }
+    // transpositions
+    for i in 0..chars.len().saturating_sub(1) {
+        let mut candidate = chars.clone();
+        candidate.swap(i, i + 1);
+        push(candidate.iter().collect(), &mut result, &mut seen);
+    }
+    // replacements
+    for i in 0..chars.len() {
+        for ch in 'a'..='z' {
+            let mut candidate = chars.clone();
+            candidate[i] = ch;
+            push(candidate.iter().collect(), &mut result, &mut seen);
+        }
+    }
+    // insertions
+    for i in 0..=chars.len() {
+        for ch in 'a'..='z' {
+            let mut candidate = chars.clone();
+            candidate.insert(i, ch);
+            push(candidate.iter().collect(), &mut result, &mut seen);
+        }
+    }
+
+    result.truncate(limit);
+    result
+}
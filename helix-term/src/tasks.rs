@@ -0,0 +1,215 @@
+//! Task discovery for the `:run-task` command.
+//!
+//! Tasks are collected from common project files in the working directory:
+//!
+//! * `.helix/tasks.toml` — a table of `name = "shell command"` entries,
+//!   taking priority over everything below.
+//! * `Makefile` (also `makefile`, `GNUmakefile`) — one task per target.
+//! * `package.json` — one task per entry in `scripts`.
+//! * `Cargo.toml` — the usual cargo verbs (build, check, test, ...).
+//!
+//! Discovery is deliberately cheap (a handful of small file reads) so that
+//! it can run every time the command is invoked; there is no caching or
+//! file watching involved.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Where a [`Task`] was discovered; shown in the task picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSource {
+    TasksToml,
+    Makefile,
+    PackageJson,
+    Cargo,
+}
+
+impl fmt::Display for TaskSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::TasksToml => "tasks.toml",
+            Self::Makefile => "make",
+            Self::PackageJson => "npm",
+            Self::Cargo => "cargo",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Task {
+    pub name: String,
+    /// The shell command the task runs.
+    pub command: String,
+    pub source: TaskSource,
+}
+
+/// Collect the tasks defined in `cwd`, in priority order. When several
+/// sources define the same task name only the highest-priority one is kept.
+pub fn discover(cwd: &Path) -> Vec<Task> {
+    let mut tasks = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    let mut add = |mut found: Vec<Task>, tasks: &mut Vec<Task>| {
+        found.retain(|task| seen.insert(task.name.clone()));
+        tasks.append(&mut found);
+    };
+
+    add(tasks_toml(cwd), &mut tasks);
+    add(makefile(cwd), &mut tasks);
+    add(package_json(cwd), &mut tasks);
+    add(cargo(cwd), &mut tasks);
+
+    tasks
+}
+
+fn tasks_toml(cwd: &Path) -> Vec<Task> {
+    let Ok(contents) = fs::read_to_string(cwd.join(".helix").join("tasks.toml")) else {
+        return Vec::new();
+    };
+    let table: toml::Table = match toml::from_str(&contents) {
+        Ok(table) => table,
+        Err(err) => {
+            log::error!("Invalid .helix/tasks.toml: {}", err);
+            return Vec::new();
+        }
+    };
+
+    let mut tasks: Vec<Task> = table
+        .into_iter()
+        .filter_map(|(name, value)| match value {
+            toml::Value::String(command) => Some(Task {
+                name,
+                command,
+                source: TaskSource::TasksToml,
+            }),
+            _ => {
+                log::error!("Task '{}' in .helix/tasks.toml is not a string", name);
+                None
+            }
+        })
+        .collect();
+    tasks.sort_by(|a, b| a.name.cmp(&b.name));
+    tasks
+}
+
+fn makefile(cwd: &Path) -> Vec<Task> {
+    let contents = ["Makefile", "makefile", "GNUmakefile"]
+        .iter()
+        .find_map(|name| fs::read_to_string(cwd.join(name)).ok());
+    let Some(contents) = contents else {
+        return Vec::new();
+    };
+
+    let mut tasks = Vec::new();
+    for line in contents.lines() {
+        // A target line starts in the first column and reads `name: deps`.
+        // Skip special targets (`.PHONY`), pattern rules, variable
+        // assignments and anything needing make-side expansion.
+        let Some((name, rest)) = line.split_once(':') else {
+            continue;
+        };
+        // `foo := bar` also splits on ':'
+        if rest.starts_with('=') {
+            continue;
+        }
+        let name = name.trim_end();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'))
+            || name.starts_with('.')
+        {
+            continue;
+        }
+        tasks.push(Task {
+            name: name.to_string(),
+            command: format!("make {}", name),
+            source: TaskSource::Makefile,
+        });
+    }
+    tasks
+}
+
+fn package_json(cwd: &Path) -> Vec<Task> {
+    let Ok(contents) = fs::read_to_string(cwd.join("package.json")) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&contents) else {
+        return Vec::new();
+    };
+    let Some(scripts) = json.get("scripts").and_then(|scripts| scripts.as_object()) else {
+        return Vec::new();
+    };
+
+    scripts
+        .keys()
+        .map(|name| Task {
+            name: name.clone(),
+            command: format!("npm run {}", name),
+            source: TaskSource::PackageJson,
+        })
+        .collect()
+}
+
+fn cargo(cwd: &Path) -> Vec<Task> {
+    if !cwd.join("Cargo.toml").exists() {
+        return Vec::new();
+    }
+
+    ["build", "check", "test", "run", "clippy", "fmt", "doc"]
+        .iter()
+        .map(|verb| Task {
+            name: verb.to_string(),
+            command: format!("cargo {}", verb),
+            source: TaskSource::Cargo,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn makefile_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Makefile"),
+            "VAR := value\n\
+             .PHONY: all\n\
+             all: build test\n\
+             build:\n\
+             \tcargo build\n\
+             %.o: %.c\n\
+             check-fmt:\n\
+             \tcargo fmt --check\n",
+        )
+        .unwrap();
+
+        let tasks = makefile(dir.path());
+        let names: Vec<_> = tasks.iter().map(|task| task.name.as_str()).collect();
+        assert_eq!(names, ["all", "build", "check-fmt"]);
+        assert_eq!(tasks[0].command, "make all");
+    }
+
+    #[test]
+    fn tasks_toml_priority() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".helix")).unwrap();
+        fs::write(
+            dir.path().join(".helix").join("tasks.toml"),
+            "build = \"just build\"\nlint = \"just lint\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+
+        let tasks = discover(dir.path());
+        // `.helix/tasks.toml` wins over the cargo task of the same name
+        let build = tasks.iter().find(|task| task.name == "build").unwrap();
+        assert_eq!(build.command, "just build");
+        assert_eq!(build.source, TaskSource::TasksToml);
+        assert!(tasks.iter().any(|task| task.source == TaskSource::Cargo));
+    }
+}
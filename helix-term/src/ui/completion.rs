@@ -91,6 +91,23 @@ pub struct CompletionItem {
     pub resolved: bool,
 }
 
+impl CompletionItem {
+    /// Sentinel `language_server_id` for items sourced from the words of open
+    /// buffers rather than a language server.
+    pub const WORD_SOURCE: usize = usize::MAX;
+    /// Sentinel `language_server_id` for items sourced from user snippet
+    /// files rather than a language server.
+    pub const SNIPPET_SOURCE: usize = usize::MAX - 1;
+
+    /// Whether this item comes from one of the non language server sources.
+    pub fn is_local(&self) -> bool {
+        matches!(
+            self.language_server_id,
+            Self::WORD_SOURCE | Self::SNIPPET_SOURCE
+        )
+    }
+}
+
 /// Wraps a Menu.
 pub struct Completion {
     popup: Popup<Menu<CompletionItem>>,
@@ -253,11 +270,17 @@ impl Completion {
                     // always present here
                     let item = item.unwrap();
 
+                    let offset_encoding = if item.is_local() {
+                        OffsetEncoding::Utf8
+                    } else {
+                        language_server!(item).offset_encoding()
+                    };
+
                     let transaction = item_to_transaction(
                         doc,
                         view.id,
                         &item.item,
-                        language_server!(item).offset_encoding(),
+                        offset_encoding,
                         trigger_offset,
                         true,
                         replace_mode,
@@ -274,20 +297,22 @@ impl Completion {
                     // always present here
                     let mut item = item.unwrap().clone();
 
-                    let language_server = language_server!(item);
-                    let offset_encoding = language_server.offset_encoding();
-
-                    let language_server = editor
-                        .language_servers
-                        .get_by_id(item.language_server_id)
-                        .unwrap();
+                    let offset_encoding = if item.is_local() {
+                        OffsetEncoding::Utf8
+                    } else {
+                        language_server!(item).offset_encoding()
+                    };
 
                     // resolve item if not yet resolved
                     if !item.resolved {
-                        if let Some(resolved) =
-                            Self::resolve_completion_item(language_server, item.item.clone())
+                        if let Some(language_server) =
+                            editor.language_servers.get_by_id(item.language_server_id)
                         {
-                            item.item = resolved;
+                            if let Some(resolved) =
+                                Self::resolve_completion_item(language_server, item.item.clone())
+                            {
+                                item.item = resolved;
+                            }
                         }
                     };
                     // if more text was entered, remove it
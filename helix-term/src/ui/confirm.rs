@@ -0,0 +1,70 @@
+//! A single-keypress confirmation dialog rendered on the statusline row.
+//!
+//! Unlike [`crate::ui::Prompt`] it does not take free-form input: the caller
+//! lists the accepted choice characters and gets the pressed one back, with
+//! `Escape` always cancelling the dialog.
+
+use crate::compositor::{Component, Compositor, Context, Event, EventResult};
+use helix_view::graphics::Rect;
+use helix_view::input::{KeyCode, KeyEvent};
+use tui::buffer::Buffer as Surface;
+
+pub type ConfirmCallback = Box<dyn FnOnce(&mut Compositor, &mut Context, char) + 'static>;
+
+pub struct Confirm {
+    message: String,
+    choices: &'static [char],
+    callback: Option<ConfirmCallback>,
+}
+
+impl Confirm {
+    pub fn new(
+        message: String,
+        choices: &'static [char],
+        callback: ConfirmCallback,
+    ) -> Self {
+        Self {
+            message,
+            choices,
+            callback: Some(callback),
+        }
+    }
+}
+
+impl Component for Confirm {
+    fn handle_event(&mut self, event: &Event, _cx: &mut Context) -> EventResult {
+        match event {
+            Event::Key(KeyEvent {
+                code: KeyCode::Esc, ..
+            }) => EventResult::Consumed(Some(Box::new(|compositor, _cx| {
+                compositor.pop();
+            }))),
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(ch),
+                ..
+            }) if self.choices.contains(&ch.to_ascii_lowercase()) => {
+                let choice = ch.to_ascii_lowercase();
+                let callback = self.callback.take();
+                EventResult::Consumed(Some(Box::new(move |compositor, cx| {
+                    compositor.pop();
+                    if let Some(callback) = callback {
+                        callback(compositor, cx, choice);
+                    }
+                })))
+            }
+            // the dialog is modal: swallow every other key instead of letting
+            // it reach the editor underneath
+            Event::Key(_) => EventResult::Consumed(None),
+            _ => EventResult::Ignored(None),
+        }
+    }
+
+    fn render(&mut self, area: Rect, surface: &mut Surface, cx: &mut Context) {
+        let line = area.y + area.height.saturating_sub(1);
+        surface.clear_with(
+            Rect::new(area.x, line, area.width, 1),
+            cx.editor.theme.get("ui.background"),
+        );
+        surface.set_string(area.x, line, &self.message, cx.editor.theme.get("warning"));
+    }
+}
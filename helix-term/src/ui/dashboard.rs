@@ -0,0 +1,83 @@
+//! Start screen drawn over the empty scratch buffer when helix is launched
+//! without file arguments: recently opened files, saved sessions and a few
+//! key hints. Dismissed by the first key press and disabled entirely with
+//! `editor.dashboard = false`.
+
+use helix_view::{graphics::Rect, Editor};
+use tui::buffer::Buffer as Surface;
+
+/// How many recent files and sessions are listed.
+const MAX_ENTRIES: usize = 5;
+
+pub struct Dashboard {
+    /// Saved session names, gathered once at startup.
+    sessions: Vec<String>,
+}
+
+impl Dashboard {
+    pub fn new() -> Self {
+        Self {
+            sessions: crate::session::list(),
+        }
+    }
+
+    pub fn render(&self, editor: &Editor, viewport: Rect, surface: &mut Surface) {
+        let title_style = editor.theme.get("ui.text.focus");
+        let text_style = editor.theme.get("ui.text");
+        let hint_style = editor.theme.get("ui.text.inactive");
+
+        let mut lines = vec![
+            (
+                format!("helix {}", helix_loader::VERSION_AND_GIT_HASH),
+                title_style,
+            ),
+            (String::new(), text_style),
+        ];
+
+        let files: Vec<String> = editor
+            .frecency
+            .recent_paths(None)
+            .into_iter()
+            .filter(|path| path.exists())
+            .take(MAX_ENTRIES)
+            .map(|path| {
+                helix_core::path::get_relative_path(&path)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        if !files.is_empty() {
+            lines.push(("Recent files".into(), title_style));
+            for file in files {
+                lines.push((format!("  {}", file), text_style));
+            }
+            lines.push((String::new(), text_style));
+        }
+
+        if !self.sessions.is_empty() {
+            lines.push(("Sessions".into(), title_style));
+            for session in self.sessions.iter().take(MAX_ENTRIES) {
+                lines.push((format!("  {}", session), text_style));
+            }
+            lines.push((String::new(), text_style));
+        }
+
+        lines.push(("Space f              open the file picker".into(), hint_style));
+        lines.push((":open <file>         open a file".into(), hint_style));
+        lines.push(("hx --session <name>  restore a session".into(), hint_style));
+        lines.push((":tutor               learn the basics".into(), hint_style));
+        lines.push((":quit                exit helix".into(), hint_style));
+
+        let width = lines.iter().map(|(line, _)| line.len()).max().unwrap_or(0) as u16;
+        let height = lines.len() as u16;
+        if viewport.width < width || viewport.height < height {
+            return;
+        }
+
+        let x = viewport.x + (viewport.width - width) / 2;
+        let y = viewport.y + (viewport.height - height) / 2;
+        for (row, (line, style)) in lines.into_iter().enumerate() {
+            surface.set_stringn(x, y + row as u16, line, width as usize, style);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! A persistent bottom panel listing diagnostics across all open documents,
+//! grouped by file. Toggled with `:diagnostics-panel`. The contents are
+//! rebuilt every frame, so the panel updates live as `publishDiagnostics`
+//! notifications arrive.
+
+use crate::key;
+use helix_core::diagnostic::Severity;
+use helix_view::{
+    align_view,
+    document::SCRATCH_BUFFER_NAME,
+    editor::Action,
+    graphics::Rect,
+    input::KeyEvent,
+    Align, DocumentId, Editor,
+};
+use tui::buffer::Buffer as Surface;
+
+/// Height of the panel, capped at half the editor area.
+pub const PANEL_HEIGHT: u16 = 10;
+
+enum Row {
+    File(String),
+    Diagnostic {
+        doc: DocumentId,
+        range: (usize, usize),
+        line: usize,
+        severity: Severity,
+        message: String,
+    },
+}
+
+pub struct DiagnosticsPanel {
+    /// Whether keys are routed to the panel instead of the editor. `Esc`
+    /// returns focus to the editor without closing the panel.
+    pub focused: bool,
+    /// Index into the diagnostic rows (file headers are not selectable).
+    cursor: usize,
+    /// Only diagnostics at or above this severity are listed.
+    min_severity: Severity,
+}
+
+impl DiagnosticsPanel {
+    pub fn new() -> Self {
+        Self {
+            focused: true,
+            cursor: 0,
+            min_severity: Severity::Hint,
+        }
+    }
+
+    fn rows(&self, editor: &Editor) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for (&id, doc) in &editor.documents {
+            let diagnostics: Vec<_> = doc
+                .shown_diagnostics()
+                .filter(|diagnostic| {
+                    diagnostic.severity.unwrap_or(Severity::Warning) >= self.min_severity
+                })
+                .collect();
+            if diagnostics.is_empty() {
+                continue;
+            }
+
+            let name = doc
+                .path()
+                .map(|path| {
+                    helix_core::path::get_relative_path(path)
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .unwrap_or_else(|| SCRATCH_BUFFER_NAME.into());
+            rows.push(Row::File(name));
+
+            for diagnostic in diagnostics {
+                rows.push(Row::Diagnostic {
+                    doc: id,
+                    range: (diagnostic.range.start, diagnostic.range.end),
+                    line: diagnostic.line,
+                    severity: diagnostic.severity.unwrap_or(Severity::Warning),
+                    message: diagnostic
+                        .message
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                });
+            }
+        }
+        rows
+    }
+
+    fn diagnostic_count(&self, editor: &Editor) -> usize {
+        self.rows(editor)
+            .iter()
+            .filter(|row| matches!(row, Row::Diagnostic { .. }))
+            .count()
+    }
+
+    fn set_min_severity(&mut self, severity: Severity) {
+        self.min_severity = severity;
+        self.cursor = 0;
+    }
+
+    fn jump(&self, editor: &mut Editor) {
+        let rows = self.rows(editor);
+        let entry = rows
+            .iter()
+            .filter_map(|row| match row {
+                Row::Diagnostic { doc, range, .. } => Some((*doc, *range)),
+                Row::File(_) => None,
+            })
+            .nth(self.cursor);
+        let Some((doc_id, (start, end))) = entry else { return };
+
+        editor.switch(doc_id, Action::Replace);
+        let (view, doc) = helix_view::current!(editor);
+        let text = doc.text().slice(..);
+        let len = text.len_chars();
+        doc.set_selection(
+            view.id,
+            helix_core::Selection::single(start.min(len), end.min(len)),
+        );
+        align_view(doc, view, Align::Center);
+    }
+
+    /// Handle a key while the panel has focus. Returns `true` when the
+    /// panel should be closed.
+    pub fn handle_key(&mut self, key: KeyEvent, editor: &mut Editor) -> bool {
+        match key {
+            key!('j') | key!(Down) => {
+                let count = self.diagnostic_count(editor);
+                self.cursor = (self.cursor + 1).min(count.saturating_sub(1));
+            }
+            key!('k') | key!(Up) => self.cursor = self.cursor.saturating_sub(1),
+            key!(Enter) => self.jump(editor),
+            key!('e') => self.set_min_severity(Severity::Error),
+            key!('w') => self.set_min_severity(Severity::Warning),
+            key!('i') => self.set_min_severity(Severity::Info),
+            key!('h') => self.set_min_severity(Severity::Hint),
+            key!(Esc) => self.focused = false,
+            key!('q') => return true,
+            _ => {}
+        }
+        false
+    }
+
+    pub fn render(&mut self, editor: &Editor, area: Rect, surface: &mut Surface) {
+        let theme = &editor.theme;
+        surface.clear_with(area, theme.get("ui.background"));
+
+        // top border with a title
+        let border_style = theme.get("ui.window");
+        let borders = tui::widgets::BorderType::line_symbols(tui::widgets::BorderType::Plain);
+        for x in area.left()..area.right() {
+            if let Some(cell) = surface.get_mut(x, area.y) {
+                cell.set_symbol(borders.horizontal).set_style(border_style);
+            }
+        }
+        let title = format!(" Diagnostics ({:?} and up) ", self.min_severity);
+        surface.set_stringn(
+            area.x + 1,
+            area.y,
+            title,
+            area.width.saturating_sub(2) as usize,
+            theme.get("ui.text"),
+        );
+
+        let inner = area.clip_top(1);
+        if inner.height == 0 {
+            return;
+        }
+
+        let warning = theme.get("warning");
+        let error = theme.get("error");
+        let info = theme.get("info");
+        let hint = theme.get("hint");
+        let text_style = theme.get("ui.text");
+        let selected_style = if self.focused {
+            theme.get("ui.menu.selected")
+        } else {
+            theme.get("ui.text.focus")
+        };
+
+        let rows = self.rows(editor);
+
+        // scroll so the selected diagnostic stays visible
+        let cursor_row = rows
+            .iter()
+            .scan(0, |diag_idx, row| {
+                let idx = *diag_idx;
+                if matches!(row, Row::Diagnostic { .. }) {
+                    *diag_idx += 1;
+                }
+                Some(idx)
+            })
+            .zip(rows.iter())
+            .position(|(idx, row)| {
+                idx == self.cursor && matches!(row, Row::Diagnostic { .. })
+            })
+            .unwrap_or(0);
+        let offset = cursor_row.saturating_sub(inner.height.saturating_sub(1) as usize);
+
+        let mut diag_idx = rows
+            .iter()
+            .take(offset)
+            .filter(|row| matches!(row, Row::Diagnostic { .. }))
+            .count();
+        for (y, row) in rows.iter().skip(offset).take(inner.height as usize).enumerate() {
+            let y = inner.y + y as u16;
+            match row {
+                Row::File(name) => {
+                    surface.set_stringn(inner.x, y, name, inner.width as usize, text_style);
+                }
+                Row::Diagnostic {
+                    line,
+                    severity,
+                    message,
+                    ..
+                } => {
+                    let style = if diag_idx == self.cursor {
+                        selected_style
+                    } else {
+                        match severity {
+                            Severity::Error => error,
+                            Severity::Warning => warning,
+                            Severity::Info => info,
+                            Severity::Hint => hint,
+                        }
+                    };
+                    let content = format!("  {}: {}", line + 1, message);
+                    surface.set_stringn(inner.x, y, content, inner.width as usize, style);
+                    diag_idx += 1;
+                }
+            }
+        }
+    }
+}
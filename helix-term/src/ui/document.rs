@@ -385,7 +385,8 @@ impl<'a> TextRenderer<'a> {
                     .unwrap_or_else(|| theme.get("ui.virtual.whitespace")),
             ),
             text_style,
-            draw_indent_guides: editor_config.indent_guides.render,
+            draw_indent_guides: editor_config.indent_guides.render
+                && !editor_config.screen_reader_mode,
             viewport,
             col_offset,
         }
@@ -29,7 +29,7 @@ use helix_view::{
     keyboard::{KeyCode, KeyModifiers},
     Document, Editor, Theme, View,
 };
-use std::{mem::take, num::NonZeroUsize, path::PathBuf, rc::Rc, sync::Arc};
+use std::{borrow::Cow, mem::take, num::NonZeroUsize, path::PathBuf, rc::Rc, sync::Arc};
 
 use tui::{buffer::Buffer as Surface, text::Span};
 
@@ -42,6 +42,16 @@ pub struct EditorView {
     pseudo_pending: Vec<KeyEvent>,
     pub(crate) last_insert: (commands::MappableCommand, Vec<InsertEvent>),
     pub(crate) completion: Option<Completion>,
+    /// Bottom panel listing diagnostics across all documents, see
+    /// `:diagnostics-panel`.
+    pub diagnostics_panel: Option<super::DiagnosticsPanel>,
+    /// Frame timing overlay in the top-right corner, see `:perf`.
+    pub perf_overlay: Option<super::PerfOverlay>,
+    /// On-screen display of pressed keys, see `:key-display`.
+    pub key_display: Option<super::KeyDisplay>,
+    /// Start screen shown when launched without files, dropped on the
+    /// first key press.
+    pub dashboard: Option<super::Dashboard>,
     spinners: ProgressSpinners,
 }
 
@@ -70,6 +80,10 @@ impl EditorView {
             pseudo_pending: Vec::new(),
             last_insert: (commands::MappableCommand::normal_mode, Vec::new()),
             completion: None,
+            diagnostics_panel: None,
+            perf_overlay: None,
+            key_display: None,
+            dashboard: None,
             spinners: ProgressSpinners::default(),
         }
     }
@@ -96,11 +110,17 @@ impl EditorView {
         let mut line_decorations: Vec<Box<dyn LineDecoration>> = Vec::new();
         let mut translated_positions: Vec<TranslatedPosition> = Vec::new();
 
-        if is_focused && config.cursorline {
+        if !config.screen_reader_mode {
+            if let Some(decoration) = Self::diagnostics_line_decorator(doc, theme, inner) {
+                line_decorations.push(decoration);
+            }
+        }
+
+        if is_focused && config.cursorline && !config.screen_reader_mode {
             line_decorations.push(Self::cursorline_decorator(doc, view, theme))
         }
 
-        if is_focused && config.cursorcolumn {
+        if is_focused && config.cursorcolumn && !config.screen_reader_mode {
             Self::highlight_cursorcolumn(doc, view, surface, theme, inner, &text_annotations);
         }
 
@@ -133,7 +153,9 @@ impl EditorView {
             highlights = Box::new(syntax::merge(highlights, overlay_highlights));
         }
 
-        for diagnostic in Self::doc_diagnostics_highlights(doc, theme) {
+        for diagnostic in
+            Self::doc_diagnostics_highlights(doc, theme, config.inline_diagnostics)
+        {
             // Most of the `diagnostic` Vecs are empty most of the time. Skipping
             // a merge for any empty Vec saves a significant amount of work.
             if diagnostic.is_empty() {
@@ -142,6 +164,16 @@ impl EditorView {
             highlights = Box::new(syntax::merge(highlights, diagnostic));
         }
 
+        let search_highlights = Self::doc_search_highlights(editor, doc, view, theme);
+        if !search_highlights.is_empty() {
+            highlights = Box::new(syntax::merge(highlights, search_highlights));
+        }
+
+        let conflict_highlights = Self::doc_conflict_highlights(doc, theme);
+        if !conflict_highlights.is_empty() {
+            highlights = Box::new(syntax::merge(highlights, conflict_highlights));
+        }
+
         let highlights: Box<dyn Iterator<Item = HighlightEvent>> = if is_focused {
             let highlights = syntax::merge(
                 highlights,
@@ -196,7 +228,9 @@ impl EditorView {
             &mut line_decorations,
             &mut translated_positions,
         );
-        Self::render_rulers(editor, doc, view, inner, surface, theme);
+        if !config.screen_reader_mode {
+            Self::render_rulers(editor, doc, view, inner, surface, theme);
+        }
 
         // if we're not at the edge of the screen, draw a right border
         if viewport.right() != view.area.right() {
@@ -211,16 +245,33 @@ impl EditorView {
         }
 
         Self::render_diagnostics(doc, view, inner, surface, theme);
+        if !config.screen_reader_mode {
+            Self::render_color_swatches(doc, view, inner, surface);
+        }
+        Self::render_eol_diagnostics(editor, doc, view, inner, surface, theme);
 
         let statusline_area = view
             .area
             .clip_top(view.area.height.saturating_sub(1))
             .clip_bottom(1); // -1 from bottom to remove commandline
 
-        let mut context =
-            statusline::RenderContext::new(editor, doc, view, is_focused, &self.spinners);
+        if editor.config().statusline.global {
+            // The shared statusline is rendered in `render`; draw a thin
+            // border instead between stacked splits that kept their row.
+            if view.statusline {
+                let border_style = theme.get("ui.window");
+                for x in statusline_area.left()..statusline_area.right() {
+                    surface[(x, statusline_area.y)]
+                        .set_symbol(tui::symbols::line::HORIZONTAL)
+                        .set_style(border_style);
+                }
+            }
+        } else {
+            let mut context =
+                statusline::RenderContext::new(editor, doc, view, is_focused, &self.spinners);
 
-        statusline::render(&mut context, statusline_area, surface);
+            statusline::render(&mut context, statusline_area, surface);
+        }
     }
 
     pub fn render_rulers(
@@ -328,12 +379,104 @@ impl EditorView {
         }
     }
 
+    /// Get highlight spans for the viewport matches of the active search
+    /// pattern, see [`Editor::search_matches`].
+    pub fn doc_search_highlights(
+        editor: &Editor,
+        doc: &Document,
+        view: &View,
+        theme: &Theme,
+    ) -> Vec<(usize, std::ops::Range<usize>)> {
+        let regex = match &editor.search_matches {
+            Some(regex) => regex,
+            None => return Vec::new(),
+        };
+        let scope = match theme
+            .find_scope_index_exact("ui.search.match")
+            .or_else(|| theme.find_scope_index_exact("ui.selection"))
+        {
+            Some(scope) => scope,
+            None => return Vec::new(),
+        };
+
+        let text = doc.text().slice(..);
+        let row = text.char_to_line(view.offset.anchor.min(text.len_chars()));
+
+        // Calculate viewport byte ranges:
+        // Saturating subs to make it inclusive zero indexing.
+        let last_line = text.len_lines().saturating_sub(1);
+        let last_visible_line = (row + view.inner_height())
+            .saturating_sub(1)
+            .min(last_line);
+        let start = text.line_to_byte(row.min(last_line));
+        let end = text.line_to_byte(last_visible_line + 1);
+
+        let wrapped_scope = theme
+            .find_scope_index_exact("ui.search.wrapped")
+            .unwrap_or(scope);
+
+        let fragment: Cow<str> = text.byte_slice(start..end).into();
+        regex
+            .find_iter(&fragment)
+            .map(|mat| {
+                let range = text.byte_to_char(start + mat.start())
+                    ..text.byte_to_char(start + mat.end());
+                if editor.search_wrapped_match.as_ref() == Some(&range) {
+                    (wrapped_scope, range)
+                } else {
+                    (scope, range)
+                }
+            })
+            .collect()
+    }
+
+    /// Get highlight spans for the sides of git merge conflicts in the
+    /// document, see [`helix_core::conflict`].
+    pub fn doc_conflict_highlights(
+        doc: &Document,
+        theme: &Theme,
+    ) -> Vec<(usize, std::ops::Range<usize>)> {
+        let text = doc.text().slice(..);
+        let conflicts = helix_core::conflict::find_conflicts(text);
+        if conflicts.is_empty() {
+            return Vec::new();
+        }
+
+        let get_scope_of = |scope, fallback| {
+            theme
+                .find_scope_index_exact(scope)
+                .or_else(|| theme.find_scope_index_exact(fallback))
+        };
+        let ours = get_scope_of("merge.ours", "diff.plus");
+        let base = get_scope_of("merge.base", "diff.delta");
+        let theirs = get_scope_of("merge.theirs", "diff.minus");
+
+        let mut spans = Vec::new();
+        for conflict in conflicts {
+            if let Some(scope) = ours {
+                spans.push((scope, conflict.ours(text)));
+            }
+            if let (Some(scope), Some(range)) = (base, conflict.base(text)) {
+                spans.push((scope, range));
+            }
+            if let Some(scope) = theirs {
+                spans.push((scope, conflict.theirs(text)));
+            }
+        }
+        spans
+    }
+
     /// Get highlight spans for document diagnostics
     pub fn doc_diagnostics_highlights(
         doc: &Document,
         theme: &Theme,
+        min_severity: helix_core::diagnostic::Severity,
     ) -> [Vec<(usize, std::ops::Range<usize>)>; 5] {
         use helix_core::diagnostic::Severity;
+
+        if !doc.diagnostics_enabled {
+            return Default::default();
+        }
         let get_scope_of = |scope| {
             theme
             .find_scope_index_exact(scope)
@@ -360,6 +503,9 @@ impl EditorView {
         let mut error_vec = Vec::new();
 
         for diagnostic in doc.diagnostics() {
+            if diagnostic.severity.unwrap_or_default() < min_severity {
+                continue;
+            }
             // Separate diagnostics into different Vecs by severity.
             let (vec, scope) = match diagnostic.severity {
                 Some(Severity::Info) => (&mut info_vec, info),
@@ -695,6 +841,179 @@ impl EditorView {
         );
     }
 
+    /// Render a colored swatch at the end of every visible line containing
+    /// hex or `rgb()` color literals, as immediate feedback when editing
+    /// themes or CSS.
+    pub fn render_color_swatches(doc: &Document, view: &View, viewport: Rect, surface: &mut Surface) {
+        use helix_core::regex::Regex;
+        use once_cell::sync::Lazy;
+
+        static COLOR_REGEX: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(
+                r"#(?:[0-9a-fA-F]{8}|[0-9a-fA-F]{6}|[0-9a-fA-F]{3})\b|rgba?\(\s*\d{1,3}\s*,\s*\d{1,3}\s*,\s*\d{1,3}",
+            )
+            .unwrap()
+        });
+
+        let text = doc.text().slice(..);
+        let first_line = text.char_to_line(view.offset.anchor.min(text.len_chars()));
+        let last_line = (first_line + viewport.height as usize).min(text.len_lines());
+        for line in first_line..last_line {
+            // minified CSS and the like: not worth scanning, and a single
+            // line can't show this many swatches anyway
+            if text.line(line).len_bytes() > 4096 {
+                continue;
+            }
+            let line_text = Cow::from(text.line(line));
+            let mut colors = COLOR_REGEX
+                .find_iter(&line_text)
+                .filter_map(|m| parse_color_literal(m.as_str()))
+                .peekable();
+            if colors.peek().is_none() {
+                continue;
+            }
+
+            let line_end = helix_core::line_ending::line_end_char_index(&text, line);
+            let pos = match view.screen_coords_at_pos(doc, text, line_end) {
+                Some(pos) => pos,
+                None => continue,
+            };
+            let mut x = viewport.x + (pos.col as u16).saturating_add(2);
+            let y = viewport.y + pos.row as u16;
+            for color in colors {
+                if x >= viewport.right() {
+                    break;
+                }
+                surface.set_stringn(x, y, "■", 1, Style::default().fg(color));
+                x = x.saturating_add(2);
+            }
+        }
+    }
+
+    /// Render the first diagnostic of each visible line as truncated virtual
+    /// text after the line's end, colored by severity. Only severities at or
+    /// above `editor.end-of-line-diagnostics` are shown.
+    pub fn render_eol_diagnostics(
+        editor: &Editor,
+        doc: &Document,
+        view: &View,
+        viewport: Rect,
+        surface: &mut Surface,
+        theme: &Theme,
+    ) {
+        use helix_core::diagnostic::Severity;
+
+        if !doc.diagnostics_enabled {
+            return;
+        }
+
+        let min_severity = match editor.config().end_of_line_diagnostics {
+            Some(severity) => severity,
+            None => return,
+        };
+
+        let warning = theme.get("warning");
+        let error = theme.get("error");
+        let info = theme.get("info");
+        let hint = theme.get("hint");
+
+        let text = doc.text().slice(..);
+        let mut last_line = None;
+        for diagnostic in doc.shown_diagnostics() {
+            if diagnostic.severity.unwrap_or(Severity::Warning) < min_severity {
+                continue;
+            }
+            // only the first diagnostic of each line
+            if last_line == Some(diagnostic.line) {
+                continue;
+            }
+            last_line = Some(diagnostic.line);
+
+            let line_end = helix_core::line_ending::line_end_char_index(&text, diagnostic.line);
+            let pos = match view.screen_coords_at_pos(doc, text, line_end) {
+                Some(pos) => pos,
+                None => continue,
+            };
+
+            // a gap, then the message truncated to the remaining width
+            let x = viewport.x + (pos.col as u16).saturating_add(2);
+            let y = viewport.y + pos.row as u16;
+            let width = viewport.right().saturating_sub(x);
+            if width == 0 {
+                continue;
+            }
+
+            let style = match diagnostic.severity {
+                Some(Severity::Error) => error,
+                Some(Severity::Warning) | None => warning,
+                Some(Severity::Info) => info,
+                Some(Severity::Hint) => hint,
+            };
+            let message = format!("● {}", diagnostic.message.lines().next().unwrap_or_default());
+            surface.set_stringn(x, y, message, width as usize, style);
+        }
+    }
+
+    /// Tint the background of whole lines that contain diagnostics, using
+    /// the `diagnostic.<severity>.line` theme scopes with `diagnostic.line`
+    /// as fallback. Themes that define none of them opt out entirely, and
+    /// lines with several diagnostics use the most severe one.
+    pub fn diagnostics_line_decorator(
+        doc: &Document,
+        theme: &Theme,
+        viewport: Rect,
+    ) -> Option<Box<dyn LineDecoration>> {
+        use helix_core::diagnostic::Severity;
+
+        if !doc.diagnostics_enabled {
+            return None;
+        }
+
+        let fallback = theme.try_get_exact("diagnostic.line");
+        let styles = [
+            theme.try_get_exact("diagnostic.hint.line").or(fallback),
+            theme.try_get_exact("diagnostic.info.line").or(fallback),
+            theme.try_get_exact("diagnostic.warning.line").or(fallback),
+            theme.try_get_exact("diagnostic.error.line").or(fallback),
+        ];
+        if styles.iter().all(Option::is_none) {
+            return None;
+        }
+
+        // diagnostics are sorted by position, so lines come out ascending
+        let mut lines: Vec<(usize, Severity)> = Vec::new();
+        for diagnostic in doc.diagnostics() {
+            let severity = diagnostic.severity.unwrap_or_default();
+            match lines.last_mut() {
+                Some((line, max_severity)) if *line == diagnostic.line => {
+                    *max_severity = severity.max(*max_severity);
+                }
+                _ => lines.push((diagnostic.line, severity)),
+            }
+        }
+        if lines.is_empty() {
+            return None;
+        }
+
+        let line_decoration = move |renderer: &mut TextRenderer, pos: LinePos| {
+            let Ok(index) = lines.binary_search_by_key(&pos.doc_line, |&(line, _)| line) else {
+                return;
+            };
+            let style = match lines[index].1 {
+                Severity::Hint => styles[0],
+                Severity::Info => styles[1],
+                Severity::Warning => styles[2],
+                Severity::Error => styles[3],
+            };
+            if let Some(style) = style {
+                let area = Rect::new(viewport.x, viewport.y + pos.visual_line, viewport.width, 1);
+                renderer.surface.set_style(area, style);
+            }
+        };
+
+        Some(Box::new(line_decoration))
+    }
+
     /// Apply the highlighting on the lines where a cursor is active
     pub fn cursorline_decorator(
         doc: &Document,
@@ -1035,6 +1354,12 @@ impl EditorView {
         }
 
         let config = cxt.editor.config();
+        // The terminal may still deliver mouse events while capture is being
+        // toggled off (or always, on some platforms), so check the setting at
+        // runtime rather than relying on capture alone.
+        if !config.mouse {
+            return EventResult::Ignored(None);
+        }
         let MouseEvent {
             kind,
             row,
@@ -1109,9 +1434,31 @@ impl EditorView {
 
                 let pos = match view.pos_at_screen_coords(doc, row, column, true) {
                     Some(pos) => pos,
-                    None => return EventResult::Ignored(None),
+                    None => {
+                        // Dragging past the top or bottom edge: scroll a line
+                        // in that direction so the selection can keep growing
+                        // beyond the visible area.
+                        let area = view.inner_area(doc);
+                        let (direction, clamped_row) = if row < area.top() {
+                            (Direction::Backward, area.top())
+                        } else if row >= area.bottom() {
+                            (Direction::Forward, area.bottom().saturating_sub(1))
+                        } else {
+                            return EventResult::Ignored(None);
+                        };
+                        commands::scroll(cxt, 1, direction);
+
+                        let (view, doc) = current!(cxt.editor);
+                        let clamped_column =
+                            column.clamp(area.left(), area.right().saturating_sub(1));
+                        match view.pos_at_screen_coords(doc, clamped_row, clamped_column, true) {
+                            Some(pos) => pos,
+                            None => return EventResult::Ignored(None),
+                        }
+                    }
                 };
 
+                let (view, doc) = current!(cxt.editor);
                 let mut selection = doc.selection(view.id).clone();
                 let primary = selection.primary_mut();
                 *primary = primary.put_cursor(doc.text().slice(..), pos, true);
@@ -1235,6 +1582,11 @@ impl Component for EditorView {
 
         match event {
             Event::Paste(contents) => {
+                // a paste invalidates whatever the completion menu was
+                // filtering on, so close it before applying the text
+                if self.completion.is_some() {
+                    self.clear_completion(cx.editor);
+                }
                 cx.count = cx.editor.count;
                 commands::paste_bracketed_value(&mut cx, contents.clone());
                 cx.editor.count = None;
@@ -1261,13 +1613,62 @@ impl Component for EditorView {
                 cx.editor.reset_idle_timer();
                 canonicalize_key(&mut key);
 
-                // clear status
-                cx.editor.status_msg = None;
+                // any key press dismisses the start screen
+                self.dashboard = None;
+
+                // dismiss the current status message, revealing the next queued one
+                cx.editor.dismiss_status();
+
+                // route keys to the diagnostics panel while it has focus
+                if let Some(panel) = &mut self.diagnostics_panel {
+                    if panel.focused {
+                        if panel.handle_key(key, cx.editor) {
+                            self.diagnostics_panel = None;
+                        }
+                        return EventResult::Consumed(None);
+                    }
+                }
 
                 let mode = cx.editor.mode();
                 let (view, _) = current!(cx.editor);
                 let focus = view.id;
 
+                // in the command line buffer opened with `Ctrl-o` at the
+                // prompt, `Enter` in normal mode submits the cursor line
+                if let Some((doc_id, register)) = cx.editor.command_line_doc {
+                    let (view, doc) = current!(cx.editor);
+                    if doc.id() == doc_id && mode == Mode::Normal && key == key!(Enter) {
+                        let text = doc.text().slice(..);
+                        let cursor = doc.selection(view.id).primary().cursor(text);
+                        let line = text.line(text.char_to_line(cursor));
+                        let line = line.to_string().trim_end().to_string();
+
+                        cx.editor.command_line_doc = None;
+                        let _ = cx.editor.close_document(doc_id, true);
+                        if line.is_empty() {
+                            return EventResult::Consumed(None);
+                        }
+                        cx.editor.registers.push(register, line.clone());
+
+                        return EventResult::Consumed(Some(Box::new(
+                            move |_compositor, cx: &mut Context| match register {
+                                ':' => commands::typed::execute_command_line(cx, &line),
+                                _ => {
+                                    let mut cx = commands::Context {
+                                        editor: cx.editor,
+                                        count: None,
+                                        register: None,
+                                        callback: None,
+                                        on_next_key_callback: None,
+                                        jobs: cx.jobs,
+                                    };
+                                    commands::MappableCommand::search_next.execute(&mut cx);
+                                }
+                            },
+                        )));
+                    }
+                }
+
                 if let Some(on_next_key) = self.on_next_key.take() {
                     // if there's a command waiting input, do that first
                     on_next_key(&mut cx, key);
@@ -1311,6 +1712,15 @@ impl Component for EditorView {
                                         );
                                     }
                                 }
+
+                                // resolve the newly selected item right away so
+                                // the documentation panel tracks the selection
+                                // instead of waiting for the idle timeout
+                                if consumed {
+                                    if let Some(completion) = &mut self.completion {
+                                        completion.ensure_item_resolved(&mut cx);
+                                    }
+                                }
                             }
 
                             // if completion didn't take the event, we pass it onto commands
@@ -1399,10 +1809,33 @@ impl Component for EditorView {
         if use_bufferline {
             editor_area = editor_area.clip_top(1);
         }
+        let global_statusline = config.statusline.global;
+        if global_statusline {
+            // -1 for the shared statusline
+            editor_area = editor_area.clip_bottom(1);
+        }
+        let panel_area = self.diagnostics_panel.is_some().then(|| {
+            let height = super::diagnostics_panel::PANEL_HEIGHT.min(editor_area.height / 2);
+            let panel_area = Rect::new(
+                editor_area.x,
+                editor_area.y + editor_area.height - height,
+                editor_area.width,
+                height,
+            );
+            editor_area = editor_area.clip_bottom(height);
+            panel_area
+        });
 
         // if the terminal size suddenly changed, we need to trigger a resize
         cx.editor.resize(editor_area);
 
+        // Views at the bottom of the editor area don't need their own
+        // statusline row when the global statusline is in use.
+        let editor_bottom = editor_area.y + editor_area.height;
+        for (view, _) in cx.editor.tree.views_mut() {
+            view.statusline = !(global_statusline && view.area.bottom() == editor_bottom);
+        }
+
         if use_bufferline {
             Self::render_bufferline(cx.editor, area.with_height(1), surface);
         }
@@ -1412,6 +1845,35 @@ impl Component for EditorView {
             self.render_view(cx.editor, doc, view, area, surface, is_focused);
         }
 
+        if global_statusline {
+            let statusline_area = Rect::new(area.x, editor_bottom, area.width, 1);
+            let (view, doc) = current_ref!(cx.editor);
+            let mut context =
+                statusline::RenderContext::new(cx.editor, doc, view, true, &self.spinners);
+            statusline::render(&mut context, statusline_area, surface);
+        }
+
+        if let (Some(panel), Some(panel_area)) = (&mut self.diagnostics_panel, panel_area) {
+            panel.render(cx.editor, panel_area, surface);
+        }
+
+        if let Some(overlay) = &self.perf_overlay {
+            overlay.render(cx.editor, editor_area, surface);
+        }
+
+        if let Some(display) = &mut self.key_display {
+            display.render(cx.editor, editor_area, surface);
+        }
+
+        if let Some(dashboard) = &self.dashboard {
+            // only over an untouched scratch buffer; opening or editing a
+            // file makes it disappear
+            let doc = current_ref!(cx.editor).1;
+            if doc.path().is_none() && doc.text().len_chars() <= 1 {
+                dashboard.render(cx.editor, editor_area, surface);
+            }
+        }
+
         if config.auto_info {
             if let Some(mut info) = cx.editor.autoinfo.take() {
                 info.render(area, surface, cx);
@@ -1423,10 +1885,11 @@ impl Component for EditorView {
         let mut status_msg_width = 0;
 
         // render status msg
-        if let Some((status_msg, severity)) = &cx.editor.status_msg {
-            status_msg_width = status_msg.width();
+        cx.editor.expire_status();
+        if let Some(status_msg) = cx.editor.status_msgs.front() {
+            status_msg_width = status_msg.message.width();
             use helix_view::editor::Severity;
-            let style = if *severity == Severity::Error {
+            let style = if status_msg.severity == Severity::Error {
                 cx.editor.theme.get("error")
             } else {
                 cx.editor.theme.get("ui.text")
@@ -1435,7 +1898,7 @@ impl Component for EditorView {
             surface.set_string(
                 area.x,
                 area.y + area.height.saturating_sub(1),
-                status_msg,
+                &status_msg.message,
                 style,
             );
         }
@@ -1492,6 +1955,34 @@ impl Component for EditorView {
     }
 }
 
+/// Parse a `#RGB`/`#RRGGBB`/`#RRGGBBAA` or `rgb(r, g, b` literal matched
+/// by the swatch regex; alpha is ignored.
+fn parse_color_literal(literal: &str) -> Option<Color> {
+    if let Some(hex) = literal.strip_prefix('#') {
+        let channel = |at: usize| u8::from_str_radix(hex.get(at..at + 2)?, 16).ok();
+        return match hex.len() {
+            3 => {
+                let channel = |at: usize| {
+                    let digit = u8::from_str_radix(hex.get(at..at + 1)?, 16).ok()?;
+                    Some(digit * 16 + digit)
+                };
+                Some(Color::Rgb(channel(0)?, channel(1)?, channel(2)?))
+            }
+            6 | 8 => Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?)),
+            _ => None,
+        };
+    }
+
+    let channels = literal.split_once('(')?.1;
+    let mut channels = channels
+        .split(',')
+        .map(|channel| channel.trim().parse::<u8>());
+    match (channels.next(), channels.next(), channels.next()) {
+        (Some(Ok(r)), Some(Ok(g)), Some(Ok(b))) => Some(Color::Rgb(r, g, b)),
+        _ => None,
+    }
+}
+
 fn canonicalize_key(key: &mut KeyEvent) {
     if let KeyEvent {
         code: KeyCode::Char(_),
@@ -0,0 +1,158 @@
+//! Image support for the file picker preview: header sniffing for common
+//! formats so a text fallback can show dimensions, and thumbnail rendering
+//! through the kitty graphics protocol where the terminal supports it.
+
+use std::io::Read;
+use std::path::Path;
+
+use helix_view::graphics::Rect;
+
+/// How much of a file is read when sniffing; JPEG dimensions live in a
+/// frame header that can sit behind sizeable metadata segments.
+const SNIFF_LEN: u64 = 64 * 1024;
+
+pub struct ImageInfo {
+    pub format: &'static str,
+    pub dimensions: Option<(u32, u32)>,
+}
+
+impl ImageInfo {
+    pub fn describe(&self) -> String {
+        match self.dimensions {
+            Some((width, height)) => {
+                format!("<{} image, {}x{}>", self.format, width, height)
+            }
+            None => format!("<{} image>", self.format),
+        }
+    }
+}
+
+/// Sniffs the format and dimensions of an image from its header, without
+/// decoding pixel data or pulling in an image crate.
+pub fn detect(path: &Path) -> Option<ImageInfo> {
+    let mut header = Vec::new();
+    std::fs::File::open(path)
+        .ok()?
+        .take(SNIFF_LEN)
+        .read_to_end(&mut header)
+        .ok()?;
+
+    let be16 = |at: usize| Some(u16::from_be_bytes(header.get(at..at + 2)?.try_into().ok()?));
+    let be32 = |at: usize| Some(u32::from_be_bytes(header.get(at..at + 4)?.try_into().ok()?));
+    let le16 = |at: usize| Some(u16::from_le_bytes(header.get(at..at + 2)?.try_into().ok()?));
+    let le32 = |at: usize| Some(u32::from_le_bytes(header.get(at..at + 4)?.try_into().ok()?));
+
+    if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some(ImageInfo {
+            format: "PNG",
+            dimensions: be32(16).zip(be32(20)),
+        });
+    }
+    if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+        return Some(ImageInfo {
+            format: "GIF",
+            dimensions: le16(6).zip(le16(8)).map(|(w, h)| (w as u32, h as u32)),
+        });
+    }
+    if header.starts_with(b"BM") {
+        return Some(ImageInfo {
+            format: "BMP",
+            dimensions: le32(18)
+                .zip(le32(22))
+                .map(|(w, h)| (w, (h as i32).unsigned_abs())),
+        });
+    }
+    if header.starts_with(b"RIFF") && header.get(8..12) == Some(b"WEBP") {
+        // only the extended (VP8X) header stores dimensions up front
+        let dimensions = (header.get(12..16) == Some(b"VP8X"))
+            .then(|| {
+                let u24 = |at: usize| {
+                    let bytes = header.get(at..at + 3)?;
+                    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]) + 1)
+                };
+                u24(24).zip(u24(27))
+            })
+            .flatten();
+        return Some(ImageInfo {
+            format: "WebP",
+            dimensions,
+        });
+    }
+    if header.starts_with(b"\xff\xd8") {
+        // walk the segment list until a start-of-frame marker
+        let mut at = 2;
+        while at + 9 < header.len() {
+            if header[at] != 0xff {
+                break;
+            }
+            let marker = header[at + 1];
+            if matches!(marker, 0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc)) {
+                return Some(ImageInfo {
+                    format: "JPEG",
+                    dimensions: be16(at + 5)
+                        .zip(be16(at + 7))
+                        .map(|(w, h)| (w as u32, h as u32)),
+                });
+            }
+            let len = be16(at + 2)? as usize;
+            at += 2 + len;
+        }
+        return Some(ImageInfo {
+            format: "JPEG",
+            dimensions: None,
+        });
+    }
+
+    None
+}
+
+/// Whether the terminal claims support for the kitty graphics protocol.
+pub fn kitty_supported() -> bool {
+    std::env::var("KITTY_WINDOW_ID").is_ok()
+        || std::env::var("TERM").map_or(false, |term| term.contains("kitty"))
+}
+
+/// Draws `path` (which must be a PNG, the only format kitty reads from a
+/// file directly) as a thumbnail filling as much of `area` as the image's
+/// aspect ratio allows. The escape sequence goes straight to the terminal:
+/// the surface diff the backend flushes afterwards only repaints cells,
+/// which kitty composites the image over.
+pub fn kitty_draw(path: &Path, dimensions: (u32, u32), area: Rect) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let (width, height) = dimensions;
+    if width == 0 || height == 0 || area.width == 0 || area.height == 0 {
+        return Ok(());
+    }
+    // terminal cells are roughly twice as tall as wide; constrain whichever
+    // axis overflows the area and let kitty keep the aspect ratio
+    let rows_for_full_width = (height * u32::from(area.width)) / (2 * width);
+    let size = if rows_for_full_width <= u32::from(area.height) {
+        format!("c={}", area.width)
+    } else {
+        format!("r={}", area.height)
+    };
+
+    let payload = helix_view::base64::encode(path.to_string_lossy().as_bytes());
+    let mut out = std::io::stdout().lock();
+    // delete previous placements, park the cursor on the area, transmit
+    // the file path (t=f) and restore the cursor
+    write!(
+        out,
+        "\x1b_Ga=d,d=a\x1b\\\x1b[s\x1b[{};{}H\x1b_Ga=T,f=100,t=f,{};{}\x1b\\\x1b[u",
+        area.y + 1,
+        area.x + 1,
+        size,
+        payload
+    )?;
+    out.flush()
+}
+
+/// Removes any thumbnail left on screen, for when the picker closes.
+pub fn kitty_clear() {
+    use std::io::Write;
+
+    let mut out = std::io::stdout().lock();
+    let _ = write!(out, "\x1b_Ga=d,d=a\x1b\\");
+    let _ = out.flush();
+}
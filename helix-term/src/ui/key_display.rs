@@ -0,0 +1,72 @@
+//! An overlay above the statusline showing the keys being pressed, for
+//! screencasts, pairing and teaching. Toggled with `:key-display`. Keys fade
+//! out after a short while: recent presses are drawn with the regular text
+//! style, older ones dimmed, and expired ones dropped on the next redraw.
+
+use std::time::{Duration, Instant};
+
+use helix_view::{graphics::Rect, input::KeyEvent, Editor};
+use tui::buffer::Buffer as Surface;
+
+/// How long a key stays on screen.
+const TTL: Duration = Duration::from_millis(2000);
+/// Keys older than this are drawn dimmed.
+const FADE_AFTER: Duration = Duration::from_millis(1200);
+/// How many keys are shown at once.
+const MAX_KEYS: usize = 8;
+
+pub struct KeyDisplay {
+    keys: Vec<(Instant, String)>,
+}
+
+impl KeyDisplay {
+    pub fn new() -> Self {
+        Self { keys: Vec::new() }
+    }
+
+    /// Record a pressed key. Called from the application's event handling so
+    /// keys show up regardless of which compositor layer consumes them.
+    pub fn push(&mut self, key: KeyEvent) {
+        self.keys.push((Instant::now(), key.to_string()));
+        if self.keys.len() > MAX_KEYS {
+            self.keys.remove(0);
+        }
+    }
+
+    pub fn render(&mut self, editor: &Editor, viewport: Rect, surface: &mut Surface) {
+        self.keys.retain(|(pressed_at, _)| pressed_at.elapsed() < TTL);
+        if self.keys.is_empty() {
+            return;
+        }
+
+        let theme = &editor.theme;
+        let fresh_style = theme.get("ui.text");
+        let faded_style = theme.get("ui.text.inactive");
+
+        let width: usize = self.keys.iter().map(|(_, key)| key.len() + 1).sum::<usize>() + 1;
+        let width = (width as u16).min(viewport.width);
+        let area = Rect::new(
+            viewport.right().saturating_sub(width),
+            viewport.bottom().saturating_sub(1),
+            width,
+            1,
+        );
+        surface.clear_with(area, theme.get("ui.popup"));
+
+        let mut x = area.x + 1;
+        for (pressed_at, key) in &self.keys {
+            let style = if pressed_at.elapsed() > FADE_AFTER {
+                faded_style
+            } else {
+                fresh_style
+            };
+            x = surface
+                .set_stringn(x, area.y, key, (area.right() - x) as usize, style)
+                .0
+                + 1;
+            if x >= area.right() {
+                break;
+            }
+        }
+    }
+}
@@ -1,12 +1,18 @@
 mod completion;
+mod confirm;
+mod dashboard;
+mod diagnostics_panel;
 mod document;
 pub(crate) mod editor;
 mod fuzzy_match;
+mod image;
 mod info;
+mod key_display;
 pub mod lsp;
 mod markdown;
 pub mod menu;
 pub mod overlay;
+mod perf;
 mod picker;
 pub mod popup;
 mod prompt;
@@ -14,13 +20,19 @@ mod spinner;
 mod statusline;
 mod text;
 
+use crate::alt;
 use crate::compositor::{Component, Compositor};
 use crate::filter_picker_entry;
 use crate::job::{self, Callback};
 pub use completion::{Completion, CompletionItem};
+pub use confirm::Confirm;
+pub use dashboard::Dashboard;
+pub use diagnostics_panel::DiagnosticsPanel;
 pub use editor::EditorView;
+pub use key_display::KeyDisplay;
 pub use markdown::Markdown;
 pub use menu::Menu;
+pub use perf::PerfOverlay;
 pub use picker::{DynamicPicker, FileLocation, Picker};
 pub use popup::Popup;
 pub use prompt::{Prompt, PromptEvent};
@@ -31,7 +43,9 @@ use helix_core::regex::Regex;
 use helix_core::regex::RegexBuilder;
 use helix_view::Editor;
 
+use std::cell::Cell;
 use std::path::PathBuf;
+use std::rc::Rc;
 
 pub fn prompt(
     cx: &mut crate::commands::Context,
@@ -59,6 +73,40 @@ pub fn prompt_with_input(
     cx.push_layer(Box::new(prompt));
 }
 
+/// The `[editor.search]` options in effect for a single regex prompt. They
+/// start out at the configured values and can be flipped while typing, see
+/// the `with_key_handler` toggles in [`regex_prompt`].
+#[derive(Clone, Copy)]
+struct SearchOptions {
+    smart_case: bool,
+    literal: bool,
+    whole_word: bool,
+}
+
+impl SearchOptions {
+    fn build_regex(&self, input: &str) -> Result<Regex, helix_core::regex::Error> {
+        let case_insensitive = if self.smart_case {
+            !input.chars().any(char::is_uppercase)
+        } else {
+            false
+        };
+
+        let mut pattern = if self.literal {
+            helix_core::regex::escape(input)
+        } else {
+            input.into()
+        };
+        if self.whole_word {
+            pattern = format!(r"\b(?:{})\b", pattern);
+        }
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(case_insensitive)
+            .multi_line(true)
+            .build()
+    }
+}
+
 pub fn regex_prompt(
     cx: &mut crate::commands::Context,
     prompt: std::borrow::Cow<'static, str>,
@@ -72,6 +120,13 @@ pub fn regex_prompt(
     let offset_snapshot = view.offset;
     let config = cx.editor.config();
 
+    let options = Rc::new(Cell::new(SearchOptions {
+        smart_case: config.search.smart_case,
+        literal: config.search.literal,
+        whole_word: config.search.whole_word,
+    }));
+    let prompt_options = Rc::clone(&options);
+
     let mut prompt = Prompt::new(
         prompt,
         history_register,
@@ -89,17 +144,7 @@ pub fn regex_prompt(
                         return;
                     }
 
-                    let case_insensitive = if config.search.smart_case {
-                        !input.chars().any(char::is_uppercase)
-                    } else {
-                        false
-                    };
-
-                    match RegexBuilder::new(input)
-                        .case_insensitive(case_insensitive)
-                        .multi_line(true)
-                        .build()
-                    {
+                    match prompt_options.get().build_regex(input) {
                         Ok(regex) => {
                             let (view, doc) = current!(cx.editor);
 
@@ -151,19 +196,35 @@ pub fn regex_prompt(
                 }
             }
         },
-    );
+    )
+    .with_key_handler(move |cx, key| {
+        let mut opts = options.get();
+        match key {
+            alt!('c') => opts.smart_case = !opts.smart_case,
+            alt!('l') => opts.literal = !opts.literal,
+            alt!('w') => opts.whole_word = !opts.whole_word,
+            _ => return false,
+        }
+        options.set(opts);
+        cx.editor.set_status(format!(
+            "smart-case: {}, literal: {}, whole-word: {}",
+            opts.smart_case, opts.literal, opts.whole_word
+        ));
+        true
+    });
     // Calculate initial completion
     prompt.recalculate_completion(cx.editor);
     // prompt
     cx.push_layer(Box::new(prompt));
 }
 
-pub fn file_picker(root: PathBuf, config: &helix_view::editor::Config) -> Picker<PathBuf> {
+pub fn file_picker(root: PathBuf, editor: &Editor) -> Picker<PathBuf> {
     use ignore::{types::TypesBuilder, WalkBuilder};
     use std::time::Instant;
 
     let now = Instant::now();
 
+    let config = editor.config();
     let dedup_symlinks = config.file_picker.deduplicate_links;
     let absolute_root = root.canonicalize().unwrap_or_else(|_| root.clone());
 
@@ -214,6 +275,17 @@ pub fn file_picker(root: PathBuf, config: &helix_view::editor::Config) -> Picker
         files.take(MAX).collect()
     };
     files.sort();
+    // Sort is stable, so ties keep the path ordering from above. Frecency
+    // scores are keyed by canonicalized paths; rebase onto the canonical
+    // root instead of canonicalizing every file individually.
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
+    files.sort_by_cached_key(|path| {
+        let canonical = path
+            .strip_prefix(&root)
+            .map(|suffix| canonical_root.join(suffix))
+            .unwrap_or_else(|_| path.clone());
+        std::cmp::Reverse(editor.frecency.score(&canonical))
+    });
 
     log::debug!("file_picker init {:?}", Instant::now().duration_since(now));
 
@@ -228,6 +300,7 @@ pub fn file_picker(root: PathBuf, config: &helix_view::editor::Config) -> Picker
         }
     })
     .with_preview(|_editor, path| Some((path.clone().into(), None)))
+    .with_file_operations(|path| Some(path.clone()), |path| path)
 }
 
 pub mod completers {
@@ -387,6 +460,29 @@ pub mod completers {
             .collect()
     }
 
+    pub fn register(editor: &Editor, input: &str) -> Vec<Completion> {
+        let matcher = Matcher::default();
+
+        let mut matches: Vec<_> = editor
+            .registers
+            .inner()
+            .keys()
+            .filter_map(|name| {
+                let name = name.to_string();
+                matcher.fuzzy_match(&name, input).map(|score| (name, score))
+            })
+            .collect();
+
+        matches.sort_unstable_by(|(name1, score1), (name2, score2)| {
+            (Reverse(*score1), name1).cmp(&(Reverse(*score2), name2))
+        });
+
+        matches
+            .into_iter()
+            .map(|(name, _score)| ((0..), name.into()))
+            .collect()
+    }
+
     pub fn lsp_workspace_command(editor: &Editor, input: &str) -> Vec<Completion> {
         let matcher = Matcher::default();
 
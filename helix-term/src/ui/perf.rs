@@ -0,0 +1,66 @@
+//! A small overlay in the top-right corner of the editor area showing how
+//! long recent operations took: rendering a frame, handling a terminal
+//! event, tree-sitter reparses and language server round-trips. Toggled
+//! with `:perf`; the timings come from `helix_core::perf`.
+
+use std::time::Duration;
+
+use helix_core::perf::{self, Metric};
+use helix_view::{graphics::Rect, Editor};
+use tui::buffer::Buffer as Surface;
+
+const ROWS: &[(Metric, &str)] = &[
+    (Metric::Render, "render"),
+    (Metric::Event, "event"),
+    (Metric::Parse, "parse"),
+    (Metric::LspRequest, "lsp"),
+];
+
+pub struct PerfOverlay;
+
+impl PerfOverlay {
+    pub fn render(&self, editor: &Editor, viewport: Rect, surface: &mut Surface) {
+        let width = 34u16;
+        let height = ROWS.len() as u16 + 1;
+        if viewport.width < width || viewport.height < height {
+            return;
+        }
+        let area = Rect::new(viewport.right() - width, viewport.y, width, height);
+
+        let theme = &editor.theme;
+        surface.clear_with(area, theme.get("ui.popup"));
+        let text_style = theme.get("ui.text");
+        let inner_width = area.width.saturating_sub(2) as usize;
+
+        let header = format!("{:8}{:>8}{:>8}{:>8}", "", "last", "avg", "max");
+        surface.set_stringn(area.x + 1, area.y, header, inner_width, text_style);
+
+        for (i, (metric, name)) in ROWS.iter().enumerate() {
+            let samples = perf::samples(*metric);
+            let line = match samples.last() {
+                None => format!("{:8}{:>8}{:>8}{:>8}", name, "-", "-", "-"),
+                Some(last) => {
+                    let avg = samples.iter().sum::<Duration>() / samples.len() as u32;
+                    let max = samples.iter().max().unwrap();
+                    format!(
+                        "{:8}{:>8}{:>8}{:>8}",
+                        name,
+                        format_duration(*last),
+                        format_duration(avg),
+                        format_duration(*max)
+                    )
+                }
+            };
+            surface.set_stringn(area.x + 1, area.y + 1 + i as u16, line, inner_width, text_style);
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let ms = duration.as_secs_f64() * 1000.0;
+    if ms >= 100.0 {
+        format!("{:.0}ms", ms)
+    } else {
+        format!("{:.1}ms", ms)
+    }
+}
@@ -22,8 +22,13 @@ use tui::{
 use fuzzy_matcher::skim::SkimMatcherV2 as Matcher;
 use tui::widgets::Widget;
 
+use std::borrow::Cow;
 use std::cmp::{self, Ordering};
-use std::{collections::HashMap, io::Read, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+    path::{Path, PathBuf},
+};
 
 use crate::ui::{Prompt, PromptEvent};
 use helix_core::{
@@ -31,7 +36,7 @@ use helix_core::{
     unicode::segmentation::UnicodeSegmentation, Position, Syntax,
 };
 use helix_view::{
-    editor::Action,
+    editor::{Action, CloseError},
     graphics::{CursorKind, Margin, Modifier, Rect},
     theme::Style,
     view::ViewPosition,
@@ -44,6 +49,22 @@ pub const MIN_AREA_WIDTH_FOR_PREVIEW: u16 = 72;
 /// Biggest file size to preview in bytes
 pub const MAX_FILE_SIZE_FOR_PREVIEW: u64 = 10 * 1024 * 1024;
 
+/// Candidate sets at least this large are scored on multiple threads.
+const PARALLEL_SCORE_THRESHOLD: usize = 10_000;
+
+/// Score `text` against the query, boosting matches that also hit the final
+/// path component so filename matches rank above matches spread over the
+/// directory part.
+fn score_option(matcher: &Matcher, query: &FuzzyQuery, text: &str) -> Option<i64> {
+    let mut score = query.fuzzy_match(text, matcher)?;
+    if let Some(pos) = text.rfind(std::path::MAIN_SEPARATOR) {
+        if let Some(basename_score) = query.fuzzy_match(&text[pos + 1..], matcher) {
+            score += basename_score / 2;
+        }
+    }
+    Some(score)
+}
+
 #[derive(PartialEq, Eq, Hash)]
 pub enum PathOrId {
     Id(DocumentId),
@@ -74,11 +95,25 @@ impl From<DocumentId> for PathOrId {
 
 type FileCallback<T> = Box<dyn Fn(&Editor, &T) -> Option<FileLocation>>;
 
+/// Callbacks that let the picker run filesystem operations (create, rename,
+/// delete) on its entries. Only set for pickers whose entries refer to paths
+/// on disk, like the file picker.
+struct FileOperations<T> {
+    /// The on-disk path an entry refers to.
+    path_fn: PathCallback<T>,
+    /// Build a fresh entry for a newly created path.
+    entry_fn: EntryCallback<T>,
+}
+
+type PathCallback<T> = Box<dyn Fn(&T) -> Option<PathBuf>>;
+type EntryCallback<T> = Box<dyn Fn(PathBuf) -> T>;
+
 /// File path and range of lines (used to align and highlight lines)
 pub type FileLocation = (PathOrId, Option<(usize, usize)>);
 
 pub enum CachedPreview {
     Document(Box<Document>),
+    Image(super::image::ImageInfo),
     Binary,
     LargeFile,
     NotFound,
@@ -101,14 +136,15 @@ impl Preview<'_, '_> {
     }
 
     /// Alternate text to show for the preview.
-    fn placeholder(&self) -> &str {
+    fn placeholder(&self) -> Cow<'_, str> {
         match *self {
-            Self::EditorDocument(_) => "<File preview>",
+            Self::EditorDocument(_) => "<File preview>".into(),
             Self::Cached(preview) => match preview {
-                CachedPreview::Document(_) => "<File preview>",
-                CachedPreview::Binary => "<Binary file>",
-                CachedPreview::LargeFile => "<File too large to preview>",
-                CachedPreview::NotFound => "<File not found>",
+                CachedPreview::Document(_) => "<File preview>".into(),
+                CachedPreview::Image(info) => info.describe().into(),
+                CachedPreview::Binary => "<Binary file>".into(),
+                CachedPreview::LargeFile => "<File too large to preview>".into(),
+                CachedPreview::NotFound => "<File not found>".into(),
             },
         }
     }
@@ -125,6 +161,9 @@ pub struct Picker<T: Item> {
     completion_height: u16,
 
     cursor: usize,
+    /// Entries marked with `Tab`, by index into `options`. The accept keys
+    /// act on all marked entries at once when any are marked.
+    marked: HashSet<usize>,
     // pattern: String,
     prompt: Prompt,
     previous_pattern: (String, FuzzyQuery),
@@ -141,6 +180,11 @@ pub struct Picker<T: Item> {
     read_buffer: Vec<u8>,
     /// Given an item in the picker, return the file path and line number to display.
     file_fn: Option<FileCallback<T>>,
+    /// Filesystem operation callbacks, when the entries are paths on disk.
+    file_operations: Option<FileOperations<T>>,
+    /// Whether a kitty graphics thumbnail is currently on screen, so it
+    /// can be cleared when the selection moves or the picker closes.
+    drew_image: bool,
 }
 
 impl<T: Item + 'static> Picker<T> {
@@ -162,6 +206,7 @@ impl<T: Item + 'static> Picker<T> {
             matcher: Box::default(),
             matches: Vec::new(),
             cursor: 0,
+            marked: HashSet::new(),
             prompt,
             previous_pattern: (String::new(), FuzzyQuery::default()),
             truncate_start: true,
@@ -172,6 +217,8 @@ impl<T: Item + 'static> Picker<T> {
             preview_cache: HashMap::new(),
             read_buffer: Vec::with_capacity(1024),
             file_fn: None,
+            file_operations: None,
+            drew_image: false,
         };
 
         picker.calculate_column_widths();
@@ -205,9 +252,34 @@ impl<T: Item + 'static> Picker<T> {
         self
     }
 
+    /// Seed the preview cache with an in-memory document for entries that do
+    /// not correspond to a file on disk. The path only serves as cache key
+    /// and for language detection, it is never read.
+    pub fn with_preview_document(mut self, path: PathBuf, document: Document) -> Self {
+        self.preview_cache
+            .insert(path, CachedPreview::Document(Box::new(document)));
+        self
+    }
+
+    /// Enable filesystem operations on the entries: `Alt-n` creates a file
+    /// or directory, `Alt-r` renames the highlighted entry and `Alt-x`
+    /// deletes it after confirmation.
+    pub fn with_file_operations(
+        mut self,
+        path_fn: impl Fn(&T) -> Option<PathBuf> + 'static,
+        entry_fn: impl Fn(PathBuf) -> T + 'static,
+    ) -> Self {
+        self.file_operations = Some(FileOperations {
+            path_fn: Box::new(path_fn),
+            entry_fn: Box::new(entry_fn),
+        });
+        self
+    }
+
     pub fn set_options(&mut self, new_options: Vec<T>) {
         self.options = new_options;
         self.cursor = 0;
+        self.marked.clear();
         self.force_score();
         self.calculate_column_widths();
     }
@@ -268,7 +340,7 @@ impl<T: Item + 'static> Picker<T> {
                 let option = &self.options[pmatch.index];
                 let text = option.sort_text(&self.editor_data);
 
-                match query.fuzzy_match(&text, &self.matcher) {
+                match score_option(&self.matcher, &query, &text) {
                     Some(s) => {
                         // Update the score
                         pmatch.score = s;
@@ -295,22 +367,62 @@ impl<T: Item + 'static> Picker<T> {
 
         let query = FuzzyQuery::new(pattern);
         self.matches.clear();
-        self.matches.extend(
-            self.options
-                .iter()
-                .enumerate()
-                .filter_map(|(index, option)| {
-                    let text = option.filter_text(&self.editor_data);
 
-                    query
-                        .fuzzy_match(&text, &self.matcher)
-                        .map(|score| PickerMatch {
-                            index,
-                            score,
-                            len: text.chars().count(),
+        if self.options.len() >= PARALLEL_SCORE_THRESHOLD {
+            // Score large candidate sets on all cores so huge worktrees stay
+            // responsive while typing. The texts are collected up front since
+            // the items themselves cannot be shared across threads.
+            let texts: Vec<_> = self
+                .options
+                .iter()
+                .map(|option| option.filter_text(&self.editor_data))
+                .collect();
+            let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+            let chunk_size = (texts.len() + threads - 1) / threads;
+            let matcher = &self.matcher;
+
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = texts
+                    .chunks(chunk_size)
+                    .enumerate()
+                    .map(|(chunk_index, chunk)| {
+                        let query = &query;
+                        scope.spawn(move || {
+                            chunk
+                                .iter()
+                                .enumerate()
+                                .filter_map(|(offset, text)| {
+                                    score_option(matcher, query, text).map(|score| PickerMatch {
+                                        index: chunk_index * chunk_size + offset,
+                                        score,
+                                        len: text.chars().count(),
+                                    })
+                                })
+                                .collect::<Vec<_>>()
                         })
-                }),
-        );
+                    })
+                    .collect();
+                for handle in handles {
+                    self.matches.extend(handle.join().unwrap());
+                }
+            });
+        } else {
+            self.matches.extend(
+                self.options
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, option)| {
+                        let text = option.filter_text(&self.editor_data);
+
+                        score_option(&self.matcher, &query, &text)
+                            .map(|score| PickerMatch {
+                                index,
+                                score,
+                                len: text.chars().count(),
+                            })
+                    }),
+            );
+        }
 
         self.matches.sort_unstable();
     }
@@ -360,10 +472,274 @@ impl<T: Item + 'static> Picker<T> {
             .map(|pmatch| &self.options[pmatch.index])
     }
 
+    /// Toggle the mark on the entry under the cursor.
+    pub fn toggle_mark(&mut self) {
+        if let Some(pmatch) = self.matches.get(self.cursor) {
+            if !self.marked.remove(&pmatch.index) {
+                self.marked.insert(pmatch.index);
+            }
+        }
+    }
+
+    /// The entries the accept keys act on: all marked entries, or the entry
+    /// under the cursor when none are marked.
+    fn marked_options(&self) -> Vec<&T> {
+        if self.marked.is_empty() {
+            return self.selection().into_iter().collect();
+        }
+        self.options
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.marked.contains(index))
+            .map(|(_, option)| option)
+            .collect()
+    }
+
     pub fn toggle_preview(&mut self) {
         self.show_preview = !self.show_preview;
     }
 
+    /// The on-disk path of the entry under the cursor, if the picker has
+    /// file operations enabled.
+    fn highlighted_path(&self) -> Option<PathBuf> {
+        let ops = self.file_operations.as_ref()?;
+        self.selection().and_then(|option| (ops.path_fn)(option))
+    }
+
+    /// Queue a callback applying `fun` to this picker if it is still on the
+    /// compositor stack. The create and rename prompts only get access to
+    /// the compositor asynchronously, after the filesystem change is done.
+    fn update_entries(cx: &mut Context, fun: impl FnOnce(&mut Self) + Send + 'static) {
+        cx.jobs.callback(async move {
+            let call: Callback = Callback::EditorCompositor(Box::new(|_editor, compositor| {
+                if let Some(overlay) = compositor.find::<ui::overlay::Overlay<Self>>() {
+                    fun(&mut overlay.content);
+                }
+            }));
+            Ok(call)
+        });
+    }
+
+    /// Add an entry for a newly created path, rescored against the current
+    /// pattern.
+    fn add_entry(&mut self, path: PathBuf) {
+        let Some(ops) = self.file_operations.as_ref() else {
+            return;
+        };
+        let entry = (ops.entry_fn)(path);
+        let mut options = std::mem::take(&mut self.options);
+        options.push(entry);
+        self.set_options(options);
+    }
+
+    /// Replace the entry for `from` with one for `to` after a rename.
+    fn rename_entry(&mut self, from: &Path, to: PathBuf) {
+        let Some(ops) = self.file_operations.as_ref() else {
+            return;
+        };
+        let mut options = std::mem::take(&mut self.options);
+        for option in options.iter_mut() {
+            if (ops.path_fn)(option).map_or(false, |path| path == from) {
+                *option = (ops.entry_fn)(to.clone());
+                break;
+            }
+        }
+        self.set_options(options);
+    }
+
+    /// Drop the entry for a deleted path.
+    fn remove_entry(&mut self, path: &Path) {
+        let Some(ops) = self.file_operations.as_ref() else {
+            return;
+        };
+        let mut options = std::mem::take(&mut self.options);
+        options.retain(|option| (ops.path_fn)(option).map_or(true, |p| p != path));
+        self.set_options(options);
+    }
+
+    /// Prompt for a path to create. A trailing path separator creates a
+    /// directory, anything else a file (with intermediate directories).
+    fn create_entry_prompt(&mut self) -> EventResult {
+        // Seed the prompt with the highlighted entry's directory so new
+        // files land next to it by default.
+        let seed = self
+            .highlighted_path()
+            .and_then(|path| {
+                Some(format!(
+                    "{}{}",
+                    path.parent()?.display(),
+                    std::path::MAIN_SEPARATOR
+                ))
+            })
+            .unwrap_or_default();
+        EventResult::Consumed(Some(Box::new(move |compositor: &mut Compositor, cx| {
+            let prompt = Prompt::new(
+                "create:".into(),
+                None,
+                ui::completers::filename,
+                move |cx: &mut Context, input: &str, event: PromptEvent| {
+                    if event != PromptEvent::Validate || input.is_empty() {
+                        return;
+                    }
+                    let is_dir = input.ends_with(std::path::MAIN_SEPARATOR);
+                    let path = helix_core::path::expand_tilde(Path::new(input));
+                    if path.exists() {
+                        cx.editor
+                            .set_error(format!("path '{}' already exists", path.display()));
+                        return;
+                    }
+                    let result = if is_dir {
+                        std::fs::create_dir_all(&path)
+                    } else {
+                        path.parent()
+                            .map_or(Ok(()), std::fs::create_dir_all)
+                            .and_then(|_| std::fs::File::create(&path).map(|_| ()))
+                    };
+                    match result {
+                        Ok(()) => {
+                            cx.editor.set_status(format!("created {}", path.display()));
+                            // the picker only lists files, so there is no
+                            // entry to add for a new directory
+                            if !is_dir {
+                                Self::update_entries(cx, move |picker| picker.add_entry(path));
+                            }
+                        }
+                        Err(err) => cx.editor.set_error(format!(
+                            "unable to create '{}': {}",
+                            path.display(),
+                            err
+                        )),
+                    }
+                },
+            )
+            .with_line(seed, cx.editor);
+            compositor.push(Box::new(prompt));
+        })))
+    }
+
+    /// Prompt for a new path for the highlighted entry and rename it on
+    /// disk, carrying any open buffer over to the new path.
+    fn rename_entry_prompt(&mut self) -> EventResult {
+        let Some(old_path) = self.highlighted_path() else {
+            return EventResult::Consumed(None);
+        };
+        let line = old_path.display().to_string();
+        EventResult::Consumed(Some(Box::new(move |compositor: &mut Compositor, cx| {
+            let prompt = Prompt::new(
+                "rename:".into(),
+                None,
+                ui::completers::filename,
+                move |cx: &mut Context, input: &str, event: PromptEvent| {
+                    if event != PromptEvent::Validate || input.is_empty() {
+                        return;
+                    }
+                    let new_path = helix_core::path::expand_tilde(Path::new(input));
+                    if new_path == old_path {
+                        return;
+                    }
+                    if new_path.exists() {
+                        cx.editor
+                            .set_error(format!("path '{}' already exists", new_path.display()));
+                        return;
+                    }
+                    if let Some(parent) = new_path.parent() {
+                        if let Err(err) = std::fs::create_dir_all(parent) {
+                            cx.editor.set_error(format!(
+                                "unable to create directory '{}': {}",
+                                parent.display(),
+                                err
+                            ));
+                            return;
+                        }
+                    }
+                    let is_dir = old_path.is_dir();
+                    crate::commands::lsp::will_rename_files(cx.editor, &old_path, &new_path, is_dir);
+                    if let Err(err) = std::fs::rename(&old_path, &new_path) {
+                        cx.editor.set_error(format!(
+                            "unable to rename '{}' to '{}': {}",
+                            old_path.display(),
+                            new_path.display(),
+                            err
+                        ));
+                        return;
+                    }
+                    // Reopen an open buffer under its new path, mirroring
+                    // `:rename-file`.
+                    if let Some(doc_id) =
+                        cx.editor.document_by_path(&old_path).map(|doc| doc.id())
+                    {
+                        let syn_loader = cx.editor.syn_loader.clone();
+                        let mut path_err = None;
+                        if let Some(doc) = cx.editor.document_mut(doc_id) {
+                            doc.close_language_servers();
+                            if let Err(err) = doc.set_path(Some(&new_path)) {
+                                path_err = Some(err);
+                            }
+                            doc.detect_language(syn_loader);
+                        }
+                        cx.editor.refresh_language_servers(doc_id);
+                        if let Some(err) = path_err {
+                            cx.editor.set_error(err.to_string());
+                            return;
+                        }
+                    }
+                    crate::commands::lsp::did_rename_files(cx.editor, &old_path, &new_path, is_dir);
+                    cx.editor
+                        .set_status(format!("renamed to {}", new_path.display()));
+                    let old_path = old_path.clone();
+                    Self::update_entries(cx, move |picker| {
+                        picker.rename_entry(&old_path, new_path)
+                    });
+                },
+            )
+            .with_line(line, cx.editor);
+            compositor.push(Box::new(prompt));
+        })))
+    }
+
+    /// Ask for confirmation, then delete the highlighted entry from disk.
+    /// Directories are only removed when empty.
+    fn delete_entry_prompt(&mut self) -> EventResult {
+        let Some(path) = self.highlighted_path() else {
+            return EventResult::Consumed(None);
+        };
+        let message = format!("Delete '{}'? (y)es (Esc to cancel)", path.display());
+        EventResult::Consumed(Some(Box::new(move |compositor: &mut Compositor, _cx| {
+            compositor.push(Box::new(ui::Confirm::new(
+                message,
+                &['y'],
+                Box::new(move |compositor, cx, _choice| {
+                    let is_dir = path.is_dir();
+                    crate::commands::lsp::will_delete_files(cx.editor, &path, is_dir);
+                    let result = if is_dir {
+                        std::fs::remove_dir(&path)
+                    } else {
+                        std::fs::remove_file(&path)
+                    };
+                    if let Err(err) = result {
+                        cx.editor
+                            .set_error(format!("unable to delete '{}': {}", path.display(), err));
+                        return;
+                    }
+                    // The file is gone, unsaved changes included; force the
+                    // buffer closed like `:delete-file` does.
+                    if let Some(doc_id) = cx.editor.document_by_path(&path).map(|doc| doc.id()) {
+                        if let Err(CloseError::SaveError(err)) =
+                            cx.editor.close_document(doc_id, true)
+                        {
+                            cx.editor.set_error(err.to_string());
+                        }
+                    }
+                    crate::commands::lsp::did_delete_files(cx.editor, &path, is_dir);
+                    cx.editor.set_status(format!("deleted {}", path.display()));
+                    if let Some(overlay) = compositor.find::<ui::overlay::Overlay<Self>>() {
+                        overlay.content.remove_entry(&path);
+                    }
+                }),
+            )));
+        })))
+    }
+
     fn prompt_handle_event(&mut self, event: &Event, cx: &mut Context) -> EventResult {
         if let EventResult::Consumed(_) = self.prompt.handle_event(event, cx) {
             // TODO: recalculate only if pattern changed
@@ -407,7 +783,12 @@ impl<T: Item + 'static> Picker<T> {
                 let preview = data
                     .map(
                         |(metadata, content_type)| match (metadata.len(), content_type) {
-                            (_, content_inspector::ContentType::BINARY) => CachedPreview::Binary,
+                            (_, content_inspector::ContentType::BINARY) => {
+                                match super::image::detect(path) {
+                                    Some(info) => CachedPreview::Image(info),
+                                    None => CachedPreview::Binary,
+                                }
+                            }
                             (size, _) if size > MAX_FILE_SIZE_FOR_PREVIEW => {
                                 CachedPreview::LargeFile
                             }
@@ -513,7 +894,16 @@ impl<T: Item + 'static> Picker<T> {
 
         let area = inner.clip_left(1).with_height(1);
 
-        let count = format!("{}/{}", self.matches.len(), self.options.len());
+        let count = if self.marked.is_empty() {
+            format!("{}/{}", self.matches.len(), self.options.len())
+        } else {
+            format!(
+                "({}) {}/{}",
+                self.marked.len(),
+                self.matches.len(),
+                self.options.len()
+            )
+        };
         surface.set_stringn(
             (area.x + area.width).saturating_sub(count.len() as u16 + 1),
             area.y,
@@ -541,14 +931,25 @@ impl<T: Item + 'static> Picker<T> {
         let offset = self.cursor - (self.cursor % std::cmp::max(1, rows as usize));
         let cursor = self.cursor.saturating_sub(offset);
 
+        let marked_style = cx
+            .editor
+            .theme
+            .try_get("ui.picker.marked")
+            .unwrap_or_else(|| {
+                cx.editor
+                    .theme
+                    .get("special")
+                    .add_modifier(Modifier::REVERSED)
+            });
+
         let options = self
             .matches
             .iter()
             .skip(offset)
             .take(rows as usize)
-            .map(|pmatch| &self.options[pmatch.index])
-            .map(|option| option.format(&self.editor_data))
-            .map(|mut row| {
+            .map(|pmatch| (pmatch.index, &self.options[pmatch.index]))
+            .map(|(index, option)| (index, option.format(&self.editor_data)))
+            .map(|(index, mut row)| {
                 const TEMP_CELL_SEP: &str = " ";
 
                 let line = row.cell_text().fold(String::new(), |mut s, frag| {
@@ -634,6 +1035,10 @@ impl<T: Item + 'static> Picker<T> {
                     cell_start_byte_offset += cell_len + TEMP_CELL_SEP.len();
                 }
 
+                if self.marked.contains(&index) {
+                    row = row.style(marked_style);
+                }
+
                 row
             });
 
@@ -674,15 +1079,41 @@ impl<T: Item + 'static> Picker<T> {
         let inner = inner.inner(&margin);
         block.render(area, surface);
 
+        // clear any thumbnail from the previous frame; the selection may
+        // have moved off an image entirely
+        if self.drew_image {
+            super::image::kitty_clear();
+            self.drew_image = false;
+        }
+
         if let Some((path, range)) = self.current_file(cx.editor) {
+            let image_path = match &path {
+                PathOrId::Path(path) => Some(path.clone()),
+                PathOrId::Id(_) => None,
+            };
             let preview = self.get_preview(path, cx.editor);
             let doc = match preview.document() {
                 Some(doc) => doc,
                 None => {
-                    let alt_text = preview.placeholder();
-                    let x = inner.x + inner.width.saturating_sub(alt_text.len() as u16) / 2;
-                    let y = inner.y + inner.height / 2;
-                    surface.set_stringn(x, y, alt_text, inner.width as usize, text);
+                    let mut drew_image = false;
+                    if let (Preview::Cached(CachedPreview::Image(info)), Some(path)) =
+                        (&preview, &image_path)
+                    {
+                        // kitty only reads PNG data straight from a file
+                        if super::image::kitty_supported() && info.format == "PNG" {
+                            if let Some(dimensions) = info.dimensions {
+                                drew_image =
+                                    super::image::kitty_draw(path, dimensions, inner).is_ok();
+                            }
+                        }
+                    }
+                    if !drew_image {
+                        let alt_text = preview.placeholder();
+                        let x = inner.x + inner.width.saturating_sub(alt_text.len() as u16) / 2;
+                        let y = inner.y + inner.height / 2;
+                        surface.set_stringn(x, y, alt_text, inner.width as usize, text);
+                    }
+                    self.drew_image = drew_image;
                     return;
                 }
             };
@@ -708,7 +1139,11 @@ impl<T: Item + 'static> Picker<T> {
                 area.height,
                 &cx.editor.theme,
             );
-            for spans in EditorView::doc_diagnostics_highlights(doc, &cx.editor.theme) {
+            for spans in EditorView::doc_diagnostics_highlights(
+                doc,
+                &cx.editor.theme,
+                cx.editor.config().inline_diagnostics,
+            ) {
                 if spans.is_empty() {
                     continue;
                 }
@@ -804,7 +1239,11 @@ impl<T: Item + 'static> Component for Picker<T> {
             shift!(Tab) | key!(Up) | ctrl!('p') => {
                 self.move_by(1, Direction::Backward);
             }
-            key!(Tab) | key!(Down) | ctrl!('n') => {
+            key!(Down) | ctrl!('n') => {
+                self.move_by(1, Direction::Forward);
+            }
+            key!(Tab) => {
+                self.toggle_mark();
                 self.move_by(1, Direction::Forward);
             }
             key!(PageDown) | ctrl!('d') => {
@@ -823,24 +1262,24 @@ impl<T: Item + 'static> Component for Picker<T> {
                 return close_fn;
             }
             alt!(Enter) => {
-                if let Some(option) = self.selection() {
+                for option in self.marked_options() {
                     (self.callback_fn)(ctx, option, Action::Load);
                 }
             }
             key!(Enter) => {
-                if let Some(option) = self.selection() {
+                for option in self.marked_options() {
                     (self.callback_fn)(ctx, option, Action::Replace);
                 }
                 return close_fn;
             }
             ctrl!('s') => {
-                if let Some(option) = self.selection() {
+                for option in self.marked_options() {
                     (self.callback_fn)(ctx, option, Action::HorizontalSplit);
                 }
                 return close_fn;
             }
             ctrl!('v') => {
-                if let Some(option) = self.selection() {
+                for option in self.marked_options() {
                     (self.callback_fn)(ctx, option, Action::VerticalSplit);
                 }
                 return close_fn;
@@ -848,6 +1287,15 @@ impl<T: Item + 'static> Component for Picker<T> {
             ctrl!('t') => {
                 self.toggle_preview();
             }
+            alt!('n') if self.file_operations.is_some() => {
+                return self.create_entry_prompt();
+            }
+            alt!('r') if self.file_operations.is_some() => {
+                return self.rename_entry_prompt();
+            }
+            alt!('x') if self.file_operations.is_some() => {
+                return self.delete_entry_prompt();
+            }
             _ => {
                 self.prompt_handle_event(event, ctx);
             }
@@ -873,6 +1321,14 @@ impl<T: Item + 'static> Component for Picker<T> {
     }
 }
 
+impl<T: Item> Drop for Picker<T> {
+    fn drop(&mut self) {
+        if self.drew_image {
+            super::image::kitty_clear();
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Debug)]
 struct PickerMatch {
     score: i64,
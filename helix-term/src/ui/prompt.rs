@@ -15,6 +15,7 @@ use helix_view::{
 };
 
 type PromptCharHandler = Box<dyn Fn(&mut Prompt, char, &Context)>;
+type PromptKeyHandler = Box<dyn FnMut(&mut Context, KeyEvent) -> bool>;
 pub type Completion = (RangeFrom<usize>, Cow<'static, str>);
 type CompletionFn = Box<dyn FnMut(&Editor, &str) -> Vec<Completion>>;
 type CallbackFn = Box<dyn FnMut(&mut Context, &str, PromptEvent)>;
@@ -32,6 +33,12 @@ pub struct Prompt {
     callback_fn: CallbackFn,
     pub doc_fn: DocFn,
     next_char_handler: Option<PromptCharHandler>,
+    /// Extra keybindings specific to this prompt, tried before the
+    /// built-in ones. Returns whether the key was handled.
+    key_handler: Option<PromptKeyHandler>,
+    /// Whether the completion menu is currently fuzzy-matching against the
+    /// history register instead of using `completion_fn`, see `Ctrl-r Ctrl-r`.
+    history_search: bool,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -83,9 +90,19 @@ impl Prompt {
             callback_fn: Box::new(callback_fn),
             doc_fn: Box::new(|_| None),
             next_char_handler: None,
+            key_handler: None,
+            history_search: false,
         }
     }
 
+    pub fn with_key_handler(
+        mut self,
+        handler: impl FnMut(&mut Context, KeyEvent) -> bool + 'static,
+    ) -> Self {
+        self.key_handler = Some(Box::new(handler));
+        self
+    }
+
     pub fn with_line(mut self, line: String, editor: &Editor) -> Self {
         let cursor = line.len();
         self.line = line;
@@ -100,7 +117,42 @@ impl Prompt {
 
     pub fn recalculate_completion(&mut self, editor: &Editor) {
         self.exit_selection();
-        self.completion = (self.completion_fn)(editor, &self.line);
+        self.completion = if self.history_search {
+            self.history_completion(editor)
+        } else {
+            (self.completion_fn)(editor, &self.line)
+        };
+    }
+
+    /// Fuzzy-match the history register against the current line, most recent
+    /// and best matches first. Used while `Ctrl-r Ctrl-r` history search is
+    /// active.
+    fn history_completion(&self, editor: &Editor) -> Vec<Completion> {
+        let values = match self
+            .history_register
+            .and_then(|register| editor.registers.read(register))
+        {
+            Some(values) => values,
+            None => return Vec::new(),
+        };
+
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+        let query = ui::fuzzy_match::FuzzyQuery::new(&self.line);
+        let mut matches: Vec<_> = values
+            .iter()
+            .rev()
+            .filter_map(|value| {
+                query
+                    .fuzzy_match(value, &matcher)
+                    .map(|score| (value, score))
+            })
+            .collect();
+        // sort_by_key is stable, so equally scored entries stay most recent first
+        matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        matches
+            .into_iter()
+            .map(|(value, _)| (0.., Cow::Owned(value.clone())))
+            .collect()
     }
 
     /// Compute the cursor position after applying movement
@@ -499,6 +551,13 @@ impl Component for Prompt {
             compositor.pop();
         })));
 
+        if let Some(handler) = &mut self.key_handler {
+            if handler(cx, event) {
+                (self.callback_fn)(cx, &self.line, PromptEvent::Update);
+                return EventResult::Consumed(None);
+            }
+        }
+
         match event {
             ctrl!('c') | key!(Esc) => {
                 (self.callback_fn)(cx, &self.line, PromptEvent::Abort);
@@ -581,6 +640,23 @@ impl Component for Prompt {
                     return close_fn;
                 }
             }
+            ctrl!('o') => {
+                // Move the prompt content into a regular buffer with full
+                // editing and the history available, for long search
+                // patterns and command lines. Enter there submits the
+                // cursor line, see `EditorView`.
+                if matches!(self.history_register, Some(':' | '/')) {
+                    let register = self.history_register.unwrap();
+                    let line = self.line.clone();
+                    (self.callback_fn)(cx, &self.line, PromptEvent::Abort);
+                    return EventResult::Consumed(Some(Box::new(
+                        move |compositor: &mut Compositor, cx: &mut Context| {
+                            compositor.pop();
+                            open_command_line_buffer(cx.editor, register, &line);
+                        },
+                    )));
+                }
+            }
             ctrl!('p') | key!(Up) => {
                 if let Some(register) = self.history_register {
                     self.change_history(cx, register, CompletionDirection::Backward);
@@ -605,6 +681,15 @@ impl Component for Prompt {
             }
             ctrl!('q') => self.exit_selection(),
             ctrl!('r') => {
+                // A second Ctrl-r toggles fuzzy search over the prompt history
+                // instead of inserting a register.
+                if self.next_char_handler.is_some() || self.history_search {
+                    self.next_char_handler = None;
+                    self.history_search = !self.history_search;
+                    self.recalculate_completion(cx.editor);
+                    (self.callback_fn)(cx, &self.line, PromptEvent::Update);
+                    return EventResult::Consumed(None);
+                }
                 self.completion = cx
                     .editor
                     .registers
@@ -664,3 +749,38 @@ impl Component for Prompt {
         )
     }
 }
+
+/// Open a scratch split seeded with the history of `register`, one entry per
+/// line and the current prompt input last, with the cursor on it. `Enter` in
+/// normal mode submits the cursor line as if it had been entered at the
+/// prompt; see the handling in `EditorView`.
+fn open_command_line_buffer(editor: &mut Editor, register: char, line: &str) {
+    let history = editor
+        .registers
+        .read(register)
+        .map(|values| values.to_vec())
+        .unwrap_or_default();
+
+    let mut contents = String::new();
+    for entry in &history {
+        contents.push_str(entry);
+        contents.push('\n');
+    }
+    contents.push_str(line);
+
+    let id = editor.new_file(helix_view::editor::Action::HorizontalSplit);
+    let (view, doc) = current!(editor);
+    let transaction = helix_core::Transaction::change(
+        doc.text(),
+        [(0, doc.text().len_chars(), Some(contents.into()))].into_iter(),
+    );
+    doc.apply(&transaction, view.id);
+    doc.append_changes_to_history(view);
+
+    // cursor on the current input, the last line
+    let text = doc.text().slice(..);
+    let last_line = text.line_to_char(text.len_lines().saturating_sub(1));
+    doc.set_selection(view.id, helix_core::Selection::point(last_line));
+
+    editor.command_line_doc = Some((id, register));
+}
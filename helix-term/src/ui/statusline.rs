@@ -1,5 +1,5 @@
 use helix_core::{coords_at_pos, encoding, Position};
-use helix_lsp::lsp::DiagnosticSeverity;
+use helix_lsp::lsp::{self, DiagnosticSeverity};
 use helix_view::document::DEFAULT_LANGUAGE_NAME;
 use helix_view::{
     document::{Mode, SCRATCH_BUFFER_NAME},
@@ -161,6 +161,8 @@ where
         helix_view::editor::StatusLineElement::Spacer => render_spacer,
         helix_view::editor::StatusLineElement::VersionControl => render_version_control,
         helix_view::editor::StatusLineElement::Register => render_register,
+        helix_view::editor::StatusLineElement::BackgroundJobs => render_background_jobs,
+        helix_view::editor::StatusLineElement::LspProgress => render_lsp_progress,
     }
 }
 
@@ -220,18 +222,63 @@ where
     );
 }
 
+fn render_lsp_progress<F>(context: &mut RenderContext, write: F)
+where
+    F: Fn(&mut RenderContext, String, Option<Style>) + Copy,
+{
+    let mut parts = Vec::new();
+    for language_server in context.doc.language_servers() {
+        let Some(progress) = context.editor.lsp_progress.progress_map(language_server.id()) else { continue };
+        for status in progress.values() {
+            let (title, message, percentage) = match status.progress() {
+                Some(lsp::WorkDoneProgress::Begin(lsp::WorkDoneProgressBegin {
+                    title,
+                    message,
+                    percentage,
+                    ..
+                })) => (Some(title), message.as_ref(), *percentage),
+                Some(lsp::WorkDoneProgress::Report(lsp::WorkDoneProgressReport {
+                    message,
+                    percentage,
+                    ..
+                })) => (None, message.as_ref(), *percentage),
+                _ => continue,
+            };
+            let mut part = title
+                .map(String::from)
+                .or_else(|| message.cloned())
+                .unwrap_or_else(|| language_server.name().to_string());
+            if let Some(percentage) = percentage {
+                part.push_str(&format!(" {}%", percentage));
+            }
+            parts.push(part);
+        }
+    }
+    if parts.is_empty() {
+        return;
+    }
+    // keep the segment stable between renders; token map iteration order is arbitrary
+    parts.sort_unstable();
+    write(context, format!(" {} ", parts.join(", ")), None);
+}
+
 fn render_diagnostics<F>(context: &mut RenderContext, write: F)
 where
     F: Fn(&mut RenderContext, String, Option<Style>) + Copy,
 {
+    if !context.doc.diagnostics_enabled {
+        return;
+    }
+
+    let min_severity = context.editor.config().statusline_diagnostics;
     let (warnings, errors) = context
         .doc
         .shown_diagnostics()
         .fold((0, 0), |mut counts, diag| {
             use helix_core::diagnostic::Severity;
             match diag.severity {
-                Some(Severity::Warning) => counts.0 += 1,
-                Some(Severity::Error) | None => counts.1 += 1,
+                Some(Severity::Warning) if Severity::Warning >= min_severity => counts.0 += 1,
+                Some(Severity::Error) | None if Severity::Error >= min_severity => counts.1 += 1,
                 _ => {}
             }
             counts
@@ -260,6 +307,7 @@ fn render_workspace_diagnostics<F>(context: &mut RenderContext, write: F)
 where
     F: Fn(&mut RenderContext, String, Option<Style>) + Copy,
 {
+    let min_severity = context.editor.config().statusline_diagnostics;
     let (warnings, errors) =
         context
             .editor
@@ -267,9 +315,14 @@ where
             .values()
             .flatten()
             .fold((0, 0), |mut counts, (diag, _)| {
+                use helix_core::diagnostic::Severity;
                 match diag.severity {
-                    Some(DiagnosticSeverity::WARNING) => counts.0 += 1,
-                    Some(DiagnosticSeverity::ERROR) | None => counts.1 += 1,
+                    Some(DiagnosticSeverity::WARNING) if Severity::Warning >= min_severity => {
+                        counts.0 += 1
+                    }
+                    Some(DiagnosticSeverity::ERROR) | None if Severity::Error >= min_severity => {
+                        counts.1 += 1
+                    }
                     _ => {}
                 }
                 counts
@@ -499,3 +552,31 @@ where
         write(context, format!(" reg={} ", reg), None)
     }
 }
+
+fn render_background_jobs<F>(context: &mut RenderContext, write: F)
+where
+    F: Fn(&mut RenderContext, String, Option<Style>) + Copy,
+{
+    let Some((name, progress, count)) = crate::job::oldest_running() else { return };
+
+    // same frames/interval as the LSP spinner, but keyed off wall time since
+    // there is no per-server spinner state to tick here
+    const FRAMES: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+    let idx = (std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        / 80) as usize
+        % FRAMES.len();
+
+    let mut segment = format!(" {} {}", FRAMES[idx], name);
+    if let Some(percent) = progress {
+        segment.push_str(&format!(" {}%", percent));
+    }
+    if count > 1 {
+        segment.push_str(&format!(" (+{})", count - 1));
+    }
+    segment.push(' ');
+
+    write(context, segment, None);
+}
@@ -85,6 +85,15 @@ where
             .get_or_init(|| {
                 use std::time::Instant;
 
+                // Escape hatch for terminals that answer the query but have a
+                // broken implementation of the protocol.
+                if std::env::var_os("HELIX_DISABLE_KEYBOARD_ENHANCEMENT").is_some() {
+                    log::info!(
+                        "Keyboard enhancement protocol disabled via HELIX_DISABLE_KEYBOARD_ENHANCEMENT"
+                    );
+                    return false;
+                }
+
                 let now = Instant::now();
                 let supported = matches!(terminal::supports_keyboard_enhancement(), Ok(true));
                 log::debug!(
@@ -86,10 +86,53 @@ pub fn encode(input: &[u8]) -> String {
     String::from_utf8(output).expect("Invalid UTF8")
 }
 
+/// Decode standard (RFC 4648) base64, with or without `=` padding.
+/// Returns `None` on invalid input.
+pub fn decode(input: &str) -> Option<Vec<u8>> {
+    fn decode_char(c: u8) -> Option<u32> {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        Some(sextet as u32)
+    }
+
+    let input = input.trim_end_matches(char::from(PAD_BYTE)).as_bytes();
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+
+    for chunk in input.chunks(4) {
+        // a single leftover sextet cannot encode a byte
+        if chunk.len() == 1 {
+            return None;
+        }
+
+        let mut chunk_int: u32 = 0;
+        for &c in chunk {
+            chunk_int = chunk_int.shl(6_u8).bitor(decode_char(c)?);
+        }
+        chunk_int = chunk_int.shl(6 * (4 - chunk.len()) as u8);
+
+        output.push(chunk_int.shr(16_u8) as u8);
+        if chunk.len() > 2 {
+            output.push(chunk_int.shr(8_u8) as u8);
+        }
+        if chunk.len() > 3 {
+            output.push(chunk_int as u8);
+        }
+    }
+
+    Some(output)
+}
+
 #[cfg(test)]
 mod tests {
     fn compare_encode(expected: &str, target: &[u8]) {
         assert_eq!(expected, super::encode(target));
+        assert_eq!(Some(target.to_vec()), super::decode(expected));
     }
 
     #[test]
@@ -143,6 +186,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_unpadded() {
+        assert_eq!(Some(b"foob".to_vec()), super::decode("Zm9vYg"));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(None, super::decode("Zm9vY!=="));
+        assert_eq!(None, super::decode("Zm9vY"));
+    }
+
     #[test]
     fn encode_all_bytes() {
         let mut bytes = Vec::<u8>::with_capacity(256);
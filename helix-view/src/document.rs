@@ -43,6 +43,10 @@ const DEFAULT_INDENT: IndentStyle = IndentStyle::Tabs;
 
 pub const DEFAULT_LANGUAGE_NAME: &str = "text";
 
+/// Documents larger than this are parsed for syntax highlighting on a
+/// background task rather than synchronously when the language is set.
+pub const SYNTAX_ASYNC_THRESHOLD: usize = 512 * 1024; // 512 KiB
+
 pub const SCRATCH_BUFFER_NAME: &str = "[scratch]";
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -178,6 +182,9 @@ pub struct Document {
     pub(crate) modified_since_accessed: bool,
 
     pub(crate) diagnostics: Vec<Diagnostic>,
+    /// Whether diagnostics are displayed for this buffer. Toggled at runtime
+    /// with `:diagnostics-toggle`; the diagnostics themselves are kept.
+    pub diagnostics_enabled: bool,
     pub(crate) language_servers: HashMap<LanguageServerName, Arc<Client>>,
 
     diff_handle: Option<DiffHandle>,
@@ -185,6 +192,10 @@ pub struct Document {
 
     // when document was used for most-recent-used buffer picker
     pub focused_at: std::time::Instant,
+
+    /// Advisory lock marker held while the file is open, so other
+    /// instances can detect concurrent edits. Removed on drop.
+    pub(crate) lock: Option<crate::lock::FileLock>,
 }
 
 /// Inlay hints for a single `(Document, View)` combo.
@@ -662,6 +673,7 @@ impl Document {
             changes,
             old_state,
             diagnostics: Vec::new(),
+            diagnostics_enabled: true,
             version: 0,
             history: Cell::new(History::default()),
             savepoints: Vec::new(),
@@ -673,6 +685,7 @@ impl Document {
             config,
             version_control_head: None,
             focused_at: std::time::Instant::now(),
+            lock: None,
         }
     }
 
@@ -692,10 +705,27 @@ impl Document {
         config: Arc<dyn DynAccess<Config>>,
     ) -> Result<Self, Error> {
         // Open the file if it exists, otherwise assume it is a new file (and thus empty).
-        let (rope, encoding, has_bom) = if path.exists() {
+        let contents = if path.exists() {
             let mut file =
                 std::fs::File::open(path).context(format!("unable to open {:?}", path))?;
-            from_reader(&mut file, encoding)?
+            Some(from_reader(&mut file, encoding)?)
+        } else {
+            None
+        };
+        Self::open_with(path, contents, encoding, config_loader, config)
+    }
+
+    /// Like [`Self::open`], but reuses `contents` if the file has already
+    /// been read, for example on a background task.
+    pub fn open_with(
+        path: &Path,
+        contents: Option<(Rope, &'static Encoding, bool)>,
+        encoding: Option<&'static Encoding>,
+        config_loader: Option<Arc<syntax::Loader>>,
+        config: Arc<dyn DynAccess<Config>>,
+    ) -> Result<Self, Error> {
+        let (rope, encoding, has_bom) = if let Some(contents) = contents {
+            contents
         } else {
             let line_ending: LineEnding = config.load().default_line_ending.into();
             let encoding = encoding.unwrap_or(encoding::UTF_8);
@@ -737,9 +767,21 @@ impl Document {
         {
             use std::process::Stdio;
             let text = self.text().clone();
+            // Expand `%{buffer_name}` in formatter args to the document path,
+            // so formatters like `prettier --stdin-filepath %{buffer_name}` can
+            // detect the language from the file name while reading from stdin.
+            let buffer_name = self
+                .path()
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let args: Vec<String> = formatter
+                .args
+                .iter()
+                .map(|arg| arg.replace("%{buffer_name}", &buffer_name))
+                .collect();
             let mut process = tokio::process::Command::new(&formatter.command);
             process
-                .args(&formatter.args)
+                .args(args)
                 .stdin(Stdio::piped())
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped());
@@ -1029,8 +1071,17 @@ impl Document {
         loader: Option<Arc<helix_core::syntax::Loader>>,
     ) {
         if let (Some(language_config), Some(loader)) = (language_config, loader) {
-            if let Some(highlight_config) = language_config.highlight_config(&loader.scopes()) {
-                self.syntax = Syntax::new(&self.text, highlight_config, loader);
+            // Large files are parsed on a background task through
+            // `Editor::launch_syntax_parse` instead, so the initial parse
+            // doesn't block the event loop; highlighting starts out off and
+            // kicks in once the parse completes.
+            if self.text.len_bytes() <= SYNTAX_ASYNC_THRESHOLD {
+                if let Some(highlight_config) = language_config.highlight_config(&loader.scopes())
+                {
+                    self.syntax = Syntax::new(&self.text, highlight_config, loader);
+                }
+            } else {
+                self.syntax = None;
             }
 
             self.language = Some(language_config);
@@ -1416,6 +1467,12 @@ impl Document {
         self.id
     }
 
+    /// When this document was last written out, used to detect external
+    /// modifications.
+    pub fn last_saved_time(&self) -> SystemTime {
+        self.last_saved_time
+    }
+
     /// If there are unsaved modifications.
     pub fn is_modified(&self) -> bool {
         let history = self.history.take();
@@ -1515,6 +1572,18 @@ impl Document {
         self.language_servers.remove(name)
     }
 
+    /// Close the document on all its language servers and detach them, so
+    /// that a following [`crate::Editor::refresh_language_servers`] call
+    /// reopens it, e.g. under a new path after a rename.
+    pub fn close_language_servers(&mut self) {
+        let Some(url) = self.url() else { return };
+        let identifier = lsp::TextDocumentIdentifier::new(url);
+        for language_server in self.language_servers.values() {
+            tokio::spawn(language_server.text_document_did_close(identifier.clone()));
+        }
+        self.language_servers.clear();
+    }
+
     pub fn language_servers_with_feature(
         &self,
         feature: LanguageServerFeature,
@@ -21,7 +21,7 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use std::{
     borrow::Cow,
     cell::Cell,
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, VecDeque},
     io::stdin,
     num::NonZeroUsize,
     path::{Path, PathBuf},
@@ -43,7 +43,7 @@ pub use helix_core::diagnostic::Severity;
 pub use helix_core::register::Registers;
 use helix_core::{
     auto_pairs::AutoPairs,
-    syntax::{self, AutoPairConfig, SoftWrap},
+    syntax::{self, AutoPairConfig, SoftWrap, Syntax},
     Change, LineEnding, NATIVE_LINE_ENDING,
 };
 use helix_core::{Position, Selection};
@@ -288,6 +288,60 @@ pub struct Config {
     pub workspace_lsp_roots: Vec<PathBuf>,
     /// Which line ending to choose for new documents. Defaults to `native`. i.e. `crlf` on Windows, otherwise `lf`.
     pub default_line_ending: LineEndingConfig,
+    /// Digraph mappings for the `insert_digraph` command, keyed by their
+    /// two-character mnemonic. Extends and overrides the built-in table.
+    pub digraphs: HashMap<String, String>,
+    /// Insert mode abbreviations, expanded when a non-word character is
+    /// typed right after them. Prefix an abbreviation with `\` to suppress
+    /// the expansion.
+    pub abbreviations: HashMap<String, String>,
+    /// Minimum severity of diagnostics to render inline after the end of
+    /// their line; lower severities only show in the gutter. `None` (the
+    /// default) disables end-of-line diagnostics.
+    pub end_of_line_diagnostics: Option<Severity>,
+    /// Minimum severity of diagnostics to mark in the gutter. Defaults to
+    /// `Hint`, i.e. all of them.
+    pub gutter_diagnostics: Severity,
+    /// Minimum severity of diagnostics to highlight inline in the text.
+    pub inline_diagnostics: Severity,
+    /// Minimum severity of diagnostics to count in the statusline elements.
+    pub statusline_diagnostics: Severity,
+    /// Additional directories `goto_file` resolves relative paths against,
+    /// after the document's directory and the workspace root.
+    pub goto_file_include_dirs: Vec<PathBuf>,
+    /// How goto commands (definition, references, ...) open the target
+    /// location: replace the current view or open a split. Defaults to
+    /// `replace`.
+    pub goto_action: GotoAction,
+    /// Whether inserting a newline inside a line comment (via `Enter`,
+    /// `o` or `O`) continues the comment on the new line. Defaults to `true`.
+    pub continue_comments: bool,
+    /// Keywords the `todo_picker` command scans the workspace for.
+    /// Defaults to `["TODO", "FIXME", "HACK", "XXX"]`.
+    pub todo_keywords: Vec<String>,
+    /// Regexes `:make` matches against every output line to extract
+    /// quickfix entries, tried in order. Named capture groups: `file`,
+    /// `line`, optionally `col` and `message`. The defaults cover
+    /// gcc/clang/rustc style `file:line:col: message` output.
+    pub quickfix_patterns: Vec<String>,
+    /// File/directory names that mark a workspace root (e.g. `.git`,
+    /// `Cargo.toml`, `go.mod`). Used when resolving the workspace for
+    /// pickers, workspace search and LSP root detection, and as the
+    /// fallback for languages without their own `roots`. Defaults to
+    /// `[".git", ".helix"]`.
+    pub roots: Vec<String>,
+    /// Whether the working directory should follow the project root of the
+    /// focused document, affecting the file picker, global search and shell
+    /// commands. Defaults to `false`.
+    pub auto_cd: bool,
+    /// Accessibility mode for terminal screen readers: suppresses decorative
+    /// drawing (indent guides, rulers, cursorline/cursorcolumn, color
+    /// swatches) and keeps the hardware cursor visible on the logical edit
+    /// point so the reader can follow it. Defaults to `false`.
+    pub screen_reader_mode: bool,
+    /// Whether to show a start screen with recent files, sessions and key
+    /// hints when launched without file arguments. Defaults to `true`.
+    pub dashboard: bool,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -383,6 +437,10 @@ pub struct SearchConfig {
     pub smart_case: bool,
     /// Whether the search should wrap after depleting the matches. Default to true.
     pub wrap_around: bool,
+    /// Treat the search pattern as a literal string instead of a regex. Defaults to false.
+    pub literal: bool,
+    /// Only match the pattern at word boundaries. Defaults to false.
+    pub whole_word: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -393,6 +451,9 @@ pub struct StatusLineConfig {
     pub right: Vec<StatusLineElement>,
     pub separator: String,
     pub mode: ModeConfig,
+    /// Draw a single statusline for the focused view at the bottom of the
+    /// terminal instead of one per split. Defaults to false.
+    pub global: bool,
 }
 
 impl Default for StatusLineConfig {
@@ -416,6 +477,7 @@ impl Default for StatusLineConfig {
             ],
             separator: String::from("│"),
             mode: ModeConfig::default(),
+            global: false,
         }
     }
 }
@@ -497,6 +559,13 @@ pub enum StatusLineElement {
 
     /// Indicator for selected register
     Register,
+
+    /// Spinner and progress for running background jobs
+    BackgroundJobs,
+
+    /// Work-done progress of the document's language servers, with
+    /// per-token percentages
+    LspProgress,
 }
 
 // Cursor shape is read and used on every rendered frame and so needs
@@ -819,6 +888,32 @@ impl Default for Config {
             completion_replace: false,
             workspace_lsp_roots: Vec::new(),
             default_line_ending: LineEndingConfig::default(),
+            digraphs: HashMap::new(),
+            abbreviations: HashMap::new(),
+            end_of_line_diagnostics: None,
+            gutter_diagnostics: Severity::Hint,
+            inline_diagnostics: Severity::Hint,
+            statusline_diagnostics: Severity::Hint,
+            goto_file_include_dirs: Vec::new(),
+            goto_action: GotoAction::default(),
+            continue_comments: true,
+            todo_keywords: ["TODO", "FIXME", "HACK", "XXX"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            quickfix_patterns: [
+                r"^(?P<file>[^\s:][^:]*):(?P<line>\d+):(?P<col>\d+):?\s*(?P<message>.*)$",
+                r"^(?P<file>[^\s:][^:]*):(?P<line>\d+):\s*(?P<message>.*)$",
+                // rustc-style ` --> file:line:col` spans
+                r"^\s*-->\s*(?P<file>\S+):(?P<line>\d+):(?P<col>\d+)",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            roots: vec![".git".to_string(), ".helix".to_string()],
+            auto_cd: false,
+            screen_reader_mode: false,
+            dashboard: true,
         }
     }
 }
@@ -828,6 +923,8 @@ impl Default for SearchConfig {
         Self {
             wrap_around: true,
             smart_case: true,
+            literal: false,
+            whole_word: false,
         }
     }
 }
@@ -859,6 +956,29 @@ pub struct Breakpoint {
 
 use futures_util::stream::{Flatten, Once};
 
+/// How long a status message is displayed before the next queued one is shown.
+pub const STATUS_MESSAGE_TIMEOUT: Duration = Duration::from_secs(8);
+/// How many messages `Editor::status_history` retains for `:messages`.
+const STATUS_HISTORY_MAX: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct StatusMessage {
+    pub message: Cow<'static, str>,
+    pub severity: Severity,
+    pub created: std::time::Instant,
+}
+
+/// One location parsed from compiler output by `:make`.
+#[derive(Debug, Clone)]
+pub struct QuickfixEntry {
+    pub path: PathBuf,
+    /// 0-indexed line.
+    pub line: usize,
+    /// 0-indexed column.
+    pub column: Option<usize>,
+    pub message: String,
+}
+
 pub struct Editor {
     /// Current editing mode.
     pub mode: Mode,
@@ -878,6 +998,7 @@ pub struct Editor {
     pub macro_recording: Option<(char, Vec<KeyEvent>)>,
     pub macro_replaying: Vec<char>,
     pub language_servers: helix_lsp::Registry,
+    pub lsp_progress: helix_lsp::LspProgressMap,
     pub diagnostics: BTreeMap<lsp::Url, Vec<(lsp::Diagnostic, usize)>>,
     pub diff_providers: DiffProviderRegistry,
 
@@ -901,7 +1022,39 @@ pub struct Editor {
     /// confirmed.
     pub last_selection: Option<Selection>,
 
-    pub status_msg: Option<(Cow<'static, str>, Severity)>,
+    /// The active search pattern. While set, all matches within the viewport
+    /// are highlighted with the `ui.search.match` theme scope. Cleared with
+    /// `:clear-search-highlight`.
+    pub search_matches: Option<helix_core::regex::Regex>,
+
+    /// The char range of the match the last search landed on after wrapping
+    /// around the document, highlighted with `ui.search.wrapped` to make the
+    /// wrap visible. Reset by the next search.
+    pub search_wrapped_match: Option<std::ops::Range<usize>>,
+
+    /// File-open history used to rank file picker results, see
+    /// [`crate::frecency`].
+    pub frecency: crate::frecency::Frecency,
+
+    /// The scratch buffer `:tree-sitter-subtree` renders into, so repeated
+    /// invocations update it in place instead of opening a new split.
+    pub subtree_doc: Option<DocumentId>,
+
+    /// The scratch buffer opened from the prompt with `Ctrl-o`, together with
+    /// the history register its contents came from. `Enter` in normal mode
+    /// submits the cursor line as the prompt input.
+    pub command_line_doc: Option<(DocumentId, char)>,
+
+    /// Pending status messages, oldest first. The front one is displayed
+    /// until it is dismissed or times out, revealing the next.
+    pub status_msgs: VecDeque<StatusMessage>,
+    /// Every status message pushed this session, for `:messages`.
+    pub status_history: Vec<StatusMessage>,
+    /// Locations parsed out of the last `:make` run, navigated with
+    /// `]q`/`[q` and the quickfix picker.
+    pub quickfix: Vec<QuickfixEntry>,
+    /// The quickfix entry last jumped to, if any.
+    pub quickfix_index: Option<usize>,
     pub autoinfo: Option<Info>,
 
     pub config: Arc<dyn DynAccess<Config>>,
@@ -915,6 +1068,12 @@ pub struct Editor {
     pub exit_code: i32,
 
     pub config_events: (UnboundedSender<ConfigEvent>, UnboundedReceiver<ConfigEvent>),
+    /// Completed background syntax parses for large documents, see
+    /// [`Self::launch_syntax_parse`].
+    pub syntax_results: (
+        UnboundedSender<SyntaxParseResult>,
+        UnboundedReceiver<SyntaxParseResult>,
+    ),
     /// Allows asynchronous tasks to control the rendering
     /// The `Notify` allows asynchronous tasks to request the editor to perform a redraw
     /// The `RwLock` blocks the editor from performing the render until an exclusive lock can be acquired
@@ -951,9 +1110,28 @@ pub enum EditorEvent {
     ConfigEvent(ConfigEvent),
     LanguageServerMessage((usize, Call)),
     DebuggerEvent(dap::Payload),
+    SyntaxParsed(SyntaxParseResult),
     IdleTimer,
 }
 
+/// The result of a background syntax parse of a (large) document.
+pub struct SyntaxParseResult {
+    pub doc_id: DocumentId,
+    /// The document revision the parse was started from; stale results are
+    /// discarded and the parse restarted.
+    pub revision: usize,
+    pub syntax: Option<Syntax>,
+}
+
+impl std::fmt::Debug for SyntaxParseResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyntaxParseResult")
+            .field("doc_id", &self.doc_id)
+            .field("revision", &self.revision)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ConfigEvent {
     Refresh,
@@ -984,6 +1162,27 @@ pub enum Action {
     VerticalSplit,
 }
 
+/// Configurable default for how goto commands open their target, a
+/// serializable subset of [`Action`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GotoAction {
+    #[default]
+    Replace,
+    HorizontalSplit,
+    VerticalSplit,
+}
+
+impl From<GotoAction> for Action {
+    fn from(action: GotoAction) -> Self {
+        match action {
+            GotoAction::Replace => Action::Replace,
+            GotoAction::HorizontalSplit => Action::HorizontalSplit,
+            GotoAction::VerticalSplit => Action::VerticalSplit,
+        }
+    }
+}
+
 /// Error thrown on failed document closed
 pub enum CloseError {
     /// Document doesn't exist
@@ -1004,6 +1203,7 @@ impl Editor {
         let language_servers = helix_lsp::Registry::new(syn_loader.clone());
         let conf = config.load();
         let auto_pairs = (&conf.auto_pairs).into();
+        helix_loader::set_workspace_markers(conf.roots.clone());
 
         // HAXX: offset the render area height by 1 to account for prompt/commandline
         area.height -= 1;
@@ -1022,6 +1222,7 @@ impl Editor {
             macro_replaying: Vec::new(),
             theme: theme_loader.default(),
             language_servers,
+            lsp_progress: helix_lsp::LspProgressMap::new(),
             diagnostics: BTreeMap::new(),
             diff_providers: DiffProviderRegistry::default(),
             debugger: None,
@@ -1031,9 +1232,17 @@ impl Editor {
             theme_loader,
             last_theme: None,
             last_selection: None,
+            search_matches: None,
+            search_wrapped_match: None,
+            frecency: crate::frecency::Frecency::default(),
+            subtree_doc: None,
+            command_line_doc: None,
             registers: Registers::default(),
             clipboard_provider: get_clipboard_provider(),
-            status_msg: None,
+            status_msgs: VecDeque::new(),
+            status_history: Vec::new(),
+            quickfix: Vec::new(),
+            quickfix_index: None,
             autoinfo: None,
             idle_timer: Box::pin(sleep(conf.idle_timeout)),
             last_motion: None,
@@ -1042,6 +1251,7 @@ impl Editor {
             auto_pairs,
             exit_code: 0,
             config_events: unbounded_channel(),
+            syntax_results: unbounded_channel(),
             redraw_handle: Default::default(),
             needs_redraw: false,
             cursor_cache: Cell::new(None),
@@ -1063,6 +1273,7 @@ impl Editor {
     pub fn refresh_config(&mut self) {
         let config = self.config();
         self.auto_pairs = (&config.auto_pairs).into();
+        helix_loader::set_workspace_markers(config.roots.clone());
         self.reset_idle_timer();
         self._refresh();
     }
@@ -1081,35 +1292,92 @@ impl Editor {
             .reset(Instant::now() + config.idle_timeout);
     }
 
+    /// Reads the given register, resolving the read-only special registers:
+    /// `%` contains the current document's name and `.` the contents of every
+    /// selection. All other names read from the register file.
+    pub fn read_register(&self, name: char) -> Option<Vec<String>> {
+        match name {
+            '%' => {
+                let doc = doc!(self);
+                Some(vec![doc.display_name().into_owned()])
+            }
+            '.' => {
+                let (view, doc) = current_ref!(self);
+                let text = doc.text().slice(..);
+                Some(
+                    doc.selection(view.id)
+                        .fragments(text)
+                        .map(Cow::into_owned)
+                        .collect(),
+                )
+            }
+            _ => self.registers.read(name).map(<[String]>::to_vec),
+        }
+    }
+
     pub fn clear_status(&mut self) {
-        self.status_msg = None;
+        self.status_msgs.clear();
+    }
+
+    /// Dismisses the currently displayed status message, revealing the next
+    /// queued one (if any).
+    pub fn dismiss_status(&mut self) {
+        self.status_msgs.pop_front();
+    }
+
+    /// Drops displayed messages that are older than [`STATUS_MESSAGE_TIMEOUT`].
+    pub fn expire_status(&mut self) {
+        while self
+            .status_msgs
+            .front()
+            .map(|msg| msg.created.elapsed() > STATUS_MESSAGE_TIMEOUT)
+            .unwrap_or(false)
+        {
+            self.status_msgs.pop_front();
+        }
+    }
+
+    fn push_status(&mut self, message: Cow<'static, str>, severity: Severity) {
+        let msg = StatusMessage {
+            message,
+            severity,
+            created: std::time::Instant::now(),
+        };
+        if self.status_history.len() == STATUS_HISTORY_MAX {
+            self.status_history.remove(0);
+        }
+        self.status_history.push(msg.clone());
+        self.status_msgs.push_back(msg);
     }
 
     #[inline]
     pub fn set_status<T: Into<Cow<'static, str>>>(&mut self, status: T) {
         let status = status.into();
         log::debug!("editor status: {}", status);
-        self.status_msg = Some((status, Severity::Info));
+        self.push_status(status, Severity::Info);
     }
 
     #[inline]
     pub fn set_error<T: Into<Cow<'static, str>>>(&mut self, error: T) {
         let error = error.into();
         log::error!("editor error: {}", error);
-        self.status_msg = Some((error, Severity::Error));
+        self.push_status(error, Severity::Error);
     }
 
+    /// The most recently pushed status message.
     #[inline]
     pub fn get_status(&self) -> Option<(&Cow<'static, str>, &Severity)> {
-        self.status_msg.as_ref().map(|(status, sev)| (status, sev))
+        self.status_msgs
+            .back()
+            .map(|msg| (&msg.message, &msg.severity))
     }
 
     /// Returns true if the current status is an error
     #[inline]
     pub fn is_err(&self) -> bool {
-        self.status_msg
-            .as_ref()
-            .map(|(_, sev)| *sev == Severity::Error)
+        self.status_msgs
+            .back()
+            .map(|msg| msg.severity == Severity::Error)
             .unwrap_or(false)
     }
 
@@ -1321,6 +1589,7 @@ impl Editor {
                 }
 
                 self.replace_document_in_view(view_id, id);
+                self.sync_cwd();
 
                 return;
             }
@@ -1355,6 +1624,7 @@ impl Editor {
         }
 
         self._refresh();
+        self.sync_cwd();
     }
 
     /// Generate an id for a new document and register it.
@@ -1406,14 +1676,26 @@ impl Editor {
 
     // ??? possible use for integration tests
     pub fn open(&mut self, path: &Path, action: Action) -> Result<DocumentId, Error> {
+        self.open_with_contents(path, action, None)
+    }
+
+    /// Like [`Self::open`], but reuses `contents` if the file has already
+    /// been read, for example on a background task.
+    pub fn open_with_contents(
+        &mut self,
+        path: &Path,
+        action: Action,
+        contents: Option<(helix_core::Rope, &'static helix_core::encoding::Encoding, bool)>,
+    ) -> Result<DocumentId, Error> {
         let path = helix_core::path::get_canonicalized_path(path)?;
         let id = self.document_by_path(&path).map(|doc| doc.id);
 
         let id = if let Some(id) = id {
             id
         } else {
-            let mut doc = Document::open(
+            let mut doc = Document::open_with(
                 &path,
+                contents,
                 None,
                 Some(self.syn_loader.clone()),
                 self.config.clone(),
@@ -1424,16 +1706,78 @@ impl Editor {
             }
             doc.set_version_control_head(self.diff_providers.get_current_head_name(&path));
 
+            let (lock, conflict) = crate::lock::acquire(&path);
+            doc.lock = lock;
+            match conflict {
+                Some(crate::lock::Conflict::Live { pid }) => self.set_error(format!(
+                    "{} is already open in another helix instance (pid {}); edits may overwrite each other, consider closing it there first",
+                    path.display(),
+                    pid
+                )),
+                Some(crate::lock::Conflict::Crashed) => self.set_error(format!(
+                    "{} was open in a session that did not exit cleanly; unsaved changes from that session are lost",
+                    path.display()
+                )),
+                None => (),
+            }
+
             let id = self.new_document(doc);
             let _ = self.launch_language_servers(id);
+            self.launch_syntax_parse(id);
 
             id
         };
 
+        self.frecency.visit(path);
         self.switch(id, action);
         Ok(id)
     }
 
+    /// Parse a document whose initial syntax parse was skipped because of
+    /// its size (see [`crate::document::SYNTAX_ASYNC_THRESHOLD`]) on a
+    /// background task. The result is delivered through
+    /// [`EditorEvent::SyntaxParsed`].
+    pub fn launch_syntax_parse(&mut self, doc_id: DocumentId) {
+        let loader = self.syn_loader.clone();
+        let Some(doc) = self.documents.get_mut(&doc_id) else { return };
+        if doc.syntax().is_some() {
+            return;
+        }
+        let Some(highlight_config) = doc
+            .language
+            .as_ref()
+            .and_then(|language| language.highlight_config(&loader.scopes()))
+        else {
+            return;
+        };
+        let text = doc.text().clone();
+        let revision = doc.get_current_revision();
+        let sender = self.syntax_results.0.clone();
+        tokio::task::spawn_blocking(move || {
+            let syntax = Syntax::new(&text, highlight_config, loader);
+            let _ = sender.send(SyntaxParseResult {
+                doc_id,
+                revision,
+                syntax,
+            });
+        });
+    }
+
+    /// Apply the result of a background syntax parse. Results for documents
+    /// that were edited in the meantime are discarded and the parse is
+    /// restarted from the current text.
+    pub fn handle_syntax_parse_result(&mut self, result: SyntaxParseResult) {
+        let Some(doc) = self.documents.get_mut(&result.doc_id) else { return };
+        if doc.syntax().is_some() {
+            return;
+        }
+        if doc.get_current_revision() != result.revision {
+            self.launch_syntax_parse(result.doc_id);
+            return;
+        }
+        doc.syntax = result.syntax;
+    }
+
     pub fn close(&mut self, id: ViewId) {
         // Remove selections for the closed view on all documents.
         for doc in self.documents_mut() {
@@ -1571,6 +1915,30 @@ impl Editor {
         let view = view!(self, view_id);
         let doc = doc_mut!(self, &view.doc);
         doc.mark_as_focused();
+        self.sync_cwd();
+    }
+
+    /// When `auto-cd` is enabled, make the working directory follow the
+    /// project root of the focused document.
+    fn sync_cwd(&mut self) {
+        if !self.config().auto_cd {
+            return;
+        }
+        let doc = doc!(self);
+        let Some(dir) = doc.path().and_then(|path| path.parent()) else {
+            return;
+        };
+        let (root, _) = helix_loader::find_workspace_in(dir);
+        if std::env::current_dir().ok().as_deref() == Some(&root) {
+            return;
+        }
+        if let Err(err) = std::env::set_current_dir(&root) {
+            log::error!(
+                "couldn't change the working directory to {}: {}",
+                root.display(),
+                err
+            );
+        }
     }
 
     pub fn focus_next(&mut self) {
@@ -1654,7 +2022,13 @@ impl Editor {
             let inner = view.inner_area(doc);
             pos.col += inner.x as usize;
             pos.row += inner.y as usize;
-            let cursorkind = config.cursor_shape.from_mode(self.mode);
+            let cursorkind = if config.screen_reader_mode {
+                // terminal screen readers follow the hardware cursor, never
+                // hide or restyle it
+                CursorKind::Block
+            } else {
+                config.cursor_shape.from_mode(self.mode)
+            };
             (Some(pos), cursorkind)
         } else {
             (None, CursorKind::default())
@@ -1693,6 +2067,9 @@ impl Editor {
                 Some(config_event) = self.config_events.1.recv() => {
                     return EditorEvent::ConfigEvent(config_event)
                 }
+                Some(parse_result) = self.syntax_results.1.recv() => {
+                    return EditorEvent::SyntaxParsed(parse_result)
+                }
                 Some(message) = self.language_servers.incoming.next() => {
                     return EditorEvent::LanguageServerMessage(message)
                 }
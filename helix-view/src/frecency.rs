@@ -0,0 +1,130 @@
+//! File-open history used to boost recently and frequently opened paths in
+//! the file picker.
+//!
+//! Paths are stored canonicalized, so every workspace ranks its own files
+//! independently. The history is loaded and saved by the application, see
+//! [`Frecency::load`] and [`Frecency::save`]; visits are only recorded in
+//! memory until then.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Maximum number of paths kept when saving, ordered by score.
+const MAX_PERSISTED_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    /// How often the path has been opened.
+    count: u32,
+    /// Unix timestamp of the last open.
+    last_access: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct Frecency {
+    entries: HashMap<PathBuf, Entry>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn file_path() -> PathBuf {
+    helix_loader::state_dir().join("frecency")
+}
+
+impl Frecency {
+    /// Record that `path` was opened. Only updates the in-memory state,
+    /// persisting happens on exit via [`Self::save`].
+    pub fn visit(&mut self, path: PathBuf) {
+        let entry = self.entries.entry(path).or_insert(Entry {
+            count: 0,
+            last_access: 0,
+        });
+        entry.count = entry.count.saturating_add(1);
+        entry.last_access = now();
+    }
+
+    /// Ranking score for `path`: the open count weighted by how recently the
+    /// path was last opened. Unknown paths score zero.
+    pub fn score(&self, path: &Path) -> u64 {
+        let entry = match self.entries.get(path) {
+            Some(entry) => entry,
+            None => return 0,
+        };
+        let age = now().saturating_sub(entry.last_access);
+        let weight = match age {
+            ..=3_600 => 8,         // within the hour
+            ..=86_400 => 4,        // within the day
+            ..=604_800 => 2,       // within the week
+            _ => 1,
+        };
+        u64::from(entry.count) * weight
+    }
+
+    /// Known paths ordered from most to least recently opened, optionally
+    /// limited to those under `scope`.
+    pub fn recent_paths(&self, scope: Option<&Path>) -> Vec<PathBuf> {
+        let mut entries: Vec<(&PathBuf, &Entry)> = self
+            .entries
+            .iter()
+            .filter(|(path, _)| match scope {
+                Some(scope) => path.starts_with(scope),
+                None => true,
+            })
+            .collect();
+        entries.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.last_access));
+        entries.into_iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    /// Load the persisted history, merging it into the in-memory state.
+    /// Lines are `<count>\t<last-access>\t<path>`, unparsable lines are
+    /// skipped so a corrupt file degrades gracefully.
+    pub fn load(&mut self) {
+        let contents = match fs::read_to_string(file_path()) {
+            Ok(contents) => contents,
+            Err(_) => return,
+        };
+        for line in contents.lines() {
+            let mut fields = line.splitn(3, '\t');
+            let entry = (|| {
+                let count = fields.next()?.parse().ok()?;
+                let last_access = fields.next()?.parse().ok()?;
+                let path = PathBuf::from(fields.next()?);
+                Some((path, Entry { count, last_access }))
+            })();
+            if let Some((path, entry)) = entry {
+                self.entries.entry(path).or_insert(entry);
+            }
+        }
+    }
+
+    /// Persist the history, keeping only the highest scoring paths.
+    pub fn save(&self) {
+        let mut entries: Vec<(&PathBuf, &Entry)> = self.entries.iter().collect();
+        entries.sort_by_key(|(path, _)| std::cmp::Reverse(self.score(path)));
+        entries.truncate(MAX_PERSISTED_ENTRIES);
+
+        let path = file_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let contents: String = entries
+            .iter()
+            .map(|(path, entry)| {
+                format!(
+                    "{}\t{}\t{}\n",
+                    entry.count,
+                    entry.last_access,
+                    path.display()
+                )
+            })
+            .collect();
+        let _ = fs::write(path, contents);
+    }
+}
@@ -47,7 +47,7 @@ impl GutterType {
 }
 
 pub fn diagnostic<'doc>(
-    _editor: &'doc Editor,
+    editor: &'doc Editor,
     doc: &'doc Document,
     _view: &View,
     theme: &Theme,
@@ -58,10 +58,12 @@ pub fn diagnostic<'doc>(
     let info = theme.get("info");
     let hint = theme.get("hint");
     let diagnostics = &doc.diagnostics;
+    let enabled = doc.diagnostics_enabled;
+    let min_severity = editor.config().gutter_diagnostics;
 
     Box::new(
         move |line: usize, _selected: bool, first_visual_line: bool, out: &mut String| {
-            if !first_visual_line {
+            if !first_visual_line || !enabled {
                 return None;
             }
             use helix_core::diagnostic::Severity;
@@ -73,7 +75,8 @@ pub fn diagnostic<'doc>(
                         && doc
                             .language_servers_with_feature(LanguageServerFeature::Diagnostics)
                             .any(|ls| ls.id() == d.language_server_id)
-                });
+                })
+                .filter(|d| d.severity.unwrap_or_default() >= min_severity);
             diagnostics_on_line.max_by_key(|d| d.severity).map(|d| {
                 write!(out, "●").ok();
                 match d.severity {
@@ -5,6 +5,7 @@ pub mod clipboard;
 pub mod document;
 pub mod editor;
 pub mod env;
+pub mod frecency;
 pub mod graphics;
 pub mod gutter;
 pub mod handlers {
@@ -15,6 +16,7 @@ pub mod base64;
 pub mod info;
 pub mod input;
 pub mod keyboard;
+pub mod lock;
 pub mod theme;
 pub mod tree;
 pub mod view;
@@ -0,0 +1,82 @@
+//! Advisory lock markers ("swap files") for open documents.
+//!
+//! When a document is opened, a marker recording our process id is written
+//! to the state directory and removed again when the document closes. An
+//! already existing marker means the file is either open in another
+//! running instance or was open in a session that crashed, so the editor
+//! warns instead of letting two sessions silently overwrite each other.
+//! The locks are advisory only: opening always proceeds.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Why a file could not be locked cleanly.
+pub enum Conflict {
+    /// Another running process holds the lock.
+    Live { pid: u32 },
+    /// A crashed session left a stale marker behind; it has been replaced.
+    Crashed,
+}
+
+/// A held lock marker, removed again when the document closes.
+pub struct FileLock {
+    marker: PathBuf,
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.marker);
+    }
+}
+
+fn lock_dir() -> PathBuf {
+    helix_loader::state_dir().join("locks")
+}
+
+/// Markers are named by a hash of the canonicalized path so they never
+/// litter the edited file's directory. The marker records
+/// `<pid>\n<path>` for inspection.
+fn marker_path(path: &Path) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    lock_dir().join(format!("{:016x}.lock", hasher.finish()))
+}
+
+fn read_owner(marker: &Path) -> Option<u32> {
+    let contents = std::fs::read_to_string(marker).ok()?;
+    contents.lines().next()?.parse().ok()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    // signal 0 performs error checking without delivering a signal
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    // no cheap liveness check; err on the side of warning
+    true
+}
+
+/// Tries to create a lock marker for `path`. Failing to write the marker
+/// never blocks editing, so this returns whatever lock could be taken
+/// alongside an eventual conflict to report to the user.
+pub fn acquire(path: &Path) -> (Option<FileLock>, Option<Conflict>) {
+    let marker = marker_path(path);
+    let conflict = match read_owner(&marker) {
+        Some(pid) if pid != std::process::id() && process_alive(pid) => {
+            return (None, Some(Conflict::Live { pid }));
+        }
+        Some(pid) if pid != std::process::id() => Some(Conflict::Crashed),
+        _ => None,
+    };
+
+    let _ = std::fs::create_dir_all(lock_dir());
+    let contents = format!("{}\n{}\n", std::process::id(), path.display());
+    let lock = std::fs::write(&marker, contents)
+        .is_ok()
+        .then_some(FileLock { marker });
+    (lock, conflict)
+}
@@ -52,6 +52,17 @@ pub enum Layout {
     // could explore stacked/tabbed
 }
 
+/// A description of the split structure of a [`Tree`], used for session
+/// persistence.
+#[derive(Debug, Clone)]
+pub enum ViewLayout {
+    Split {
+        layout: Layout,
+        children: Vec<ViewLayout>,
+    },
+    View(ViewId),
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Direction {
     Up,
@@ -257,6 +268,25 @@ impl Tree {
         })
     }
 
+    /// Describe the current split structure as a [`ViewLayout`].
+    pub fn layout(&self) -> ViewLayout {
+        self.layout_node(self.root)
+    }
+
+    fn layout_node(&self, index: ViewId) -> ViewLayout {
+        match &self.nodes[index].content {
+            Content::View(view) => ViewLayout::View(view.id),
+            Content::Container(container) => ViewLayout::Split {
+                layout: container.layout,
+                children: container
+                    .children
+                    .iter()
+                    .map(|child| self.layout_node(*child))
+                    .collect(),
+            },
+        }
+    }
+
     pub fn views_mut(&mut self) -> impl Iterator<Item = (&mut View, bool)> {
         let focus = self.focus;
         self.nodes
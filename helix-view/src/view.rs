@@ -80,7 +80,7 @@ impl JumpList {
         self.jumps.retain(|(other_id, _)| other_id != doc_id);
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &Jump> {
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Jump> {
         self.jumps.iter()
     }
 
@@ -126,6 +126,13 @@ pub struct View {
     pub object_selections: Vec<Selection>,
     /// all gutter-related configuration settings, used primarily for gutter rendering
     pub gutters: GutterConfig,
+    /// Whether this view follows the scrolling of other scroll-bound views,
+    /// toggled with `:scroll-bind`. Useful for diff or translation pairs.
+    pub scroll_bind: bool,
+    /// Whether the view reserves its bottom line for a statusline. Disabled
+    /// for views at the bottom of the editor area when the global statusline
+    /// is in use, reclaiming the line for text.
+    pub statusline: bool,
     /// A mapping between documents and the last history revision the view was updated at.
     /// Changes between documents and views are synced lazily when switching windows. This
     /// mapping keeps track of the last applied history revision so that only new changes
@@ -159,6 +166,8 @@ impl View {
             last_modified_docs: [None, None],
             object_selections: Vec::new(),
             gutters,
+            scroll_bind: false,
+            statusline: true,
             doc_revisions: HashMap::new(),
         }
     }
@@ -171,11 +180,13 @@ impl View {
     }
 
     pub fn inner_area(&self, doc: &Document) -> Rect {
-        self.area.clip_left(self.gutter_offset(doc)).clip_bottom(1) // -1 for statusline
+        self.area
+            .clip_left(self.gutter_offset(doc))
+            .clip_bottom(self.statusline as u16) // -1 for statusline
     }
 
     pub fn inner_height(&self) -> usize {
-        self.area.clip_bottom(1).height.into() // -1 for statusline
+        self.area.clip_bottom(self.statusline as u16).height.into() // -1 for statusline
     }
 
     pub fn inner_width(&self, doc: &Document) -> u16 {